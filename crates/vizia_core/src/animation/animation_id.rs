@@ -4,6 +4,7 @@ use vizia_id::{
 };
 
 use crate::context::EventContext;
+use crate::entity::Entity;
 
 /// An ID used to reference style animations stored in the style store.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,6 +16,11 @@ impl_generational_id!(Animation);
 pub trait AnimId {
     /// Returns the animation associated with the id.
     fn get(&self, cx: &EventContext) -> Option<Animation>;
+
+    /// Returns the animation associated with the id when played on the given view.
+    fn get_for(&self, cx: &EventContext, _entity: Entity) -> Option<Animation> {
+        self.get(cx)
+    }
 }
 
 impl AnimId for Animation {
@@ -25,6 +31,11 @@ impl AnimId for Animation {
 
 impl AnimId for &'static str {
     fn get(&self, cx: &EventContext) -> Option<Animation> {
-        cx.style.get_animation(self).copied()
+        self.get_for(cx, cx.current)
+    }
+
+    // `@keyframes` nested within a style rule take precedence for the views matching the rule.
+    fn get_for(&self, cx: &EventContext, entity: Entity) -> Option<Animation> {
+        cx.style.get_animation_for(cx.tree, entity, self)
     }
 }
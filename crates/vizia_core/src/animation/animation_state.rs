@@ -54,7 +54,7 @@ where
     pub(crate) fn new(id: Animation) -> Self {
         AnimationState {
             id,
-            start_time: Instant::now(),
+            start_time: crate::context::now(),
             duration: Duration::new(0, 0),
             delay: Duration::new(0, 0),
             keyframes: Vec::new(),
@@ -94,7 +94,7 @@ where
     pub(crate) fn play(&mut self, entity: Entity) {
         self.active = true;
         self.t = 0.0;
-        self.start_time = Instant::now();
+        self.start_time = crate::context::now();
         self.entities.insert(entity);
     }
 
@@ -110,7 +110,7 @@ where
     fn default() -> Self {
         AnimationState {
             id: Animation::null(),
-            start_time: Instant::now(),
+            start_time: crate::context::now(),
             duration: Duration::new(0, 0),
             delay: Duration::new(0, 0),
             keyframes: Vec::new(),
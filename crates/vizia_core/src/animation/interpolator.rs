@@ -1,8 +1,9 @@
 use morphorm::Units;
 use vizia_style::{
     Angle, BackgroundSize, ClipPath, Color, ColorStop, Display, Filter, FontSize, Gradient, Length,
-    LengthOrPercentage, LengthPercentageOrAuto, LengthValue, LineDirection, LinearGradient,
-    Opacity, PercentageOrNumber, Rect, Scale, Shadow, Transform, Translate, RGBA,
+    LengthOrPercentage, LengthPercentageOrAuto, LengthValue, LineDirection, LineHeight,
+    LinearGradient, Opacity, PercentageOrNumber, Rect, Scale, Shadow, TextSpacing, Transform,
+    Translate, RGBA,
 };
 
 use skia_safe::Matrix;
@@ -93,6 +94,31 @@ impl Interpolator for Filter {
             (Filter::Blur(start), Filter::Blur(end)) => {
                 Filter::Blur(Length::interpolate(start, end, t))
             }
+            (Filter::Brightness(start), Filter::Brightness(end)) => {
+                Filter::Brightness(f32::interpolate(start, end, t))
+            }
+            (Filter::Contrast(start), Filter::Contrast(end)) => {
+                Filter::Contrast(f32::interpolate(start, end, t))
+            }
+            (Filter::Grayscale(start), Filter::Grayscale(end)) => {
+                Filter::Grayscale(f32::interpolate(start, end, t))
+            }
+            (Filter::Saturate(start), Filter::Saturate(end)) => {
+                Filter::Saturate(f32::interpolate(start, end, t))
+            }
+            (Filter::HueRotate(start), Filter::HueRotate(end)) => {
+                Filter::HueRotate(Angle::interpolate(start, end, t))
+            }
+            (Filter::Invert(start), Filter::Invert(end)) => {
+                Filter::Invert(f32::interpolate(start, end, t))
+            }
+            (Filter::Sepia(start), Filter::Sepia(end)) => {
+                Filter::Sepia(f32::interpolate(start, end, t))
+            }
+            (Filter::DropShadow(start), Filter::DropShadow(end)) => {
+                Filter::DropShadow(Shadow::interpolate(start, end, t))
+            }
+            _ => end.clone(),
         }
     }
 }
@@ -306,6 +332,8 @@ impl Interpolator for LinearGradient {
                         }
                     })
                     .collect::<Vec<_>>(),
+                interpolation: end.interpolation,
+                repeating: end.repeating,
             }
         } else {
             end.clone()
@@ -343,6 +371,28 @@ impl Interpolator for FontSize {
     }
 }
 
+impl Interpolator for LineHeight {
+    fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+        match (start, end) {
+            (LineHeight::Number(start_val), LineHeight::Number(end_val)) => {
+                LineHeight::Number(f32::interpolate(start_val, end_val, t))
+            }
+
+            (LineHeight::Length(start_val), LineHeight::Length(end_val)) => {
+                LineHeight::Length(LengthOrPercentage::interpolate(start_val, end_val, t))
+            }
+
+            _ => end.clone(),
+        }
+    }
+}
+
+impl Interpolator for TextSpacing {
+    fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
+        TextSpacing::Length(Length::px(f32::interpolate(&start.to_px(), &end.to_px(), t)))
+    }
+}
+
 impl<T: Interpolator> Interpolator for Rect<T> {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
         Rect(
@@ -358,6 +408,25 @@ impl Interpolator for ClipPath {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
         match (start, end) {
             (ClipPath::Shape(s), ClipPath::Shape(e)) => ClipPath::Shape(Rect::interpolate(s, e, t)),
+            (ClipPath::Inset(s, sr), ClipPath::Inset(e, er)) => {
+                ClipPath::Inset(Rect::interpolate(s, e, t), Rect::interpolate(sr, er, t))
+            }
+            (ClipPath::Circle(Some(s), _), ClipPath::Circle(Some(e), center)) => {
+                ClipPath::Circle(Some(LengthOrPercentage::interpolate(s, e, t)), center.clone())
+            }
+            (ClipPath::Polygon(s), ClipPath::Polygon(e)) if s.len() == e.len() => {
+                ClipPath::Polygon(
+                    s.iter()
+                        .zip(e.iter())
+                        .map(|((sx, sy), (ex, ey))| {
+                            (
+                                LengthOrPercentage::interpolate(sx, ex, t),
+                                LengthOrPercentage::interpolate(sy, ey, t),
+                            )
+                        })
+                        .collect(),
+                )
+            }
             _ => end.clone(),
         }
     }
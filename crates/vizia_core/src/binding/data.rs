@@ -79,6 +79,9 @@ impl_data_simple!(LengthOrPercentage);
 impl_data_simple!(CornerShape);
 impl_data_simple!(Shadow);
 impl_data_simple!(TextDecorationLine);
+impl_data_simple!(LineHeight);
+impl_data_simple!(TextSpacing);
+impl_data_simple!(Direction);
 
 impl Data for &'static str {
     fn same(&self, other: &Self) -> bool {
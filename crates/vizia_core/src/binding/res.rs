@@ -125,6 +125,8 @@ impl_res_simple!(TextOverflow);
 impl_res_simple!(LineClamp);
 impl_res_clone!(Shadow);
 impl_res_clone!(LinearGradientBuilder);
+impl_res_clone!(RadialGradientBuilder);
+impl_res_clone!(ConicGradientBuilder);
 impl_res_clone!(ShadowBuilder);
 impl_res_simple!(FontVariation);
 impl_res_clone!(Filter);
@@ -140,10 +142,13 @@ impl_res_clone!(FamilyOwned);
 impl_res_simple!(TextDecorationLine);
 impl_res_clone!(TextStroke);
 impl_res_clone!(TextStrokeStyle);
+impl_res_clone!(LineHeight);
+impl_res_clone!(TextSpacing);
 impl_res_simple!(Alignment);
 impl_res_simple!(WindowPosition);
 impl_res_simple!(Anchor);
 impl_res_simple!(AnchorTarget);
+impl_res_simple!(Direction);
 
 impl<'i> ResGet<FontFamily<'i>> for FontFamily<'i> {
     fn get_ref<'a>(&'a self, _: &'a impl DataContext) -> Option<LensValue<'a, Self>> {
@@ -102,6 +102,16 @@ impl BackendContext {
         // self.set_window_position(window_entity, physical_x, physical_y);
     }
 
+    /// Restricts text rendering to fonts added with [`Context::add_font_mem`], ignoring any fonts installed on the system.
+    ///
+    /// This is useful for rendering text identically across machines.
+    pub fn disable_system_fonts(&mut self) {
+        let font_collection = &mut self.0.text_context.font_collection;
+        font_collection.set_default_font_manager(None::<skia_safe::FontMgr>, None);
+        font_collection.disable_font_fallback();
+        font_collection.clear_caches();
+    }
+
     /// Returns a reference to the [`Environment`] model.
     pub fn environment(&self) -> &Environment {
         self.0.data::<Environment>().unwrap()
@@ -122,6 +132,11 @@ impl BackendContext {
         draw_system(&mut self.0, window_entity, surface, dirty_surface)
     }
 
+    /// Draws a view and its descendants onto a canvas, without the views behind or in front of it.
+    pub fn draw_subtree(&mut self, entity: Entity, canvas: &Canvas) {
+        draw_subtree_system(&mut self.0, entity, canvas);
+    }
+
     /// Set the current entity. This is useful in user code when you're performing black magic and
     /// want to trick other parts of the code into thinking you're processing some other part of the
     /// tree.
@@ -203,6 +218,11 @@ impl BackendContext {
 
     /// Calls the style system to match entities with shared styles.
     pub fn process_style_updates(&mut self) {
+        media_query_system(&mut self.0);
+
+        // Generated content views are added before styling so that they are styled in the same pass.
+        generated_content_system(&mut self.0);
+
         style_system(&mut self.0);
 
         // Load any unloaded images and remove unused images.
@@ -256,6 +276,33 @@ impl BackendContext {
         self.0.tick_timers();
     }
 
+    /// Replaces the system clock with a virtual clock starting at the given time.
+    ///
+    /// While a virtual clock is in use, timers, scheduled events and animations only progress when
+    /// the clock is moved forward with [`advance_clock`](Self::advance_clock).
+    pub fn set_virtual_clock(&mut self, time: Instant) {
+        super::set_virtual_time(Some(time));
+    }
+
+    /// Switches back from a virtual clock to the system clock.
+    pub fn clear_virtual_clock(&mut self) {
+        super::set_virtual_time(None);
+    }
+
+    /// Moves the virtual clock forward by the given duration.
+    ///
+    /// Does nothing if a virtual clock has not been set with [`set_virtual_clock`](Self::set_virtual_clock).
+    pub fn advance_clock(&mut self, duration: Duration) {
+        if let Some(time) = super::virtual_time() {
+            super::set_virtual_time(Some(time + duration));
+        }
+    }
+
+    /// Returns the current time of the clock used for timers, scheduled events and animations.
+    pub fn now(&self) -> Instant {
+        super::now()
+    }
+
     pub fn get_next_timer_time(&self) -> Option<Instant> {
         let timer_time = self.0.running_timers.peek().map(|timer_state| timer_state.time);
         let scheduled_event_time = self.0.event_schedule.peek().map(|timed_event| timed_event.time);
@@ -269,7 +316,7 @@ impl BackendContext {
     }
 
     pub fn emit_scheduled_events(&mut self) {
-        let now = Instant::now();
+        let now = super::now();
         while let Some(timed_event) = self.0.event_schedule.peek() {
             if timed_event.time <= now {
                 self.0.event_queue.push_back(self.0.event_schedule.pop().unwrap().event);
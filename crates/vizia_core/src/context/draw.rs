@@ -8,6 +8,7 @@ use skia_safe::{
     Point, RRect, Rect, SamplingOptions, Shader, TileMode,
 };
 use std::any::{Any, TypeId};
+use std::collections::VecDeque;
 use std::f32::consts::SQRT_2;
 use vizia_style::LengthPercentageOrAuto;
 
@@ -67,6 +68,7 @@ pub struct DrawContext<'a> {
     pub(crate) modifiers: &'a Modifiers,
     pub(crate) mouse: &'a MouseState<Entity>,
     pub(crate) windows: &'a mut HashMap<Entity, WindowState>,
+    pub(crate) event_queue: &'a mut VecDeque<Event>,
 }
 
 macro_rules! get_units_property {
@@ -135,6 +137,16 @@ impl DrawContext<'_> {
         }
     }
 
+    /// Sends a message to the current view which propagates up the tree, handled after drawing has finished.
+    pub(crate) fn emit<M: Any + Send>(&mut self, message: M) {
+        self.event_queue.push_back(
+            Event::new(message)
+                .target(self.current)
+                .origin(self.current)
+                .propagate(Propagation::Up),
+        );
+    }
+
     /// Returns the z-index of the current view.
     pub fn z_index(&self) -> i32 {
         self.style.z_index.get(self.current).copied().unwrap_or_default()
@@ -169,12 +181,13 @@ impl DrawContext<'_> {
             .get(self.current)
             .map(|clip| match clip {
                 ClipPath::Auto => bounds,
-                ClipPath::Shape(rect) => bounds.shrink_sides(
+                ClipPath::Shape(rect) | ClipPath::Inset(rect, _) => bounds.shrink_sides(
                     rect.3.to_pixels(bounds.w, scale),
                     rect.0.to_pixels(bounds.h, scale),
                     rect.1.to_pixels(bounds.w, scale),
                     rect.2.to_pixels(bounds.h, scale),
                 ),
+                _ => bounds,
             })
             .unwrap_or(bounds);
 
@@ -205,6 +218,11 @@ impl DrawContext<'_> {
         Some(clip_path)
     }
 
+    /// Returns the clipping shape of the current view specified by the `clip-path` property, if any.
+    pub fn clip_shape(&self) -> Option<Path> {
+        self.style.clip_path.get(self.current)?.as_clip_shape(self.bounds(), self.scale_factor())
+    }
+
     /// Returns the 2D transform of the current view.
     pub fn transform(&self) -> Matrix {
         let bounds = self.bounds();
@@ -449,6 +467,14 @@ impl DrawContext<'_> {
         selection_color
     );
 
+    /// Returns the color of selected text for the current view, if set by a `::selection` rule.
+    pub fn selected_text_color(&self) -> Option<Color> {
+        self.style
+            .selected_text_color
+            .get(self.current)
+            .map(|col| Color::rgba(col.r(), col.g(), col.b(), col.a()))
+    }
+
     get_color_property!(
         /// Returns the text caret color for the current view.
         caret_color
@@ -484,6 +510,11 @@ impl DrawContext<'_> {
         self.style.shadow.get(self.current)
     }
 
+    /// Returns a reference to any filters applied to the current view.
+    pub fn filter(&self) -> Option<&Vec<Filter>> {
+        self.style.filter.get(self.current)
+    }
+
     /// Return to reference to any filter applied to the current view.
     pub fn backdrop_filter(&self) -> Option<&Filter> {
         self.style.backdrop_filter.get(self.current)
@@ -953,45 +984,35 @@ impl DrawContext<'_> {
                                     }
                                 };
 
-                                let num_stops = linear_gradient.stops.len();
-
-                                let mut stops = linear_gradient
+                                let stops = linear_gradient
                                     .stops
                                     .iter()
-                                    .enumerate()
-                                    .map(|(index, stop)| {
-                                        let pos = if let Some(pos) = &stop.position {
+                                    .map(|stop| {
+                                        let pos = stop.position.as_ref().map(|pos| {
                                             pos.to_pixels(parent_length, self.scale_factor())
                                                 / parent_length
-                                        } else {
-                                            index as f32 / (num_stops - 1) as f32
-                                        };
-                                        (pos, skia_safe::Color::from(stop.color))
+                                        });
+                                        (pos, stop.color)
                                     })
                                     .collect::<Vec<_>>();
 
-                                // Insert a stop at the front if the first stop is not at 0.
-                                if let Some(first) = stops.first() {
-                                    if first.0 != 0.0 {
-                                        stops.insert(0, (0.0, first.1));
-                                    }
-                                }
-
-                                // Insert a stop at the end if the last stop is not at 1.0.
-                                if let Some(last) = stops.last() {
-                                    if last.0 != 1.0 {
-                                        stops.push((1.0, last.1));
-                                    }
-                                }
-
-                                let (offsets, colors): (Vec<f32>, Vec<skia_safe::Color>) =
-                                    stops.into_iter().unzip();
+                                let stops = gradient_stops(
+                                    &stops,
+                                    &linear_gradient.interpolation,
+                                    linear_gradient.repeating,
+                                );
 
+                                // Map the gradient line onto the range covered by the stops.
+                                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                                let (from, to) = stops.range;
                                 let shader = Shader::linear_gradient(
-                                    (Point::from(start), Point::from(end)),
-                                    GradientShaderColors::Colors(&colors[..]),
-                                    Some(&offsets[..]),
-                                    TileMode::Clamp,
+                                    (
+                                        Point::new(start.0 + dx * from, start.1 + dy * from),
+                                        Point::new(start.0 + dx * to, start.1 + dy * to),
+                                    ),
+                                    GradientShaderColors::Colors(&stops.colors[..]),
+                                    Some(&stops.offsets[..]),
+                                    stops.tile_mode,
                                     None,
                                     None,
                                 );
@@ -1003,49 +1024,85 @@ impl DrawContext<'_> {
                             }
 
                             Gradient::Radial(radial_gradient) => {
-                                let num_stops = radial_gradient.stops.len();
-
-                                let mut stops = radial_gradient
+                                let stops = radial_gradient
                                     .stops
                                     .iter()
-                                    .enumerate()
-                                    .map(|(index, stop)| {
-                                        let pos = if let Some(pos) = &stop.position {
+                                    .map(|stop| {
+                                        let pos = stop.position.as_ref().map(|pos| {
                                             pos.to_pixels(bounds.width(), self.scale_factor())
                                                 / bounds.width()
-                                        } else {
-                                            index as f32 / (num_stops - 1) as f32
-                                        };
+                                        });
+                                        (pos, stop.color)
+                                    })
+                                    .collect::<Vec<_>>();
+
+                                let stops = gradient_stops(
+                                    &stops,
+                                    &radial_gradient.interpolation,
+                                    radial_gradient.repeating,
+                                );
 
-                                        (pos, skia_safe::Color::from(stop.color))
+                                let center = gradient_center(
+                                    &radial_gradient.position,
+                                    bounds,
+                                    self.scale_factor(),
+                                );
+                                let radius = bounds.w.max(bounds.h);
+                                let (from, to) = stops.range;
+                                let shader = Shader::two_point_conical_gradient(
+                                    center,
+                                    radius * from,
+                                    center,
+                                    radius * to,
+                                    GradientShaderColors::Colors(&stops.colors[..]),
+                                    Some(&stops.offsets[..]),
+                                    stops.tile_mode,
+                                    None,
+                                    None,
+                                );
+
+                                let mut paint = Paint::default();
+                                paint.set_shader(shader);
+                                canvas.draw_path(&path, &paint);
+                            }
+
+                            Gradient::Conic(conic_gradient) => {
+                                let stops = conic_gradient
+                                    .stops
+                                    .iter()
+                                    .map(|stop| {
+                                        (stop.position.map(|pos| pos.to_fraction()), stop.color)
                                     })
                                     .collect::<Vec<_>>();
 
-                                // Insert a stop at the front if the first stop is not at 0.
-                                if let Some(first) = stops.first() {
-                                    if first.0 != 0.0 {
-                                        stops.insert(0, (0.0, first.1));
-                                    }
-                                }
+                                let stops = gradient_stops(
+                                    &stops,
+                                    &conic_gradient.interpolation,
+                                    conic_gradient.repeating,
+                                );
 
-                                // Insert a stop at the end if the last stop is not at 1.0.
-                                if let Some(last) = stops.last() {
-                                    if last.0 != 1.0 {
-                                        stops.push((1.0, last.1));
-                                    }
-                                }
+                                let center = gradient_center(
+                                    &conic_gradient.position,
+                                    bounds,
+                                    self.scale_factor(),
+                                );
 
-                                let (offsets, colors): (Vec<f32>, Vec<skia_safe::Color>) =
-                                    stops.into_iter().unzip();
+                                // Skia sweeps clockwise from the positive x axis, whereas conic
+                                // gradients start from the top.
+                                let matrix = Matrix::rotate_deg_pivot(
+                                    conic_gradient.angle.to_degrees() - 90.0,
+                                    center,
+                                );
 
-                                let shader = Shader::radial_gradient(
-                                    Point::from(bounds.center()),
-                                    bounds.w.max(bounds.h),
-                                    GradientShaderColors::Colors(&colors[..]),
-                                    Some(&offsets[..]),
-                                    TileMode::Clamp,
-                                    None,
+                                let (from, to) = stops.range;
+                                let shader = Shader::sweep_gradient(
+                                    center,
+                                    GradientShaderColors::Colors(&stops.colors[..]),
+                                    Some(&stops.offsets[..]),
+                                    stops.tile_mode,
+                                    Some((from * 360.0, to * 360.0)),
                                     None,
+                                    Some(&matrix),
                                 );
 
                                 let mut paint = Paint::default();
@@ -1196,44 +1253,79 @@ impl DrawContext<'_> {
     /// Draw any text for the current view.
     pub fn draw_text(&mut self, canvas: &Canvas) {
         if let Some(paragraph) = self.text_context.text_paragraphs.get(self.current) {
-            let bounds = self.bounds();
+            paragraph.paint(canvas, self.text_origin(paragraph.height()));
+        }
+    }
 
-            let alignment = self.alignment();
-
-            let (mut top, _) = match alignment {
-                Alignment::TopLeft => (0.0, 0.0),
-                Alignment::TopCenter => (0.0, 0.5),
-                Alignment::TopRight => (0.0, 1.0),
-                Alignment::Left => (0.5, 0.0),
-                Alignment::Center => (0.5, 0.5),
-                Alignment::Right => (0.5, 1.0),
-                Alignment::BottomLeft => (1.0, 0.0),
-                Alignment::BottomCenter => (1.0, 0.5),
-                Alignment::BottomRight => (1.0, 1.0),
-            };
-
-            let padding_top = match self.padding_top() {
-                Units::Pixels(val) => val,
-                _ => 0.0,
-            };
-
-            let padding_bottom = match self.padding_bottom() {
-                Units::Pixels(val) => val,
-                _ => 0.0,
-            };
-
-            top *= bounds.height() - padding_top - padding_bottom - paragraph.height();
-
-            let padding_left = match self.padding_left() {
-                Units::Pixels(val) => val,
-                _ => 0.0,
-            };
-
-            paragraph.paint(
-                canvas,
-                ((bounds.x + padding_left).round(), (bounds.y + padding_top + top).round()),
-            );
+    /// Repaints the text of the current view within the given rectangles using the selected text color,
+    /// which is set by the `color` property of a `::selection` rule. Does nothing if no such color is set.
+    pub fn draw_selected_text(&mut self, canvas: &Canvas, rects: &[Rect]) {
+        let Some(color) = self.selected_text_color() else {
+            return;
+        };
+
+        if rects.is_empty() {
+            return;
         }
+
+        if let Some(paragraph) = self.text_context.text_paragraphs.get(self.current) {
+            let mut clip = Path::new();
+            for rect in rects {
+                clip.add_rect(*rect, None);
+            }
+
+            canvas.save();
+            canvas.clip_path(&clip, ClipOp::Intersect, true);
+
+            let mut paint = Paint::default();
+            paint.set_color_filter(skia_safe::color_filters::blend(
+                color,
+                skia_safe::BlendMode::SrcIn,
+            ));
+            canvas.save_layer(&SaveLayerRec::default().paint(&paint));
+            paragraph.paint(canvas, self.text_origin(paragraph.height()));
+            canvas.restore();
+
+            canvas.restore();
+        }
+    }
+
+    // Returns the position at which a paragraph of the given height is painted within the bounds of the current view.
+    pub(crate) fn text_origin(&self, paragraph_height: f32) -> (f32, f32) {
+        let bounds = self.bounds();
+
+        let alignment = self.alignment();
+
+        let (mut top, _) = match alignment {
+            Alignment::TopLeft => (0.0, 0.0),
+            Alignment::TopCenter => (0.0, 0.5),
+            Alignment::TopRight => (0.0, 1.0),
+            Alignment::Left => (0.5, 0.0),
+            Alignment::Center => (0.5, 0.5),
+            Alignment::Right => (0.5, 1.0),
+            Alignment::BottomLeft => (1.0, 0.0),
+            Alignment::BottomCenter => (1.0, 0.5),
+            Alignment::BottomRight => (1.0, 1.0),
+        };
+
+        let padding_top = match self.padding_top() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        let padding_bottom = match self.padding_bottom() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        top *= bounds.height() - padding_top - padding_bottom - paragraph_height;
+
+        let padding_left = match self.padding_left() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        ((bounds.x + padding_left).round(), (bounds.y + padding_top + top).round())
     }
 }
 
@@ -1264,6 +1356,96 @@ impl DataContext for DrawContext<'_> {
     }
 }
 
+// Helper function for resolving the center of a radial or conic gradient within the given bounds.
+fn gradient_center(position: &Position, bounds: BoundingBox, scale_factor: f32) -> Point {
+    Point::new(
+        bounds.x + position.x.to_length_or_percentage().to_pixels(bounds.w, scale_factor),
+        bounds.y + position.y.to_length_or_percentage().to_pixels(bounds.h, scale_factor),
+    )
+}
+
+/// The resolved color stops of a gradient, ready to be passed to a gradient shader.
+struct GradientStops {
+    offsets: Vec<f32>,
+    colors: Vec<skia_safe::Color>,
+    /// The start and end of the stops as fractions of the gradient line.
+    range: (f32, f32),
+    tile_mode: TileMode,
+}
+
+/// The number of stops sampled between each pair of color stops when interpolating in a color
+/// space other than sRGB, which gradient shaders do not support directly.
+const GRADIENT_SAMPLES: usize = 16;
+
+// Helper function for resolving the positions of gradient color stops, given as fractions of the
+// gradient line, and sampling the colors between them in the interpolation color space.
+fn gradient_stops(
+    stops: &[(Option<f32>, Color)],
+    interpolation: &ColorInterpolation,
+    repeating: bool,
+) -> GradientStops {
+    let num_stops = stops.len();
+
+    let mut resolved: Vec<(f32, RGBA)> = Vec::with_capacity(num_stops);
+    for (index, (pos, color)) in stops.iter().enumerate() {
+        let pos = pos.unwrap_or_else(|| index as f32 / (num_stops - 1).max(1) as f32);
+        // A stop cannot be positioned before the stop preceding it.
+        let pos = resolved.last().map_or(pos, |(prev, _)| pos.max(*prev));
+        resolved.push((pos, color.get_rgba()));
+    }
+
+    let (range, tile_mode) = match (resolved.first(), resolved.last()) {
+        (Some(first), Some(last)) if repeating && last.0 > first.0 => {
+            ((first.0, last.0), TileMode::Repeat)
+        }
+        _ => ((0.0, 1.0), TileMode::Clamp),
+    };
+
+    if tile_mode == TileMode::Repeat {
+        for stop in resolved.iter_mut() {
+            stop.0 = (stop.0 - range.0) / (range.1 - range.0);
+        }
+    } else {
+        // Insert a stop at the front if the first stop is not at 0.
+        if let Some(first) = resolved.first().copied() {
+            if first.0 != 0.0 {
+                resolved.insert(0, (0.0, first.1));
+            }
+        }
+
+        // Insert a stop at the end if the last stop is not at 1.0.
+        if let Some(last) = resolved.last().copied() {
+            if last.0 != 1.0 {
+                resolved.push((1.0, last.1));
+            }
+        }
+    }
+
+    let mut offsets = Vec::with_capacity(resolved.len());
+    let mut colors = Vec::with_capacity(resolved.len());
+    let mut previous: Option<(f32, RGBA)> = None;
+    for (pos, color) in resolved {
+        if let Some((prev_pos, prev_color)) = previous {
+            if interpolation.color_space != InterpolationColorSpace::Srgb
+                && prev_color != color
+                && pos > prev_pos
+            {
+                for step in 1..GRADIENT_SAMPLES {
+                    let t = step as f32 / GRADIENT_SAMPLES as f32;
+                    offsets.push(prev_pos + (pos - prev_pos) * t);
+                    colors.push(interpolation.mix(prev_color, color, t).into());
+                }
+            }
+        }
+
+        offsets.push(pos);
+        colors.push(color.into());
+        previous = Some((pos, color));
+    }
+
+    GradientStops { offsets, colors, range, tile_mode }
+}
+
 // Helper function for computing a rounded corner with variable smoothing
 fn compute_smooth_corner(
     corner_radius: f32,
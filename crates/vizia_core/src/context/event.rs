@@ -5,13 +5,15 @@ use std::error::Error;
 use std::rc::Rc;
 
 use hashbrown::{HashMap, HashSet};
+use log::warn;
 use vizia_storage::{LayoutTreeIterator, TreeIterator};
 
 use crate::animation::{AnimId, Interpolator};
 use crate::cache::CachedData;
 use crate::events::{TimedEvent, TimedEventHandle, TimerState, ViewHandler};
 use crate::prelude::*;
-use crate::resource::ResourceManager;
+use crate::resource::{theme_filename, ResourceManager};
+use crate::style::{parse_stylesheet, DiagnosticSeverity};
 use crate::tree::{focus_backward, focus_forward, is_navigatable};
 use vizia_input::MouseState;
 
@@ -199,6 +201,28 @@ impl<'a> EventContext<'a> {
         self.entity_identifiers.get(id).cloned()
     }
 
+    /// Returns the views which match the given CSS selector, in tree order.
+    pub fn query(&self, selector: &str) -> Vec<Entity> {
+        crate::systems::query_selector(self.style, self.tree, selector)
+    }
+
+    /// Returns the first view which matches the given CSS selector, in tree order.
+    pub fn query_first(&self, selector: &str) -> Option<Entity> {
+        self.query(selector).first().copied()
+    }
+
+    /// Returns the style rules which match the given view, in order of precedence, with the rule which takes
+    /// precedence first.
+    pub fn matched_rules(&self, entity: Entity) -> Vec<MatchedRule> {
+        crate::style::inspect_matched_rules(entity, self.style, self.tree)
+    }
+
+    /// Returns the matched style rules of the given view along with the computed value of each style property and
+    /// where that value comes from.
+    pub fn computed_style(&self, entity: Entity) -> ComputedStyle {
+        crate::style::inspect_style(entity, self.style, self.tree)
+    }
+
     /// Returns the [Entity] id of the current view.
     pub fn current(&self) -> Entity {
         self.current
@@ -275,12 +299,13 @@ impl<'a> EventContext<'a> {
             .get(self.current)
             .map(|clip| match clip {
                 ClipPath::Auto => bounds,
-                ClipPath::Shape(rect) => bounds.shrink_sides(
+                ClipPath::Shape(rect) | ClipPath::Inset(rect, _) => bounds.shrink_sides(
                     rect.3.to_pixels(bounds.w, scale),
                     rect.0.to_pixels(bounds.h, scale),
                     rect.1.to_pixels(bounds.w, scale),
                     rect.2.to_pixels(bounds.h, scale),
                 ),
+                _ => bounds,
             })
             .unwrap_or(bounds);
 
@@ -307,6 +332,11 @@ impl<'a> EventContext<'a> {
         }
     }
 
+    /// Returns the clipping shape of the current view specified by the `clip-path` property, if any.
+    pub(crate) fn clip_shape(&self) -> Option<skia_safe::Path> {
+        self.style.clip_path.get(self.current)?.as_clip_shape(self.bounds(), self.scale_factor())
+    }
+
     /// Returns the 2D transform of the current view.
     pub fn transform(&self) -> Matrix {
         let bounds = self.bounds();
@@ -371,7 +401,7 @@ impl<'a> EventContext<'a> {
 
     /// Trigger an animation with the given id to play on the current view.
     pub fn play_animation(&mut self, anim_id: impl AnimId, duration: Duration, delay: Duration) {
-        if let Some(animation_id) = anim_id.get(self) {
+        if let Some(animation_id) = anim_id.get_for(self, self.current) {
             self.style.enqueue_animation(self.current, animation_id, duration, delay);
         }
     }
@@ -385,7 +415,7 @@ impl<'a> EventContext<'a> {
         delay: Duration,
     ) {
         if let Some(target_entity) = self.resolve_entity_identifier(target) {
-            if let Some(animation_id) = anim_id.get(self) {
+            if let Some(animation_id) = anim_id.get_for(self, target_entity) {
                 self.style.enqueue_animation(target_entity, animation_id, duration, delay)
             }
         }
@@ -698,17 +728,22 @@ impl<'a> EventContext<'a> {
         self.data::<Environment>().unwrap()
     }
 
-    /// Sets the current [theme mode](ThemeMode).
+    /// Sets the current [theme mode](ThemeMode), replacing the rules of the built-in theme.
     pub fn set_theme_mode(&mut self, theme_mode: ThemeMode) {
         if !self.ignore_default_theme {
-            match theme_mode {
-                ThemeMode::LightMode => {
-                    self.resource_manager.themes[2] = String::from(LIGHT_THEME);
-                }
+            let theme = match theme_mode {
+                ThemeMode::LightMode => LIGHT_THEME,
+                ThemeMode::DarkMode => DARK_THEME,
+            };
 
-                ThemeMode::DarkMode => {
-                    self.resource_manager.themes[2] = String::from(DARK_THEME);
-                }
+            self.resource_manager.themes[2] = String::from(theme);
+
+            let (stylesheet, _) = parse_stylesheet(&theme_filename(2), theme);
+            let rules = stylesheet.map(|stylesheet| stylesheet.rules.0).unwrap_or_default();
+            if self.style.replace_stylesheet(2, rules) {
+                self.restyle_all();
+            } else {
+                self.rebuild_styles();
             }
         }
     }
@@ -743,37 +778,135 @@ impl<'a> EventContext<'a> {
     }
 
     /// Reloads the stylesheets linked to the application.
+    ///
+    /// Stylesheets which fail to read keep their previous rules, and the failures are logged. Returns the last
+    /// error encountered while reading the stylesheets.
     pub fn reload_styles(&mut self) -> Result<(), std::io::Error> {
         if self.resource_manager.themes.is_empty() && self.resource_manager.styles.is_empty() {
             return Ok(());
         }
 
+        let mut result = Ok(());
+        let mut changed = false;
+        for index in 0..self.resource_manager.styles.len() {
+            match self.load_style(index) {
+                Ok(loaded) => changed |= loaded,
+                Err(err) => {
+                    warn!("Failed to read stylesheet: {}", err);
+                    result = Err(err);
+                }
+            }
+        }
+
+        if changed {
+            self.restyle_all();
+        }
+
+        result
+    }
+
+    /// Reloads the file-backed stylesheets which have been modified on disk since they were last read.
+    ///
+    /// A stylesheet whose new content has errors keeps its previous rules.
+    pub fn reload_modified_styles(&mut self) {
+        let mut changed = false;
+        for index in self.resource_manager.modified_styles() {
+            match self.load_style(index) {
+                Ok(loaded) => changed |= loaded,
+                Err(err) => warn!("Failed to read stylesheet: {}", err),
+            }
+        }
+
+        if changed {
+            self.restyle_all();
+        }
+    }
+
+    // Reads the stylesheet at the given index and, if its content has changed, parses it and replaces its rules,
+    // leaving the rules of other stylesheets in place. Returns true if the rules of the stylesheet changed.
+    pub(crate) fn load_style(&mut self, index: usize) -> Result<bool, std::io::Error> {
+        let Some(content) = self.resource_manager.load_style(index)? else {
+            return Ok(false);
+        };
+
+        let filename = self.resource_manager.style_filename(index);
+        let (stylesheet, diagnostics) = parse_stylesheet(&filename, &content);
+
+        for diagnostic in diagnostics.iter() {
+            warn!("{}", diagnostic);
+        }
+
+        // A stylesheet without content has not been added to the style rules yet.
+        let source = &mut self.resource_manager.style_sources[index];
+        let added = source.content.is_some();
+        let failed = diagnostics.iter().any(|d| d.severity == DiagnosticSeverity::Error);
+        source.diagnostics = diagnostics;
+
+        // Rules which were dropped due to errors would be lost, so keep the previous rules if there are any.
+        if added && failed {
+            warn!("Failed to reload {}, keeping the previous rules", filename);
+            return Ok(false);
+        }
+
+        let position = self.resource_manager.style_position(index);
+        let rules = stylesheet.map(|stylesheet| stylesheet.rules.0).unwrap_or_default();
+        let replaced = if added {
+            self.style.replace_stylesheet(position, rules)
+        } else {
+            self.style.insert_stylesheet(position, rules);
+            true
+        };
+
+        self.resource_manager.style_sources[index].content = Some(content);
+
+        if !replaced {
+            self.rebuild_styles();
+        }
+
+        Ok(true)
+    }
+
+    // Parses a stylesheet which is not read from a file, such as a theme, and inserts its rules at the given
+    // position in the order of the stylesheets, logging any problems found.
+    pub(crate) fn insert_stylesheet(&mut self, position: usize, filename: &str, stylesheet: &str) {
+        let (stylesheet, diagnostics) = parse_stylesheet(filename, stylesheet);
+
+        for diagnostic in diagnostics.iter() {
+            warn!("{}", diagnostic);
+        }
+
+        let rules = stylesheet.map(|stylesheet| stylesheet.rules.0).unwrap_or_default();
+        self.style.insert_stylesheet(position, rules);
+
+        self.restyle_all();
+    }
+
+    // Replaces the style rules with those of the themes and loaded stylesheets, and restyles every view.
+    pub(crate) fn rebuild_styles(&mut self) {
         self.style.remove_rules();
 
         self.style.clear_style_rules();
 
-        let mut overall_theme = String::new();
-
-        // Reload built-in themes
-        for theme in self.resource_manager.themes.iter() {
-            overall_theme += theme;
+        // Problems found in the stylesheets were logged when they were added.
+        for (filename, stylesheet) in self.resource_manager.style_contents() {
+            self.style.parse_theme(&filename, stylesheet);
         }
 
-        for style_string in self.resource_manager.styles.iter().flat_map(|style| style.get_style())
-        {
-            overall_theme += &style_string;
+        for rule in self.resource_manager.style_rules.iter() {
+            self.style.insert_rule_builder(rule);
         }
 
-        self.style.parse_theme(&overall_theme);
+        self.restyle_all();
+    }
 
+    // Restyles every view after the style rules have changed.
+    pub(crate) fn restyle_all(&mut self) {
         for entity in self.tree.into_iter() {
             self.style.needs_restyle(entity);
             self.style.needs_relayout();
             //self.style.needs_redraw(entity);
             self.style.needs_text_update(entity);
         }
-
-        Ok(())
     }
 
     /// Spawns a thread and provides a [ContextProxy] for sending events back to the main UI thread.
@@ -1210,10 +1343,10 @@ impl<'a> EventContext<'a> {
         self.timers.push(TimerState {
             entity: Entity::root(),
             id,
-            time: Instant::now(),
+            time: super::now(),
             interval,
             duration,
-            start_time: Instant::now(),
+            start_time: super::now(),
             callback: Rc::new(callback),
             ticking: false,
             stopping: false,
@@ -1234,7 +1367,7 @@ impl<'a> EventContext<'a> {
         }
 
         self.modify_timer(timer, |timer_state| {
-            let now = Instant::now();
+            let now = super::now();
             timer_state.start_time = now;
             timer_state.time = now;
             timer_state.entity = current;
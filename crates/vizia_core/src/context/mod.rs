@@ -7,6 +7,7 @@ mod draw;
 mod event;
 mod proxy;
 mod resource;
+mod simulate;
 
 use log::debug;
 use skia_safe::{
@@ -14,7 +15,7 @@ use skia_safe::{
     textlayout::{FontCollection, TypefaceFontProvider},
     FontMgr,
 };
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BinaryHeap, VecDeque};
 use std::rc::Rc;
 use std::sync::Mutex;
@@ -50,7 +51,7 @@ use crate::{
 use crate::{cache::CachedData, resource::ImageOrSvg};
 
 use crate::prelude::*;
-use crate::resource::ResourceManager;
+use crate::resource::{theme_filename, view_style_filename, ResourceManager};
 use crate::text::TextContext;
 use vizia_input::MouseState;
 use vizia_storage::{ChildIterator, LayoutTreeIterator};
@@ -72,6 +73,26 @@ thread_local! {
     pub static MAPS: RefCell<HashMap<MapId, (Entity, Box<dyn Any>)>> = RefCell::new(HashMap::new());
     /// The 'current' entity which is used for storing lens map mapping functions as per above.
     pub static CURRENT: RefCell<Entity> = RefCell::new(Entity::root());
+    /// The time reported by a virtual clock, if one has been set by the backend.
+    static VIRTUAL_TIME: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Returns the current time used for timers, scheduled events and animations.
+///
+/// This is the system time unless a backend has replaced it with a virtual clock, for example to
+/// step time deterministically when running headless tests.
+pub(crate) fn now() -> Instant {
+    virtual_time().unwrap_or_else(Instant::now)
+}
+
+/// Returns the time of the virtual clock, or `None` if the system clock is in use.
+pub(crate) fn virtual_time() -> Option<Instant> {
+    VIRTUAL_TIME.with(|time| time.get())
+}
+
+/// Sets the time of the virtual clock, or switches back to the system clock if `None`.
+pub(crate) fn set_virtual_time(time: Option<Instant>) {
+    VIRTUAL_TIME.with(|virtual_time| virtual_time.set(time));
 }
 
 #[derive(Default, Clone)]
@@ -215,7 +236,7 @@ impl Context {
                 #[cfg(not(feature = "x11"))]
                 Box::new(NopClipboardContext::new().unwrap())
             },
-            click_time: Instant::now(),
+            click_time: now(),
             clicks: 0,
             click_pos: (0.0, 0.0),
             click_button: MouseButton::Left,
@@ -580,20 +601,111 @@ impl Context {
     pub(crate) fn add_theme(&mut self, theme: &str) {
         self.resource_manager.themes.push(theme.to_owned());
 
-        EventContext::new(self).reload_styles().expect("Failed to reload styles");
+        let position = self.resource_manager.themes.len() - 1;
+        EventContext::new(self).insert_stylesheet(position, &theme_filename(position), theme);
+    }
+
+    /// Adds the stylesheet declared by a view type with [`View::stylesheet`], if it has not already been added.
+    ///
+    /// The stylesheet is nested within a `:where()` rule for the element name of the view, so that it only applies
+    /// to views of the type and their descendants without adding to the specificity of its rules. Rules of equal
+    /// specificity in stylesheets added by the application take precedence.
+    pub(crate) fn add_view_stylesheet<V: View>(&mut self, element: Option<&str>) {
+        let Some(stylesheet) = V::stylesheet() else { return };
+
+        let type_id = TypeId::of::<V>();
+        if self.resource_manager.view_styles.iter().any(|(id, _, _)| *id == type_id) {
+            return;
+        }
+
+        let stylesheet = match element {
+            Some(element) => format!(":where({}) {{\n{}\n}}", element, stylesheet),
+            None => stylesheet.to_owned(),
+        };
+
+        let name = std::any::type_name::<V>();
+        let position = self.resource_manager.themes.len() + self.resource_manager.view_styles.len();
+        EventContext::new(self).insert_stylesheet(
+            position,
+            &view_style_filename(name),
+            &stylesheet,
+        );
+
+        self.resource_manager.view_styles.push((type_id, name, stylesheet));
     }
 
+    /// Adds a stylesheet to the application, which can be a string of CSS or the path of a CSS file.
+    ///
+    /// Returns an error if the stylesheet could not be read, in which case it is not added. Problems found while
+    /// parsing the stylesheet are logged and can be retrieved with [`style_diagnostics`](Context::style_diagnostics).
     pub fn add_stylesheet(&mut self, style: impl IntoCssStr) -> Result<(), std::io::Error> {
         self.resource_manager.styles.push(Box::new(style));
 
-        EventContext::new(self).reload_styles().expect("Failed to reload styles");
+        let index = self.resource_manager.styles.len() - 1;
+        if let Err(err) = EventContext::new(self).load_style(index) {
+            self.resource_manager.styles.truncate(index);
+            self.resource_manager.style_sources.truncate(index);
+            return Err(err);
+        }
+
+        EventContext::new(self).restyle_all();
 
         Ok(())
     }
 
+    /// Adds the rules of a [`StyleSheetBuilder`] to the application.
+    ///
+    /// The rules take precedence over stylesheets of equal specificity, with later rules taking precedence over
+    /// earlier ones.
+    pub fn add_style_rules(&mut self, stylesheet: StyleSheetBuilder) {
+        // Builder rules are stored after the rules of every stylesheet, so the new rules can be appended.
+        for rule in stylesheet.rules.iter() {
+            self.style.insert_rule_builder(rule);
+        }
+
+        self.resource_manager.style_rules.extend(stylesheet.rules);
+
+        EventContext::new(self).restyle_all();
+    }
+
+    /// Returns the diagnostics from the most recent parse of each stylesheet added with
+    /// [`add_stylesheet`](Context::add_stylesheet), such as unknown properties, invalid values, and unsupported
+    /// selectors, along with their source locations.
+    pub fn style_diagnostics(&self) -> Vec<StyleDiagnostic> {
+        self.resource_manager.style_diagnostics().cloned().collect()
+    }
+
+    /// Watches the stylesheets added from files with [`add_stylesheet`](Context::add_stylesheet), checking them
+    /// for changes on disk at the given interval.
+    ///
+    /// A stylesheet which has changed is reloaded automatically. If the new content fails to parse, the previous
+    /// rules of the stylesheet are kept. Returns the [`Timer`] used to poll the files, which can be stopped with
+    /// [`stop_timer`](Context::stop_timer).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// cx.add_stylesheet(include_style!("src/style.css")).unwrap();
+    /// cx.watch_stylesheets(Duration::from_millis(500));
+    /// ```
+    pub fn watch_stylesheets(&mut self, interval: Duration) -> Timer {
+        let timer = self.add_timer(interval, None, |cx, action| {
+            if let TimerAction::Tick(_) = action {
+                cx.reload_modified_styles();
+            }
+        });
+
+        self.with_current(Entity::root(), |cx| cx.start_timer(timer));
+
+        timer
+    }
+
     /// Remove all user themes from the application.
     pub fn remove_user_themes(&mut self) {
-        self.resource_manager.themes.clear();
+        for _ in self.resource_manager.themes.drain(..) {
+            self.style.remove_stylesheet(0);
+        }
 
         self.add_theme(DEFAULT_LAYOUT);
         self.add_theme(MARKDOWN);
@@ -658,10 +770,10 @@ impl Context {
         self.timers.push(TimerState {
             entity: Entity::root(),
             id,
-            time: Instant::now(),
+            time: now(),
             interval,
             duration,
-            start_time: Instant::now(),
+            start_time: now(),
             callback: Rc::new(callback),
             ticking: false,
             stopping: false,
@@ -682,7 +794,7 @@ impl Context {
         }
 
         self.modify_timer(timer, |timer_state| {
-            let now = Instant::now();
+            let now = now();
             timer_state.start_time = now;
             timer_state.time = now;
             timer_state.entity = current;
@@ -744,7 +856,7 @@ impl Context {
 
     // Tick all timers.
     pub(crate) fn tick_timers(&mut self) {
-        let now = Instant::now();
+        let now = now();
         while let Some(next_timer_state) = self.running_timers.peek() {
             if next_timer_state.time <= now {
                 let mut timer_state = self.running_timers.pop().unwrap();
@@ -865,6 +977,45 @@ impl Context {
         self.entity_identifiers.get(identity).cloned()
     }
 
+    /// Returns the views which match the given CSS selector, in tree order.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let checked_items = cx.query("list > list-item:checked");
+    /// ```
+    pub fn query(&self, selector: &str) -> Vec<Entity> {
+        crate::systems::query_selector(&self.style, &self.tree, selector)
+    }
+
+    /// Returns the first view which matches the given CSS selector, in tree order.
+    pub fn query_first(&self, selector: &str) -> Option<Entity> {
+        self.query(selector).first().copied()
+    }
+
+    /// Returns the style rules which match the given view, in order of precedence, with the rule which takes
+    /// precedence first.
+    pub fn matched_rules(&self, entity: Entity) -> Vec<MatchedRule> {
+        crate::style::inspect_matched_rules(entity, &self.style, &self.tree)
+    }
+
+    /// Returns the matched style rules of the given view along with the computed value of each style property and
+    /// where that value comes from.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let style = cx.computed_style(Entity::root());
+    /// for property in style.specified() {
+    ///     println!("{}: {:?} ({:?})", property.name, property.value, property.origin);
+    /// }
+    /// ```
+    pub fn computed_style(&self, entity: Entity) -> ComputedStyle {
+        crate::style::inspect_style(entity, &self.style, &self.tree)
+    }
+
     /// Toggles the addition/removal of a class name for the current view.
     ///
     /// # Example
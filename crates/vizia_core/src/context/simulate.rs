@@ -0,0 +1,188 @@
+use crate::events::EventManager;
+use crate::prelude::*;
+
+/// The number of intermediate mouse move events sent during a simulated drag.
+const DRAG_STEPS: usize = 8;
+
+/// Methods for simulating user input, for use in tests and tooling.
+///
+/// Simulated input is sent to the window as if it came from a windowing backend and is dispatched immediately,
+/// so hover, capture, focus, `Press`/`PressDown` synthesis and click counting all behave as they would for real input.
+/// Positions are in physical pixels relative to the window, and views must have been laid out before they
+/// can be hovered or clicked.
+impl Context {
+    /// Simulates moving the mouse cursor to the given position in the main window.
+    pub fn simulate_mouse_move(&mut self, x: f32, y: f32) {
+        self.dispatch_simulated(Entity::root(), WindowEvent::MouseMove(x, y));
+    }
+
+    /// Simulates moving the mouse cursor to the center of the given view.
+    pub fn simulate_mouse_move_to(&mut self, entity: Entity) {
+        let window_entity = self.tree.get_parent_window(entity).unwrap_or(Entity::root());
+        let (x, y) = self.cache.get_bounds(entity).center();
+        self.dispatch_simulated(window_entity, WindowEvent::MouseMove(x, y));
+    }
+
+    /// Simulates pressing a mouse button at the current cursor position.
+    pub fn simulate_mouse_down(&mut self, button: MouseButton) {
+        let window_entity = self.hovered_window();
+        self.dispatch_simulated(window_entity, WindowEvent::MouseDown(button));
+    }
+
+    /// Simulates releasing a mouse button at the current cursor position.
+    pub fn simulate_mouse_up(&mut self, button: MouseButton) {
+        let window_entity = self.hovered_window();
+        self.dispatch_simulated(window_entity, WindowEvent::MouseUp(button));
+    }
+
+    /// Simulates pressing and releasing a mouse button at the current cursor position.
+    pub fn simulate_click(&mut self, button: MouseButton) {
+        self.simulate_mouse_down(button);
+        self.simulate_mouse_up(button);
+    }
+
+    /// Simulates two clicks of a mouse button in quick succession at the current cursor position.
+    pub fn simulate_double_click(&mut self, button: MouseButton) {
+        self.simulate_click(button);
+        self.simulate_click(button);
+    }
+
+    /// Simulates dragging the mouse from one position to another in the main window while holding a mouse button.
+    pub fn simulate_drag(&mut self, button: MouseButton, from: (f32, f32), to: (f32, f32)) {
+        self.simulate_mouse_move(from.0, from.1);
+        self.simulate_mouse_down(button);
+
+        for step in 1..=DRAG_STEPS {
+            let t = step as f32 / DRAG_STEPS as f32;
+            self.simulate_mouse_move(from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+        }
+
+        self.simulate_mouse_up(button);
+    }
+
+    /// Simulates dragging the mouse from the center of one view to the center of another while holding a mouse button.
+    pub fn simulate_drag_to(&mut self, button: MouseButton, from: Entity, to: Entity) {
+        let from = self.cache.get_bounds(from).center();
+        let to = self.cache.get_bounds(to).center();
+        self.simulate_drag(button, from, to);
+    }
+
+    /// Simulates scrolling the mouse wheel by the given number of lines at the current cursor position.
+    pub fn simulate_scroll(&mut self, x: f32, y: f32) {
+        let window_entity = self.hovered_window();
+        self.dispatch_simulated(window_entity, WindowEvent::MouseScroll(x, y));
+    }
+
+    /// Simulates pressing and releasing a key while holding the modifiers of the given key chord.
+    pub fn simulate_key_chord(&mut self, key_chord: KeyChord) {
+        let window_entity = self.focused_window();
+        let modifiers = std::mem::replace(&mut self.modifiers, key_chord.modifiers);
+
+        self.dispatch_simulated(window_entity, WindowEvent::KeyDown(key_chord.code, None));
+        self.dispatch_simulated(window_entity, WindowEvent::KeyUp(key_chord.code, None));
+
+        self.modifiers = modifiers;
+    }
+
+    /// Simulates typing the given text with the keyboard.
+    ///
+    /// Each character produces a [`CharInput`](WindowEvent::CharInput) event, followed by key down and key up
+    /// events when the character corresponds to a key code. Newlines and tabs only produce key events for the
+    /// enter and tab keys. Uppercase and shifted characters are typed while holding the shift key.
+    pub fn simulate_type(&mut self, text: &str) {
+        let window_entity = self.focused_window();
+
+        for character in text.chars() {
+            let modifiers = self.modifiers;
+            let code = match character {
+                '\n' => Some(Code::Enter),
+                '\t' => Some(Code::Tab),
+                _ => {
+                    let code = char_to_code(character);
+                    if code.is_some_and(|(_, shift)| shift) {
+                        self.modifiers |= Modifiers::SHIFT;
+                    }
+
+                    self.dispatch_simulated(window_entity, WindowEvent::CharInput(character));
+                    code.map(|(code, _)| code)
+                }
+            };
+
+            if let Some(code) = code {
+                self.dispatch_simulated(window_entity, WindowEvent::KeyDown(code, None));
+                self.dispatch_simulated(window_entity, WindowEvent::KeyUp(code, None));
+            }
+
+            self.modifiers = modifiers;
+        }
+    }
+
+    /// Simulates an input method updating the text being composed, with the cursor at the given byte range within
+    /// the text. An empty string clears the composed text.
+    pub fn simulate_ime_preedit(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+        let window_entity = self.focused_window();
+        self.dispatch_simulated(window_entity, WindowEvent::ImePreedit(text.to_owned(), cursor));
+    }
+
+    /// Simulates an input method committing the given composed text.
+    pub fn simulate_ime_commit(&mut self, text: &str) {
+        let window_entity = self.focused_window();
+        self.dispatch_simulated(window_entity, WindowEvent::ImeCommit(text.to_owned()));
+    }
+
+    fn hovered_window(&self) -> Entity {
+        self.tree.get_parent_window(self.hovered).unwrap_or(Entity::root())
+    }
+
+    fn focused_window(&self) -> Entity {
+        self.tree.get_parent_window(self.focused).unwrap_or(Entity::root())
+    }
+
+    // Sends a window event in the same way as a windowing backend and dispatches it immediately.
+    fn dispatch_simulated(&mut self, window_entity: Entity, window_event: WindowEvent) {
+        self.event_queue.push_back(
+            Event::new(window_event)
+                .target(window_entity)
+                .origin(window_entity)
+                .propagate(Propagation::Up),
+        );
+
+        EventManager::new().flush_events(self, |_| {});
+    }
+}
+
+// Returns the key code of the key which produces the given character on a US keyboard layout, and whether the shift
+// key must be held to produce it.
+fn char_to_code(character: char) -> Option<(Code, bool)> {
+    let code = match character {
+        'a'..='z' | 'A'..='Z' => format!("Key{}", character.to_ascii_uppercase()).parse().ok()?,
+        '0'..='9' => format!("Digit{character}").parse().ok()?,
+        ' ' => Code::Space,
+        '-' | '_' => Code::Minus,
+        '=' | '+' => Code::Equal,
+        '[' | '{' => Code::BracketLeft,
+        ']' | '}' => Code::BracketRight,
+        '\\' | '|' => Code::Backslash,
+        ';' | ':' => Code::Semicolon,
+        '\'' | '"' => Code::Quote,
+        '`' | '~' => Code::Backquote,
+        ',' | '<' => Code::Comma,
+        '.' | '>' => Code::Period,
+        '/' | '?' => Code::Slash,
+        '!' => Code::Digit1,
+        '@' => Code::Digit2,
+        '#' => Code::Digit3,
+        '$' => Code::Digit4,
+        '%' => Code::Digit5,
+        '^' => Code::Digit6,
+        '&' => Code::Digit7,
+        '*' => Code::Digit8,
+        '(' => Code::Digit9,
+        ')' => Code::Digit0,
+        _ => return None,
+    };
+
+    let shift = character.is_ascii_uppercase() || "_+{}|:\"~<>?!@#$%^&*()".contains(character);
+
+    Some((code, shift))
+}
@@ -72,6 +72,7 @@ impl Environment {
                 cx.emit(TextEvent::ToggleCaret);
             }
         });
+        cx.style.locale = locale.clone();
         Self { locale, theme: Theme::default(), caret_timer }
     }
 }
@@ -94,18 +95,19 @@ impl Model for Environment {
         event.take(|event, _| match event {
             EnvironmentEvent::SetLocale(locale) => {
                 self.locale = locale;
+                set_style_locale(cx, &self.locale);
             }
 
             EnvironmentEvent::SetThemeMode(theme) => {
                 theme.clone_into(&mut self.theme.app_theme);
 
                 cx.set_theme_mode(self.theme.get_current_theme());
-                cx.reload_styles().unwrap();
             }
 
             EnvironmentEvent::UseSystemLocale => {
                 self.locale =
                     sys_locale::get_locale().map(|l| l.parse().unwrap()).unwrap_or_default();
+                set_style_locale(cx, &self.locale);
             }
 
             EnvironmentEvent::ToggleThemeMode => {
@@ -117,7 +119,6 @@ impl Model for Environment {
                 self.theme.app_theme = AppTheme::BuiltIn(theme_mode);
 
                 cx.set_theme_mode(theme_mode);
-                cx.reload_styles().unwrap();
             }
         });
 
@@ -126,10 +127,18 @@ impl Model for Environment {
                 self.theme.sys_theme = Some(*theme);
                 if self.theme.app_theme == AppTheme::System {
                     cx.set_theme_mode(*theme);
-                    cx.reload_styles().unwrap();
                 }
             }
             _ => (),
         })
     }
 }
+
+// Updates the locale used to match `:lang()` and `:dir()` selectors and restyles every view.
+fn set_style_locale(cx: &mut EventContext, locale: &LanguageIdentifier) {
+    cx.style.locale = locale.clone();
+
+    for entity in cx.tree.into_iter() {
+        cx.style.needs_restyle(entity);
+    }
+}
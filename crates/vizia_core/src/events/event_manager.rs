@@ -292,7 +292,7 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
             }
 
             // track double/triple -click
-            let new_click_time = Instant::now();
+            let new_click_time = crate::context::now();
             let click_duration = new_click_time - cx.click_time;
             let new_click_pos = (cx.mouse.cursor_x, cx.mouse.cursor_y);
             if click_duration <= DOUBLE_CLICK_INTERVAL
@@ -467,7 +467,7 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
 
                 let mut filter = BloomFilter::default();
                 compute_element_hash(cx.hovered, &cx.tree, &cx.style, &mut filter);
-                let result = compute_matched_rules(cx.hovered, &cx.style, &cx.tree, &filter);
+                let result = compute_matched_rules(cx.hovered, &cx.style, &cx.tree, Some(&filter));
 
                 let entity = cx.hovered;
                 debug!("/* Matched rules for Entity: {} Parent: {:?} View: {} posx: {} posy: {} width: {} height: {}",
@@ -510,7 +510,8 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
             }
 
             if *code == Code::F5 {
-                EventContext::new(cx).reload_styles().unwrap();
+                // Stylesheets which fail to read are logged and keep their previous rules.
+                EventContext::new(cx).reload_styles().ok();
             }
 
             if *code == Code::Tab {
@@ -612,7 +613,11 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                 cx.triggered = Entity::null();
             }
         }
-        WindowEvent::CharInput(_) => {
+        WindowEvent::CharInput(_)
+        | WindowEvent::ImeEnabled
+        | WindowEvent::ImePreedit(..)
+        | WindowEvent::ImeCommit(_)
+        | WindowEvent::ImeDisabled => {
             meta.target = cx.focused;
         }
         WindowEvent::WindowFocused(is_focused) => {
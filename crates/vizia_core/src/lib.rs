@@ -79,8 +79,9 @@ pub mod prelude {
     pub use super::layout::{BoundingBox, GeoChanged};
     pub use super::localization::{Localized, ToStringLocalized};
     pub use super::modifiers::{
-        AbilityModifiers, AccessibilityModifiers, ActionModifiers, LayoutModifiers,
-        LinearGradientBuilder, ShadowBuilder, StyleModifiers, TextModifiers,
+        AbilityModifiers, AccessibilityModifiers, ActionModifiers, ConicGradientBuilder,
+        LayoutModifiers, LinearGradientBuilder, RadialGradientBuilder, ShadowBuilder,
+        StyleModifiers, TextModifiers,
     };
     pub use super::resource::{ImageId, ImageRetentionPolicy};
     pub use super::util::{IntoCssStr, CSS};
@@ -1,4 +1,5 @@
-use vizia_style::{ColorStop, CornerRadius, Rect};
+use hashbrown::HashMap;
+use vizia_style::{ColorStop, CornerRadius, Rect, UnresolvedValue};
 
 use super::internal;
 use crate::prelude::*;
@@ -85,6 +86,114 @@ pub trait StyleModifiers: internal::Modifiable {
         self
     }
 
+    /// Sets the value of an attribute on the view, which can be matched with attribute selectors in CSS.
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// Element::new(cx).attribute("data-severity", "error");
+    /// ```
+    /// css
+    /// ```css
+    /// element[data-severity=error] {
+    ///     background-color: red;
+    /// }
+    ///```
+    fn attribute<U: ToString>(mut self, name: &str, value: impl Res<U>) -> Self {
+        let name = name.to_owned();
+        let entity = self.entity();
+        let current = self.current();
+        self.context().with_current(current, move |cx| {
+            value.set_or_bind(cx, entity, move |cx, value| {
+                let value = value.get(cx).to_string();
+
+                if let Some(attributes) = cx.style.attributes.get_mut(entity) {
+                    attributes.insert(name.clone(), value);
+                } else {
+                    let mut attributes = HashMap::new();
+                    attributes.insert(name.clone(), value);
+                    cx.style.attributes.insert(entity, attributes);
+                }
+
+                cx.needs_restyle(entity);
+            });
+        });
+
+        self
+    }
+
+    /// Sets the text direction of the view and its descendants, which can be matched with `:dir()` in CSS.
+    ///
+    /// Views without a direction inherit it from their ancestors, or otherwise from the current locale.
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// Element::new(cx).direction(Direction::Rtl);
+    /// ```
+    /// css
+    /// ```css
+    /// element:dir(rtl) {
+    ///     background-color: red;
+    /// }
+    ///```
+    fn direction(mut self, direction: impl Res<Direction>) -> Self {
+        let entity = self.entity();
+        let current = self.current();
+        self.context().with_current(current, move |cx| {
+            direction.set_or_bind(cx, entity, move |cx, direction| {
+                let direction = direction.get(cx);
+                cx.style.direction.insert(entity, direction);
+                cx.needs_restyle(entity);
+            });
+        });
+
+        self
+    }
+
+    /// Sets the value of a custom property on the view.
+    ///
+    /// Custom properties are inherited by the descendants of the view and can be referenced
+    /// by other properties in CSS with `var()`.
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// Element::new(cx).custom_property("--accent", "#ff0000");
+    /// ```
+    /// css
+    /// ```css
+    /// element {
+    ///     background-color: var(--accent, blue);
+    /// }
+    ///```
+    fn custom_property<U: ToString>(mut self, name: &str, value: impl Res<U>) -> Self {
+        let name = name.to_owned();
+        let entity = self.entity();
+        let current = self.current();
+        self.context().with_current(current, move |cx| {
+            value.set_or_bind(cx, entity, move |cx, value| {
+                let value = value.get(cx).to_string();
+                let Some(value) = UnresolvedValue::parse(&value) else {
+                    log::warn!("Failed to parse value of custom property {}: {}", name, value);
+                    return;
+                };
+
+                if let Some(properties) = cx.style.inline_custom_properties.get_mut(entity) {
+                    properties.insert(name.clone(), value);
+                } else {
+                    let mut properties = HashMap::new();
+                    properties.insert(name.clone(), value);
+                    cx.style.inline_custom_properties.insert(entity, properties);
+                }
+
+                cx.needs_restyle(entity);
+            });
+        });
+
+        self
+    }
+
     // PseudoClassFlags
     // TODO: Should these have their own modifiers trait?
 
@@ -317,6 +426,22 @@ pub trait StyleModifiers: internal::Modifiable {
         SystemFlags::REDRAW
     );
 
+    /// Sets the filters applied to the view and its children, such as `grayscale` or `drop-shadow`.
+    fn filter<U: Into<Vec<Filter>>>(mut self, value: impl Res<U>) -> Self {
+        let entity = self.entity();
+        let current = self.current();
+        self.context().with_current(current, |cx| {
+            value.set_or_bind(cx, entity, move |cx, v| {
+                let value = v.get(cx).into();
+                cx.style.filter.insert(cx.current, value);
+
+                cx.needs_redraw(entity);
+            });
+        });
+
+        self
+    }
+
     /// Sets the backdrop filter for the view.
     fn backdrop_filter<U: Into<Filter>>(mut self, value: impl Res<U>) -> Self {
         let entity = self.entity();
@@ -720,6 +845,8 @@ impl<V: View> StyleModifiers for Handle<'_, V> {}
 pub struct LinearGradientBuilder {
     direction: LineDirection,
     stops: Vec<ColorStop<LengthOrPercentage>>,
+    interpolation: ColorInterpolation,
+    repeating: bool,
 }
 
 impl Default for LinearGradientBuilder {
@@ -731,16 +858,26 @@ impl Default for LinearGradientBuilder {
 impl LinearGradientBuilder {
     /// Creates a new [LinearGradientBuilder].
     pub fn new() -> Self {
-        LinearGradientBuilder { direction: LineDirection::default(), stops: Vec::new() }
+        Self::with_direction(LineDirection::default())
     }
 
     /// Set the direction of the linear gradient.
     pub fn with_direction(direction: impl Into<LineDirection>) -> Self {
-        LinearGradientBuilder { direction: direction.into(), stops: Vec::new() }
+        LinearGradientBuilder {
+            direction: direction.into(),
+            stops: Vec::new(),
+            interpolation: ColorInterpolation::default(),
+            repeating: false,
+        }
     }
 
     fn build(self) -> Gradient {
-        Gradient::Linear(LinearGradient { direction: self.direction, stops: self.stops })
+        Gradient::Linear(LinearGradient {
+            direction: self.direction,
+            stops: self.stops,
+            interpolation: self.interpolation,
+            repeating: self.repeating,
+        })
     }
 
     /// Add a color stop to the linear gradient.
@@ -749,6 +886,20 @@ impl LinearGradientBuilder {
 
         self
     }
+
+    /// Set the color space used to interpolate between the color stops.
+    pub fn interpolation(mut self, interpolation: impl Into<ColorInterpolation>) -> Self {
+        self.interpolation = interpolation.into();
+
+        self
+    }
+
+    /// Set whether the color stops repeat, as with `repeating-linear-gradient()`.
+    pub fn repeating(mut self, repeating: bool) -> Self {
+        self.repeating = repeating;
+
+        self
+    }
 }
 
 impl From<LinearGradientBuilder> for Gradient {
@@ -757,6 +908,188 @@ impl From<LinearGradientBuilder> for Gradient {
     }
 }
 
+impl From<LinearGradientBuilder> for BackgroundImage<'_> {
+    fn from(value: LinearGradientBuilder) -> Self {
+        value.build().into()
+    }
+}
+
+impl From<LinearGradientBuilder> for Vec<BackgroundImage<'_>> {
+    fn from(value: LinearGradientBuilder) -> Self {
+        value.build().into()
+    }
+}
+
+/// A builder for constructing radial gradients.
+#[derive(Debug, Clone)]
+pub struct RadialGradientBuilder {
+    position: Position,
+    stops: Vec<ColorStop<LengthOrPercentage>>,
+    interpolation: ColorInterpolation,
+    repeating: bool,
+}
+
+impl Default for RadialGradientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RadialGradientBuilder {
+    /// Creates a new [RadialGradientBuilder] centered on the view.
+    pub fn new() -> Self {
+        Self::with_position(Position::center())
+    }
+
+    /// Set the center of the radial gradient.
+    pub fn with_position(position: impl Into<Position>) -> Self {
+        RadialGradientBuilder {
+            position: position.into(),
+            stops: Vec::new(),
+            interpolation: ColorInterpolation::default(),
+            repeating: false,
+        }
+    }
+
+    fn build(self) -> Gradient {
+        Gradient::Radial(RadialGradient {
+            position: self.position,
+            stops: self.stops,
+            interpolation: self.interpolation,
+            repeating: self.repeating,
+        })
+    }
+
+    /// Add a color stop to the radial gradient.
+    pub fn add_stop(mut self, stop: impl Into<ColorStop<LengthOrPercentage>>) -> Self {
+        self.stops.push(stop.into());
+
+        self
+    }
+
+    /// Set the color space used to interpolate between the color stops.
+    pub fn interpolation(mut self, interpolation: impl Into<ColorInterpolation>) -> Self {
+        self.interpolation = interpolation.into();
+
+        self
+    }
+
+    /// Set whether the color stops repeat, as with `repeating-radial-gradient()`.
+    pub fn repeating(mut self, repeating: bool) -> Self {
+        self.repeating = repeating;
+
+        self
+    }
+}
+
+impl From<RadialGradientBuilder> for Gradient {
+    fn from(value: RadialGradientBuilder) -> Self {
+        value.build()
+    }
+}
+
+impl From<RadialGradientBuilder> for BackgroundImage<'_> {
+    fn from(value: RadialGradientBuilder) -> Self {
+        value.build().into()
+    }
+}
+
+impl From<RadialGradientBuilder> for Vec<BackgroundImage<'_>> {
+    fn from(value: RadialGradientBuilder) -> Self {
+        value.build().into()
+    }
+}
+
+/// A builder for constructing conic gradients.
+#[derive(Debug, Clone)]
+pub struct ConicGradientBuilder {
+    angle: Angle,
+    position: Position,
+    stops: Vec<ColorStop<AnglePercentage>>,
+    interpolation: ColorInterpolation,
+    repeating: bool,
+}
+
+impl Default for ConicGradientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConicGradientBuilder {
+    /// Creates a new [ConicGradientBuilder] centered on the view and starting from the top.
+    pub fn new() -> Self {
+        Self::with_angle(Angle::Deg(0.0))
+    }
+
+    /// Set the angle at which the conic gradient starts, measured clockwise from the top.
+    pub fn with_angle(angle: impl Into<Angle>) -> Self {
+        ConicGradientBuilder {
+            angle: angle.into(),
+            position: Position::center(),
+            stops: Vec::new(),
+            interpolation: ColorInterpolation::default(),
+            repeating: false,
+        }
+    }
+
+    fn build(self) -> Gradient {
+        Gradient::Conic(ConicGradient {
+            angle: self.angle,
+            position: self.position,
+            stops: self.stops,
+            interpolation: self.interpolation,
+            repeating: self.repeating,
+        })
+    }
+
+    /// Set the center of the conic gradient.
+    pub fn position(mut self, position: impl Into<Position>) -> Self {
+        self.position = position.into();
+
+        self
+    }
+
+    /// Add a color stop to the conic gradient.
+    pub fn add_stop(mut self, stop: impl Into<ColorStop<AnglePercentage>>) -> Self {
+        self.stops.push(stop.into());
+
+        self
+    }
+
+    /// Set the color space used to interpolate between the color stops.
+    pub fn interpolation(mut self, interpolation: impl Into<ColorInterpolation>) -> Self {
+        self.interpolation = interpolation.into();
+
+        self
+    }
+
+    /// Set whether the color stops repeat, as with `repeating-conic-gradient()`.
+    pub fn repeating(mut self, repeating: bool) -> Self {
+        self.repeating = repeating;
+
+        self
+    }
+}
+
+impl From<ConicGradientBuilder> for Gradient {
+    fn from(value: ConicGradientBuilder) -> Self {
+        value.build()
+    }
+}
+
+impl From<ConicGradientBuilder> for BackgroundImage<'_> {
+    fn from(value: ConicGradientBuilder) -> Self {
+        value.build().into()
+    }
+}
+
+impl From<ConicGradientBuilder> for Vec<BackgroundImage<'_>> {
+    fn from(value: ConicGradientBuilder) -> Self {
+        value.build().into()
+    }
+}
+
 /// A builder for constructing a shadow.
 #[derive(Debug, Clone)]
 pub struct ShadowBuilder {
@@ -164,6 +164,42 @@ pub trait TextModifiers: internal::Modifiable {
         TextStrokeStyle,
         SystemFlags::REFLOW
     );
+
+    modifier!(
+        /// Sets the shadows drawn behind the text of the view.
+        text_shadow,
+        Vec<Shadow>,
+        SystemFlags::REFLOW
+    );
+
+    modifier!(
+        /// Sets the height of each line of text, either as a multiple of the font size or as a fixed length.
+        line_height,
+        LineHeight,
+        SystemFlags::REFLOW
+    );
+
+    modifier!(
+        /// Sets the additional spacing between characters of the text.
+        letter_spacing,
+        TextSpacing,
+        SystemFlags::REFLOW
+    );
+
+    modifier!(
+        /// Sets the additional spacing between words of the text.
+        word_spacing,
+        TextSpacing,
+        SystemFlags::REFLOW
+    );
+
+    modifier!(
+        /// Sets the indentation of the first line of text. A percentage is relative to the width of the text and a
+        /// negative value outdents the first line.
+        text_indent,
+        LengthOrPercentage,
+        SystemFlags::REFLOW
+    );
 }
 
 impl<V> TextModifiers for Handle<'_, V> {}
@@ -7,10 +7,13 @@ use vizia_id::{GenerationalId, IdManager};
 
 use crate::context::ResourceContext;
 use crate::entity::Entity;
-use crate::prelude::IntoCssStr;
+use crate::prelude::{IntoCssStr, StyleRuleBuilder};
+use crate::style::StyleDiagnostic;
 // use crate::view::Canvas;
 use fluent_bundle::{FluentBundle, FluentResource};
 use hashbrown::{HashMap, HashSet};
+use std::any::TypeId;
+use std::time::SystemTime;
 use unic_langid::LanguageIdentifier;
 
 pub(crate) enum ImageOrSvg {
@@ -26,6 +29,15 @@ pub(crate) struct StoredImage {
     pub observers: HashSet<Entity>,
 }
 
+/// The last successfully parsed content of a stylesheet, along with the modification time of its file and the
+/// diagnostics from when it was last read.
+#[derive(Default)]
+pub(crate) struct StyleSource {
+    pub content: Option<String>,
+    pub modified: Option<SystemTime>,
+    pub diagnostics: Vec<StyleDiagnostic>,
+}
+
 /// An image should be stored in the resource manager.
 #[derive(Copy, Clone, PartialEq)]
 pub enum ImageRetentionPolicy {
@@ -41,7 +53,13 @@ pub enum ImageRetentionPolicy {
 #[derive(Default)]
 pub struct ResourceManager {
     pub themes: Vec<String>, // Themes are the string content stylesheets
+    // Stylesheets declared by view types, added the first time a view of the type is built, along with the name
+    // of the type.
+    pub(crate) view_styles: Vec<(TypeId, &'static str, String)>,
     pub styles: Vec<Box<dyn IntoCssStr>>,
+    // Rules added from Rust with a `StyleSheetBuilder`, which take precedence over stylesheets.
+    pub(crate) style_rules: Vec<StyleRuleBuilder>,
+    pub(crate) style_sources: Vec<StyleSource>,
 
     pub(crate) image_id_manager: IdManager<ImageId>,
     pub(crate) images: HashMap<ImageId, StoredImage>,
@@ -112,11 +130,14 @@ impl ResourceManager {
 
         ResourceManager {
             themes: Vec::new(),
+            view_styles: Vec::new(),
 
             image_id_manager,
             images,
             image_ids: HashMap::new(),
             styles: Vec::new(),
+            style_rules: Vec::new(),
+            style_sources: Vec::new(),
 
             translations: HashMap::from([(
                 LanguageIdentifier::default(),
@@ -128,6 +149,78 @@ impl ResourceManager {
         }
     }
 
+    /// Reads the stylesheet at the given index, returning its content if it has changed since it was last loaded.
+    pub(crate) fn load_style(&mut self, index: usize) -> Result<Option<String>, std::io::Error> {
+        if self.style_sources.len() < self.styles.len() {
+            self.style_sources.resize_with(self.styles.len(), StyleSource::default);
+        }
+
+        let style = &self.styles[index];
+        let source = &mut self.style_sources[index];
+
+        source.modified = style.path().and_then(modified_time);
+
+        let content = style.get_style()?;
+
+        if source.content.as_ref() == Some(&content) {
+            return Ok(None);
+        }
+
+        Ok(Some(content))
+    }
+
+    /// Returns the name used in the diagnostics of the stylesheet at the given index, which is the path of its file
+    /// if it has one.
+    pub(crate) fn style_filename(&self, index: usize) -> String {
+        self.styles[index]
+            .path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| format!("<stylesheet {}>", index))
+    }
+
+    /// Returns the indices of the file-backed stylesheets which have been modified on disk since they were last read.
+    pub(crate) fn modified_styles(&self) -> Vec<usize> {
+        self.styles
+            .iter()
+            .zip(self.style_sources.iter())
+            .enumerate()
+            .filter(|(_, (style, source))| {
+                style
+                    .path()
+                    .and_then(modified_time)
+                    .is_some_and(|modified| Some(modified) != source.modified)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the name and content of the themes, followed by the stylesheets of view types and then the loaded
+    /// stylesheets, so that later stylesheets take precedence. A stylesheet which failed to load is empty.
+    pub(crate) fn style_contents(&self) -> impl Iterator<Item = (String, &str)> {
+        self.themes
+            .iter()
+            .enumerate()
+            .map(|(index, theme)| (theme_filename(index), theme.as_str()))
+            .chain(
+                self.view_styles
+                    .iter()
+                    .map(|(_, name, style)| (view_style_filename(name), style.as_str())),
+            )
+            .chain(self.style_sources.iter().enumerate().map(|(index, source)| {
+                (self.style_filename(index), source.content.as_deref().unwrap_or_default())
+            }))
+    }
+
+    /// Returns the position of the stylesheet at the given index within [`style_contents`](Self::style_contents).
+    pub(crate) fn style_position(&self, index: usize) -> usize {
+        self.themes.len() + self.view_styles.len() + index
+    }
+
+    /// Returns the diagnostics from the most recent parse of each stylesheet.
+    pub(crate) fn style_diagnostics(&self) -> impl Iterator<Item = &StyleDiagnostic> {
+        self.style_sources.iter().flat_map(|source| source.diagnostics.iter())
+    }
+
     pub fn renegotiate_language(&mut self) {
         let available = self
             .translations
@@ -196,3 +289,17 @@ impl ResourceManager {
         }
     }
 }
+
+/// Returns the name used in the diagnostics of the theme at the given index.
+pub(crate) fn theme_filename(index: usize) -> String {
+    format!("<theme {}>", index)
+}
+
+/// Returns the name used in the diagnostics of the stylesheet declared by the view type with the given name.
+pub(crate) fn view_style_filename(name: &str) -> String {
+    format!("<stylesheet of {}>", name)
+}
+
+fn modified_time(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
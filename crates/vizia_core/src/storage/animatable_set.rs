@@ -1,5 +1,6 @@
 use crate::animation::{AnimationState, Interpolator};
 use crate::prelude::*;
+use crate::storage::DataOrigin;
 use vizia_storage::{SparseSet, SparseSetGeneric, SparseSetIndex};
 
 const INDEX_MASK: u32 = u32::MAX / 4;
@@ -289,13 +290,48 @@ where
         self.animations.insert(animation, animation_description);
     }
 
+    /// Removes an animation, which stops it from being played again. Playing instances of the animation continue
+    /// until they finish.
+    pub(crate) fn remove_animation(&mut self, animation: Animation) {
+        self.animations.remove(animation);
+    }
+
     pub(crate) fn insert_rule(&mut self, rule: Rule, value: T) {
         self.shared_data.insert(rule, value);
     }
 
-    // pub(crate) fn remove_rule(&mut self, rule: Rule) -> Option<T> {
-    //     self.shared_data.remove(rule)
-    // }
+    /// Removes the shared data and transitions of the given rules.
+    ///
+    /// Removing shared data moves the data of other rules, so every entity is unlinked from shared data and must be
+    /// relinked.
+    pub(crate) fn remove_rules(&mut self, rules: &[Rule]) {
+        for rule in rules {
+            if !self.shared_data.contains(*rule) {
+                continue;
+            }
+
+            let animation = self.shared_data.sparse[rule.index()].animation;
+            self.animations.remove(animation);
+
+            // The data of the last rule is moved into the place of the removed data, but must keep its own transition.
+            let last = self.shared_data.dense.last().map(|entry| entry.key.index());
+            let last_animation = last.map(|last| self.shared_data.sparse[last].animation);
+
+            self.shared_data.remove(*rule);
+
+            if let (Some(last), Some(last_animation)) = (last, last_animation) {
+                if last != rule.index() {
+                    self.shared_data.sparse[last].animation = last_animation;
+                }
+            }
+        }
+
+        for index in self.inline_data.sparse.iter_mut() {
+            if !index.data_index.is_inline() {
+                index.data_index = DataIndex::null();
+            }
+        }
+    }
 
     /// Inserts a transition for a given rule
     ///
@@ -603,6 +639,31 @@ where
         None
     }
 
+    /// Returns where the value of the entity is stored, or `None` if the entity has no value.
+    pub(crate) fn origin(&self, entity: Entity) -> Option<DataOrigin> {
+        let inline_index = self.inline_data.sparse.get(entity.index())?;
+        if (inline_index.anim_index as usize) < self.active_animations.len() {
+            return Some(DataOrigin::Animation);
+        }
+
+        let data_index = inline_index.data_index;
+        if data_index.is_inline() {
+            if data_index.index() >= self.inline_data.dense.len() {
+                return None;
+            }
+        } else if data_index.index() >= self.shared_data.dense.len() {
+            return None;
+        }
+
+        Some(if data_index.is_inherited() {
+            DataOrigin::Inherited
+        } else if data_index.is_inline() {
+            DataOrigin::Inline
+        } else {
+            DataOrigin::Shared(self.shared_data.dense[data_index.index()].key.index())
+        })
+    }
+
     /// Link an entity to some shared data.
     pub(crate) fn link(&mut self, entity: Entity, rules: &[(Rule, u32)]) -> bool {
         let entity_index = entity.index();
@@ -653,7 +714,7 @@ where
                                         .clone();
 
                                 current_anim_state.dt = current_anim_state.t - 1.0;
-                                current_anim_state.start_time = Instant::now();
+                                current_anim_state.start_time = crate::context::now();
                             } else {
                                 // Transitioning to new rule
                                 current_anim_state.to_rule = rule_data_index;
@@ -664,7 +725,7 @@ where
                                         .value
                                         .clone();
                                 current_anim_state.t = 0.0;
-                                current_anim_state.start_time = Instant::now();
+                                current_anim_state.start_time = crate::context::now();
                             }
                         }
                     }
@@ -698,7 +759,7 @@ where
                         self.play_animation(
                             entity,
                             rule_animation,
-                            Instant::now(),
+                            crate::context::now(),
                             duration,
                             delay,
                         );
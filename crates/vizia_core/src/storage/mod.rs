@@ -1,2 +1,15 @@
 pub(crate) mod animatable_set;
 pub(crate) mod style_set;
+
+/// Describes where the value of a style property for an entity is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DataOrigin {
+    /// Inline data set on the entity.
+    Inline,
+    /// Shared data of the style rule with the given index.
+    Shared(usize),
+    /// Inline or shared data inherited from an ancestor.
+    Inherited,
+    /// The output of an active animation or transition.
+    Animation,
+}
@@ -1,5 +1,6 @@
 #![allow(unused)]
 use crate::prelude::*;
+use crate::storage::DataOrigin;
 
 use vizia_storage::{SparseSetGeneric, SparseSetIndex};
 
@@ -252,9 +253,21 @@ where
         self.shared_data.insert(rule, value);
     }
 
-    // pub(crate) fn remove_rule(&mut self, rule: Rule) -> Option<T> {
-    //     self.shared_data.remove(rule)
-    // }
+    /// Removes the shared data of the given rules.
+    ///
+    /// Removing shared data moves the data of other rules, so every entity is unlinked from shared data and must be
+    /// relinked.
+    pub(crate) fn remove_rules(&mut self, rules: &[Rule]) {
+        for rule in rules {
+            self.shared_data.remove(*rule);
+        }
+
+        for index in self.inline_data.sparse.iter_mut() {
+            if !index.data_index.is_inline() {
+                index.data_index = DataIndex::null();
+            }
+        }
+    }
 
     /// Returns a reference to any inline data on the entity if it exists.
     pub fn get_inline(&self, entity: Entity) -> Option<&T> {
@@ -321,6 +334,26 @@ where
         None
     }
 
+    /// Returns where the value of the entity is stored, or `None` if the entity has no value.
+    pub(crate) fn origin(&self, entity: Entity) -> Option<DataOrigin> {
+        let data_index = self.inline_data.sparse.get(entity.index())?.data_index;
+        if data_index.is_inline() {
+            if data_index.index() >= self.inline_data.dense.len() {
+                return None;
+            }
+        } else if data_index.index() >= self.shared_data.dense.len() {
+            return None;
+        }
+
+        Some(if data_index.is_inherited() {
+            DataOrigin::Inherited
+        } else if data_index.is_inline() {
+            DataOrigin::Inline
+        } else {
+            DataOrigin::Shared(self.shared_data.dense[data_index.index()].key.index())
+        })
+    }
+
     /// Link an entity to some shared data.
     pub(crate) fn link(&mut self, entity: Entity, rules: &[(Rule, u32)]) -> bool {
         let entity_index = entity.index();
@@ -0,0 +1,362 @@
+use vizia_style::{serialize_selector_ident, Property};
+
+use crate::prelude::*;
+
+/// A builder for constructing a selector from an element name, class names, an ID and pseudo-classes.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// // Equivalent to `button.primary:hover`.
+/// let selector = SelectorBuilder::new()
+///     .element("button")
+///     .class("primary")
+///     .pseudo_class(PseudoClassFlags::HOVER);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SelectorBuilder {
+    element: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    pseudo_classes: PseudoClassFlags,
+    ancestor: Option<(Box<SelectorBuilder>, bool)>,
+}
+
+impl SelectorBuilder {
+    /// Creates a new [SelectorBuilder] which matches any view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the element name to match.
+    pub fn element(mut self, element: impl Into<String>) -> Self {
+        self.element = Some(element.into());
+
+        self
+    }
+
+    /// Set the ID to match.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+
+        self
+    }
+
+    /// Add a class name to match.
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+
+        self
+    }
+
+    /// Add the pseudo-classes to match, such as `PseudoClassFlags::HOVER`.
+    pub fn pseudo_class(mut self, pseudo_classes: PseudoClassFlags) -> Self {
+        self.pseudo_classes |= pseudo_classes;
+
+        self
+    }
+
+    /// Only match views which are descendants of a view matching the given selector.
+    pub fn descendant_of(mut self, ancestor: SelectorBuilder) -> Self {
+        self.ancestor = Some((Box::new(ancestor), false));
+
+        self
+    }
+
+    /// Only match views which are direct children of a view matching the given selector.
+    pub fn child_of(mut self, parent: SelectorBuilder) -> Self {
+        self.ancestor = Some((Box::new(parent), true));
+
+        self
+    }
+
+    // Writes the selector as CSS, escaping the names so that they cannot change the structure of the selector.
+    fn write_css(&self, dest: &mut String) {
+        if let Some((ancestor, is_parent)) = &self.ancestor {
+            ancestor.write_css(dest);
+            dest.push_str(if *is_parent { " > " } else { " " });
+        }
+
+        match &self.element {
+            Some(element) => serialize_selector_ident(element, dest),
+            None => dest.push('*'),
+        }
+
+        if let Some(id) = &self.id {
+            dest.push('#');
+            serialize_selector_ident(id, dest);
+        }
+
+        for class in self.classes.iter() {
+            dest.push('.');
+            serialize_selector_ident(class, dest);
+        }
+
+        for (flag, name) in PSEUDO_CLASS_NAMES {
+            if self.pseudo_classes.contains(flag) {
+                dest.push(':');
+                dest.push_str(name);
+            }
+        }
+    }
+
+    pub(crate) fn to_css(&self) -> String {
+        let mut css = String::new();
+        self.write_css(&mut css);
+        css
+    }
+}
+
+const PSEUDO_CLASS_NAMES: [(PseudoClassFlags, &str); 21] = [
+    (PseudoClassFlags::HOVER, "hover"),
+    (PseudoClassFlags::ACTIVE, "active"),
+    (PseudoClassFlags::OVER, "over"),
+    (PseudoClassFlags::FOCUS, "focus"),
+    (PseudoClassFlags::FOCUS_VISIBLE, "focus-visible"),
+    (PseudoClassFlags::FOCUS_WITHIN, "focus-within"),
+    (PseudoClassFlags::READ_ONLY, "read-only"),
+    (PseudoClassFlags::READ_WRITE, "read-write"),
+    (PseudoClassFlags::PLACEHOLDER_SHOWN, "placeholder-shown"),
+    (PseudoClassFlags::DEFAULT, "default"),
+    (PseudoClassFlags::CHECKED, "checked"),
+    (PseudoClassFlags::INDETERMINATE, "indeterminate"),
+    (PseudoClassFlags::BLANK, "blank"),
+    (PseudoClassFlags::VALID, "valid"),
+    (PseudoClassFlags::INVALID, "invalid"),
+    (PseudoClassFlags::IN_RANGE, "in-range"),
+    (PseudoClassFlags::OUT_OF_RANGE, "out-of-range"),
+    (PseudoClassFlags::REQUIRED, "required"),
+    (PseudoClassFlags::OPTIONAL, "optional"),
+    (PseudoClassFlags::USER_VALID, "user-valid"),
+    (PseudoClassFlags::USER_INVALID, "user-invalid"),
+];
+
+/// A builder for constructing a set of style rules in Rust, as an alternative to a CSS stylesheet.
+///
+/// The rules are added to the application with [`add_style_rules`](Context::add_style_rules).
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// # let cx = &mut Context::default();
+/// let brand = Color::rgb(40, 90, 200);
+/// let brand_hover = Color::rgb(60, 110, 220);
+///
+/// cx.add_style_rules(
+///     StyleSheetBuilder::new()
+///         .rule(SelectorBuilder::new().element("button").class("primary"), |rule| {
+///             rule.background_color(brand).color(Color::white())
+///         })
+///         .rule(
+///             SelectorBuilder::new().element("button").class("primary").pseudo_class(PseudoClassFlags::HOVER),
+///             |rule| rule.background_color(brand_hover),
+///         ),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StyleSheetBuilder {
+    pub(crate) rules: Vec<StyleRuleBuilder>,
+}
+
+impl StyleSheetBuilder {
+    /// Creates a new [StyleSheetBuilder].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule which applies the declarations of the builder to views matching the selector.
+    pub fn rule(
+        mut self,
+        selector: SelectorBuilder,
+        rule: impl FnOnce(StyleRuleBuilder) -> StyleRuleBuilder,
+    ) -> Self {
+        self.rules.push((rule)(StyleRuleBuilder::new(selector)));
+
+        self
+    }
+}
+
+/// A builder for constructing the declarations of a style rule.
+#[derive(Debug, Clone)]
+pub struct StyleRuleBuilder {
+    pub(crate) selector: SelectorBuilder,
+    pub(crate) properties: Vec<Property<'static>>,
+}
+
+// Generates the builder methods which push a property declaration onto `self.properties`, shared by
+// `StyleRuleBuilder` and `KeyframeBuilder`. The target names the builder in the docs of the methods, and the
+// lifetime is that of the declarations. Any additional setters are written as `name: type => Property, "doc";`.
+macro_rules! property_setters {
+    (@setters $target:literal; $($name:ident: $t:ty => $variant:ident, $doc:literal;)*) => {
+        $(
+            #[doc = concat!("Set the ", $doc, " for the ", $target, ".")]
+            pub fn $name(mut self, val: impl Into<$t>) -> Self {
+                self.properties.push(vizia_style::Property::$variant(val.into()));
+
+                self
+            }
+        )*
+    };
+
+    ($target:literal, $lt:lifetime $(; $($extra:tt)*)?) => {
+        $crate::style::property_setters!(@setters $target;
+            // DISPLAY
+            display: vizia_style::Display => Display, "display value";
+            visibility: vizia_style::Visibility => Visibility, "visibility value";
+            overflow: vizia_style::Overflow => Overflow, "overflow value";
+            clip_path: vizia_style::ClipPath => ClipPath, "clip-path value";
+            opacity: vizia_style::Opacity => Opacity, "opacity value";
+            z_index: i32 => ZIndex, "z-index value";
+
+            // LAYOUT
+            layout_type: vizia_style::LayoutType => LayoutType, "layout type value";
+            position_type: vizia_style::PositionType => PositionType, "position type value";
+            alignment: vizia_style::Alignment => Alignment, "alignment value";
+            left: vizia_style::Units => Left, "left space value";
+            right: vizia_style::Units => Right, "right space value";
+            top: vizia_style::Units => Top, "top space value";
+            bottom: vizia_style::Units => Bottom, "bottom space value";
+            width: vizia_style::Units => Width, "width value";
+            height: vizia_style::Units => Height, "height value";
+            size: vizia_style::Units => Size, "width and height values";
+            min_width: vizia_style::Units => MinWidth, "minimum width value";
+            max_width: vizia_style::Units => MaxWidth, "maximum width value";
+            min_height: vizia_style::Units => MinHeight, "minimum height value";
+            max_height: vizia_style::Units => MaxHeight, "maximum height value";
+            padding: vizia_style::Units => Padding, "padding value for all sides";
+            padding_left: vizia_style::Units => PaddingLeft, "left padding value";
+            padding_right: vizia_style::Units => PaddingRight, "right padding value";
+            padding_top: vizia_style::Units => PaddingTop, "top padding value";
+            padding_bottom: vizia_style::Units => PaddingBottom, "bottom padding value";
+            gap: vizia_style::Units => Gap, "horizontal and vertical gap values";
+            horizontal_gap: vizia_style::Units => HorizontalGap, "horizontal gap value";
+            vertical_gap: vizia_style::Units => VerticalGap, "vertical gap value";
+
+            // BACKGROUND
+            background_color: vizia_style::Color => BackgroundColor, "background color value";
+            background_image: Vec<vizia_style::BackgroundImage<$lt>> => BackgroundImage, "background image value";
+            background_size: Vec<vizia_style::BackgroundSize> => BackgroundSize, "background size value";
+
+            // BORDER
+            border_width: vizia_style::BorderWidth => BorderWidth, "border width value";
+            border_color: vizia_style::Color => BorderColor, "border color value";
+            corner_radius: vizia_style::CornerRadius => CornerRadius, "corner radius value";
+            border_style: vizia_style::BorderStyle => BorderStyle, "border style value";
+            corner_top_left_radius: vizia_style::LengthOrPercentage => CornerTopLeftRadius, "top-left corner radius value";
+            corner_top_right_radius: vizia_style::LengthOrPercentage => CornerTopRightRadius, "top-right corner radius value";
+            corner_bottom_left_radius: vizia_style::LengthOrPercentage => CornerBottomLeftRadius, "bottom-left corner radius value";
+            corner_bottom_right_radius: vizia_style::LengthOrPercentage => CornerBottomRightRadius, "bottom-right corner radius value";
+
+            // OUTLINE
+            outline_width: vizia_style::BorderWidth => OutlineWidth, "outline width value";
+            outline_color: vizia_style::Color => OutlineColor, "outline color value";
+            outline_offset: vizia_style::LengthOrPercentage => OutlineOffset, "outline offset value";
+
+            // SHADOW AND FILTER
+            shadow: Vec<vizia_style::Shadow> => Shadow, "shadow value";
+            filter: Vec<vizia_style::Filter> => Filter, "filter value";
+            backdrop_filter: vizia_style::Filter => BackdropFilter, "backdrop filter value";
+
+            // TRANSFORM
+            transform: Vec<vizia_style::Transform> => Transform, "transform value";
+            transform_origin: vizia_style::Position => TransformOrigin, "transform origin value";
+            translate: vizia_style::Translate => Translate, "translate value";
+            rotate: vizia_style::Angle => Rotate, "rotate value";
+            scale: vizia_style::Scale => Scale, "scale value";
+
+            // TEXT
+            color: vizia_style::Color => FontColor, "font color value";
+            font_family: Vec<vizia_style::FontFamily<$lt>> => FontFamily, "font family value";
+            font_size: vizia_style::FontSize => FontSize, "font size value";
+            font_weight: vizia_style::FontWeight => FontWeight, "font weight value";
+            font_slant: vizia_style::FontSlant => FontSlant, "font slant value";
+            text_align: vizia_style::TextAlign => TextAlign, "text alignment value";
+            text_wrap: bool => TextWrap, "text wrap value";
+            text_overflow: vizia_style::TextOverflow => TextOverflow, "text overflow value";
+            text_decoration_line: vizia_style::TextDecorationLine => TextDecorationLine, "text decoration line value";
+            line_height: vizia_style::LineHeight => LineHeight, "line height value";
+            letter_spacing: vizia_style::TextSpacing => LetterSpacing, "letter spacing value";
+            word_spacing: vizia_style::TextSpacing => WordSpacing, "word spacing value";
+            text_shadow: Vec<vizia_style::Shadow> => TextShadow, "text shadow value";
+            caret_color: vizia_style::Color => CaretColor, "caret color value";
+            selection_color: vizia_style::Color => SelectionColor, "selection color value";
+            font_variation_settings: Vec<vizia_style::FontVariation> => FontVariationSettings, "font variation settings value";
+            text_indent: vizia_style::LengthOrPercentage => TextIndent, "text indent value";
+
+            // INTERACTION
+            cursor: vizia_style::CursorIcon => Cursor, "cursor icon value";
+            pointer_events: vizia_style::PointerEvents => PointerEvents, "pointer events value";
+            $($($extra)*)?
+        );
+    };
+}
+
+pub(crate) use property_setters;
+
+impl StyleRuleBuilder {
+    pub(crate) fn new(selector: SelectorBuilder) -> Self {
+        Self { selector, properties: Vec::new() }
+    }
+
+    property_setters!("rule", 'static;
+        transition: Vec<vizia_style::Transition> => Transition, "transition value";
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectors_are_written_as_css() {
+        let selector = SelectorBuilder::new()
+            .element("button")
+            .class("primary")
+            .id("submit")
+            .pseudo_class(PseudoClassFlags::HOVER | PseudoClassFlags::FOCUS)
+            .child_of(SelectorBuilder::new().class("toolbar"));
+        assert_eq!(selector.to_css(), "*.toolbar > button#submit.primary:hover:focus");
+
+        // Names are escaped rather than interpreted as selector syntax.
+        let selector = SelectorBuilder::new().class("a b");
+        assert_eq!(selector.to_css(), "*.a\\ b");
+    }
+
+    #[test]
+    fn built_rules_apply_to_matching_views() {
+        let cx = &mut Context::default();
+        cx.add_stylesheet(".swatch { background-color: #000000; }").unwrap();
+
+        let mut stylesheet = StyleSheetBuilder::new();
+        for (name, color) in
+            [("primary", Color::rgb(255, 0, 0)), ("secondary", Color::rgb(0, 0, 255))]
+        {
+            stylesheet = stylesheet
+                .rule(SelectorBuilder::new().class("swatch").class(name), |rule| {
+                    rule.background_color(color)
+                })
+                .rule(
+                    SelectorBuilder::new()
+                        .class("swatch")
+                        .class(name)
+                        .pseudo_class(PseudoClassFlags::HOVER),
+                    |rule| rule.background_color(Color::rgb(0, 255, 0)),
+                );
+        }
+        cx.add_style_rules(stylesheet);
+
+        let primary = Element::new(cx).class("swatch").class("primary").entity();
+        let secondary = Element::new(cx).class("swatch").class("secondary").entity();
+        crate::systems::style_system(cx);
+
+        let background_color =
+            |cx: &Context, entity: Entity| cx.style.background_color.get(entity).copied();
+        assert_eq!(background_color(cx, primary), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(background_color(cx, secondary), Some(Color::rgb(0, 0, 255)));
+
+        cx.style.pseudo_classes.get_mut(primary).unwrap().set(PseudoClassFlags::HOVER, true);
+        cx.needs_restyle(primary);
+        crate::systems::style_system(cx);
+        assert_eq!(background_color(cx, primary), Some(Color::rgb(0, 255, 0)));
+    }
+}
@@ -0,0 +1,115 @@
+use skia_safe::{Matrix, Path, PathDirection, Point, RRect, Rect};
+use vizia_style::{ClipPath, LengthOrPercentage};
+
+use crate::layout::BoundingBox;
+
+/// Trait for converting a clip path definition into a `Path`.
+pub(crate) trait IntoClipShape {
+    /// Returns the clipping shape for a view with the given bounds, or `None` if the view is not clipped.
+    fn as_clip_shape(&self, bounds: BoundingBox, scale_factor: f32) -> Option<Path>;
+}
+
+impl IntoClipShape for ClipPath {
+    fn as_clip_shape(&self, bounds: BoundingBox, scale_factor: f32) -> Option<Path> {
+        let x = |length: &LengthOrPercentage| length.to_pixels(bounds.w, scale_factor);
+        let y = |length: &LengthOrPercentage| length.to_pixels(bounds.h, scale_factor);
+
+        let mut path = Path::new();
+
+        match self {
+            ClipPath::Auto => return None,
+
+            ClipPath::Shape(insets) => {
+                let rect =
+                    bounds.shrink_sides(x(&insets.3), y(&insets.0), x(&insets.1), y(&insets.2));
+                path.add_rect(Rect::from(rect), None);
+            }
+
+            ClipPath::Inset(insets, radii) => {
+                let rect =
+                    bounds.shrink_sides(x(&insets.3), y(&insets.0), x(&insets.1), y(&insets.2));
+                let radius = |length: &LengthOrPercentage| Point::new(x(length), y(length));
+                let rrect = RRect::new_rect_radii(
+                    Rect::from(rect),
+                    &[radius(&radii.0), radius(&radii.1), radius(&radii.2), radius(&radii.3)],
+                );
+                path.add_rrect(rrect, None);
+            }
+
+            ClipPath::Circle(radius, center) => {
+                let cx = bounds.x + x(&center.x.to_length_or_percentage());
+                let cy = bounds.y + y(&center.y.to_length_or_percentage());
+                let radius = match radius {
+                    // Percentages are relative to the normalized diagonal of the view.
+                    Some(radius) => radius.to_pixels(
+                        (bounds.w * bounds.w + bounds.h * bounds.h).sqrt()
+                            / std::f32::consts::SQRT_2,
+                        scale_factor,
+                    ),
+                    None => (cx - bounds.left())
+                        .min(bounds.right() - cx)
+                        .min(cy - bounds.top())
+                        .min(bounds.bottom() - cy),
+                };
+                path.add_circle((cx, cy), radius.max(0.0), PathDirection::CW);
+            }
+
+            ClipPath::Ellipse(radii, center) => {
+                let cx = bounds.x + x(&center.x.to_length_or_percentage());
+                let cy = bounds.y + y(&center.y.to_length_or_percentage());
+                let (rx, ry) = match radii {
+                    Some((rx, ry)) => (x(rx), y(ry)),
+                    None => (
+                        (cx - bounds.left()).min(bounds.right() - cx),
+                        (cy - bounds.top()).min(bounds.bottom() - cy),
+                    ),
+                };
+                let (rx, ry) = (rx.max(0.0), ry.max(0.0));
+                path.add_oval(Rect::from_ltrb(cx - rx, cy - ry, cx + rx, cy + ry), None);
+            }
+
+            ClipPath::Polygon(points) => {
+                let points = points
+                    .iter()
+                    .map(|(px, py)| Point::new(bounds.x + x(px), bounds.y + y(py)))
+                    .collect::<Vec<_>>();
+                path.add_poly(&points, true);
+            }
+
+            ClipPath::Path(data) => {
+                let mut matrix = Matrix::scale((scale_factor, scale_factor));
+                matrix.post_translate((bounds.x, bounds.y));
+                path = Path::from_svg(data)?.with_transform(&matrix);
+            }
+        }
+
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_are_positioned_within_bounds() {
+        let bounds = BoundingBox { x: 100.0, y: 100.0, w: 40.0, h: 20.0 };
+
+        let circle = ClipPath::from("circle()").as_clip_shape(bounds, 1.0).unwrap();
+        assert!(circle.contains((120.0, 110.0)));
+        assert!(!circle.contains((101.0, 101.0)));
+
+        let polygon = ClipPath::from("polygon(50% 0%, 100% 100%, 0% 100%)")
+            .as_clip_shape(bounds, 1.0)
+            .unwrap();
+        assert!(polygon.contains((120.0, 115.0)));
+        assert!(!polygon.contains((102.0, 102.0)));
+
+        let path =
+            ClipPath::from("path(\"M 0 0 L 10 0 L 10 10 Z\")").as_clip_shape(bounds, 2.0).unwrap();
+        assert!(path.contains((118.0, 102.0)));
+        assert!(!path.contains((102.0, 118.0)));
+
+        assert!(ClipPath::Auto.as_clip_shape(bounds, 1.0).is_none());
+    }
+}
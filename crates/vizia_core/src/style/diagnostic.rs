@@ -0,0 +1,127 @@
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use vizia_style::{ParserOptions, StyleSheet};
+
+/// The severity of a [`StyleDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    /// A declaration could not be parsed, such as an unknown property or an invalid value, and has no effect.
+    Warning,
+    /// A rule or declaration was dropped from the stylesheet, such as a rule with an unsupported selector.
+    Error,
+}
+
+/// A problem found while parsing a stylesheet, along with its location in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleDiagnostic {
+    /// The severity of the problem.
+    pub severity: DiagnosticSeverity,
+    /// A description of the problem.
+    pub message: String,
+    /// The file path of the stylesheet, or a placeholder name for stylesheets which are not read from a file.
+    pub filename: String,
+    /// The line number, starting at 1.
+    pub line: u32,
+    /// The column number, starting at 1.
+    pub column: u32,
+}
+
+impl fmt::Display for StyleDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Error => "error",
+        };
+
+        write!(f, "{}:{}:{}: {}: {}", self.filename, self.line, self.column, severity, self.message)
+    }
+}
+
+/// Parses a stylesheet, returning its rules along with the diagnostics for any problems found. The rules are `None`
+/// if the stylesheet could not be parsed.
+pub(crate) fn parse_stylesheet<'i>(
+    filename: &str,
+    stylesheet: &'i str,
+) -> (Option<StyleSheet<'i>>, Vec<StyleDiagnostic>) {
+    let warnings = Arc::new(RwLock::new(Vec::new()));
+    let options = ParserOptions {
+        filename: filename.to_owned(),
+        warnings: Some(warnings.clone()),
+        ..ParserOptions::new()
+    };
+
+    let (stylesheet, error) = match StyleSheet::parse(stylesheet, options) {
+        Ok(stylesheet) => (Some(stylesheet), None),
+        Err(err) => (None, Some(err)),
+    };
+
+    let warnings = warnings.read().map(|warnings| warnings.clone()).unwrap_or_default();
+
+    let diagnostics = warnings
+        .into_iter()
+        .chain(error)
+        .map(|error| {
+            let (line, column) =
+                error.location.as_ref().map(|loc| (loc.line + 1, loc.column)).unwrap_or((0, 0));
+
+            StyleDiagnostic {
+                severity: if error.kind.is_warning() {
+                    DiagnosticSeverity::Warning
+                } else {
+                    DiagnosticSeverity::Error
+                },
+                message: error.kind.to_string(),
+                filename: filename.to_owned(),
+                line,
+                column,
+            }
+        })
+        .collect();
+
+    (stylesheet, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn diagnostics_have_locations() {
+        let (stylesheet, diagnostics) = parse_stylesheet(
+            "theme.css",
+            ".a {\n    width: wide;\n    colour: red;\n}\n.b:nope(1) {}\n",
+        );
+
+        assert!(stylesheet.is_some());
+
+        assert_eq!(
+            diagnostics.iter().map(|d| (d.severity, d.line)).collect::<Vec<_>>(),
+            vec![
+                (DiagnosticSeverity::Warning, 2),
+                (DiagnosticSeverity::Warning, 3),
+                (DiagnosticSeverity::Error, 5),
+            ]
+        );
+
+        assert_eq!(diagnostics[0].message, "Invalid value for property: width");
+        assert_eq!(diagnostics[1].message, "Unknown property: colour");
+        assert!(diagnostics[1].to_string().starts_with("theme.css:3:"));
+    }
+
+    #[test]
+    fn stylesheet_diagnostics_report_locations() {
+        let cx = &mut Context::default();
+
+        let missing = std::env::temp_dir().join("vizia_missing_stylesheet.css");
+        assert!(cx.add_stylesheet(missing).is_err());
+
+        cx.add_stylesheet(".swatch {\n    backgrond-color: red;\n}\n").unwrap();
+
+        let diagnostics = cx.style_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(diagnostics[0].filename, "<stylesheet 0>");
+    }
+}
@@ -0,0 +1,307 @@
+use vizia_style::selector_to_css;
+
+use crate::prelude::*;
+use crate::storage::DataOrigin;
+use crate::style::{Rule, Style};
+use crate::systems::compute_matched_rules;
+
+/// A style rule which matches a view, as returned by [`Context::matched_rules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedRule {
+    /// The CSS text of the selector of the rule, such as `list > list-item:checked`.
+    pub selector: String,
+    /// The specificity of the selector.
+    pub specificity: u32,
+}
+
+/// Describes where the computed value of a style property comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleOrigin {
+    /// The value was set inline on the view, such as with a style modifier.
+    Inline,
+    /// The value was set by a style rule which matches the view.
+    Rule(MatchedRule),
+    /// The value was inherited from an ancestor.
+    Inherited,
+    /// The value is the output of a running animation or transition.
+    Animation,
+    /// The property has not been set, so the default value applies.
+    Default,
+}
+
+/// The computed value of a style property for a view, along with its origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputedProperty {
+    /// The CSS name of the property, such as `background-color`.
+    pub name: &'static str,
+    /// The debug representation of the value, or `None` if the property has not been set.
+    pub value: Option<String>,
+    /// Where the value comes from.
+    pub origin: StyleOrigin,
+}
+
+/// The matched rules and computed style properties of a view, as returned by [`Context::computed_style`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputedStyle {
+    /// The style rules which match the view, in order of precedence.
+    pub matched_rules: Vec<MatchedRule>,
+    /// The computed value of each style property.
+    pub properties: Vec<ComputedProperty>,
+}
+
+impl ComputedStyle {
+    /// Returns the computed property with the given CSS name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&ComputedProperty> {
+        self.properties.iter().find(|property| property.name == name)
+    }
+
+    /// Returns the properties which have been set on the view, ignoring those with a default value.
+    pub fn specified(&self) -> impl Iterator<Item = &ComputedProperty> {
+        self.properties.iter().filter(|property| property.origin != StyleOrigin::Default)
+    }
+}
+
+/// Returns the style rules which match an entity, in order of precedence, along with their ids.
+fn matched_rules(entity: Entity, style: &Style, tree: &Tree<Entity>) -> Vec<(Rule, MatchedRule)> {
+    compute_matched_rules(entity, style, tree, None)
+        .into_iter()
+        .filter_map(|(rule, specificity)| {
+            let style_rule = style.rules.get(&rule)?;
+            Some((
+                rule,
+                MatchedRule { selector: selector_to_css(&style_rule.selector), specificity },
+            ))
+        })
+        .collect()
+}
+
+/// Returns the style rules which match an entity, in order of precedence.
+pub(crate) fn inspect_matched_rules(
+    entity: Entity,
+    style: &Style,
+    tree: &Tree<Entity>,
+) -> Vec<MatchedRule> {
+    matched_rules(entity, style, tree).into_iter().map(|(_, matched_rule)| matched_rule).collect()
+}
+
+/// Returns the matched rules and computed style properties of an entity.
+pub(crate) fn inspect_style(entity: Entity, style: &Style, tree: &Tree<Entity>) -> ComputedStyle {
+    let rules = matched_rules(entity, style, tree);
+
+    // Declarations which use variables are stored in rules resolved from the rule which declares them, so shared
+    // data is reported as coming from the declaring rule.
+    let rule_origin = |index: usize| {
+        let index = style
+            .resolved_var_rule_info
+            .iter()
+            .find(|(resolved, _)| resolved.index() == index)
+            .map_or(index, |(_, resolved_rule)| resolved_rule.source.index());
+
+        rules
+            .iter()
+            .find(|(rule, _)| rule.index() == index)
+            .map(|(_, matched_rule)| matched_rule.clone())
+            // The value has not yet been relinked after a change to the matched rules, so the rule is looked up
+            // directly.
+            .or_else(|| {
+                style.rules.iter().find(|(rule, _)| rule.index() == index).map(|(_, style_rule)| {
+                    MatchedRule {
+                        selector: selector_to_css(&style_rule.selector),
+                        specificity: style_rule.selector.specificity(),
+                    }
+                })
+            })
+    };
+
+    let origin = |has_value: bool, origin: Option<DataOrigin>| match origin {
+        // Without a value the default applies, whatever the entity is linked to.
+        _ if !has_value => StyleOrigin::Default,
+        Some(DataOrigin::Inline) => StyleOrigin::Inline,
+        // Shared data which has outlived its rule is reported as the default rather than attributed to another rule.
+        Some(DataOrigin::Shared(index)) => {
+            rule_origin(index).map_or(StyleOrigin::Default, StyleOrigin::Rule)
+        }
+        Some(DataOrigin::Inherited) => StyleOrigin::Inherited,
+        Some(DataOrigin::Animation) => StyleOrigin::Animation,
+        None => StyleOrigin::Default,
+    };
+
+    macro_rules! properties {
+        ($($name:literal => $field:ident),* $(,)?) => {
+            vec![$(ComputedProperty {
+                name: $name,
+                value: style.$field.get(entity).map(|value| format!("{:?}", value)),
+                origin: origin(style.$field.get(entity).is_some(), style.$field.origin(entity)),
+            }),*]
+        };
+    }
+
+    let properties = properties! {
+        "display" => display,
+        "visibility" => visibility,
+        "overflow-x" => overflowx,
+        "overflow-y" => overflowy,
+        "clip-path" => clip_path,
+        "opacity" => opacity,
+        "z-index" => z_index,
+        "blend-mode" => blend_mode,
+        "layout-type" => layout_type,
+        "position-type" => position_type,
+        "alignment" => alignment,
+        "grid-columns" => grid_columns,
+        "grid-rows" => grid_rows,
+        "column-start" => column_start,
+        "column-span" => column_span,
+        "row-start" => row_start,
+        "row-span" => row_span,
+        "left" => left,
+        "right" => right,
+        "top" => top,
+        "bottom" => bottom,
+        "width" => width,
+        "height" => height,
+        "min-width" => min_width,
+        "max-width" => max_width,
+        "min-height" => min_height,
+        "max-height" => max_height,
+        "padding-left" => padding_left,
+        "padding-right" => padding_right,
+        "padding-top" => padding_top,
+        "padding-bottom" => padding_bottom,
+        "horizontal-gap" => horizontal_gap,
+        "vertical-gap" => vertical_gap,
+        "min-horizontal-gap" => min_horizontal_gap,
+        "max-horizontal-gap" => max_horizontal_gap,
+        "min-vertical-gap" => min_vertical_gap,
+        "max-vertical-gap" => max_vertical_gap,
+        "border-width" => border_width,
+        "border-color" => border_color,
+        "border-style" => border_style,
+        "corner-top-left-shape" => corner_top_left_shape,
+        "corner-top-right-shape" => corner_top_right_shape,
+        "corner-bottom-left-shape" => corner_bottom_left_shape,
+        "corner-bottom-right-shape" => corner_bottom_right_shape,
+        "corner-top-left-radius" => corner_top_left_radius,
+        "corner-top-right-radius" => corner_top_right_radius,
+        "corner-bottom-left-radius" => corner_bottom_left_radius,
+        "corner-bottom-right-radius" => corner_bottom_right_radius,
+        "outline-width" => outline_width,
+        "outline-color" => outline_color,
+        "outline-offset" => outline_offset,
+        "background-color" => background_color,
+        "background-image" => background_image,
+        "background-size" => background_size,
+        "fill" => fill,
+        "shadow" => shadow,
+        "text-shadow" => text_shadow,
+        "filter" => filter,
+        "backdrop-filter" => backdrop_filter,
+        "transform" => transform,
+        "transform-origin" => transform_origin,
+        "translate" => translate,
+        "rotate" => rotate,
+        "scale" => scale,
+        "color" => font_color,
+        "font-size" => font_size,
+        "font-family" => font_family,
+        "font-weight" => font_weight,
+        "font-slant" => font_slant,
+        "font-width" => font_width,
+        "font-variation-settings" => font_variation_settings,
+        "selection-color" => selection_color,
+        "caret-color" => caret_color,
+        "text-wrap" => text_wrap,
+        "text-align" => text_align,
+        "text-overflow" => text_overflow,
+        "line-clamp" => line_clamp,
+        "text-decoration-line" => text_decoration_line,
+        "text-stroke-width" => text_stroke_width,
+        "text-stroke-style" => text_stroke_style,
+        "line-height" => line_height,
+        "letter-spacing" => letter_spacing,
+        "word-spacing" => word_spacing,
+        "text-indent" => text_indent,
+        "underline-style" => underline_style,
+        "underline-color" => underline_color,
+        "overline-style" => overline_style,
+        "overline-color" => overline_color,
+        "strikethrough-style" => strikethrough_style,
+        "strikethrough-color" => strikethrough_color,
+        "cursor" => cursor,
+        "pointer-events" => pointer_events,
+    };
+
+    ComputedStyle { matched_rules: rules.into_iter().map(|(_, rule)| rule).collect(), properties }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computed_style_reports_matched_rules_and_origins() {
+        let cx = &mut Context::default();
+        cx.add_stylesheet(
+            r#"
+            .card {
+                width: 40px;
+                color: #0000ff;
+                background-color: #ff0000;
+                --accent: #ffff00;
+                border-color: var(--accent);
+            }
+
+            #card.card {
+                background-color: #00ff00;
+            }
+            "#,
+        )
+        .unwrap();
+
+        VStack::new(cx, |cx| {
+            Element::new(cx).id("child");
+        })
+        .class("card")
+        .id("card")
+        .height(Pixels(30.0));
+
+        crate::systems::style_system(cx);
+
+        let card = cx.query_first("#card").unwrap();
+        let child = cx.query_first("#child").unwrap();
+
+        let matched_rules = cx.matched_rules(card);
+        let selectors = matched_rules.iter().map(|rule| rule.selector.as_str()).collect::<Vec<_>>();
+        assert_eq!(selectors, ["#card.card", ".card"]);
+
+        let style = cx.computed_style(card);
+        assert_eq!(style.matched_rules, matched_rules);
+
+        let background_color = style.get("background-color").unwrap();
+        assert_eq!(background_color.value, Some(format!("{:?}", Color::rgb(0, 255, 0))));
+        assert!(matches!(
+            &background_color.origin,
+            StyleOrigin::Rule(rule) if rule.selector == "#card.card"
+        ));
+        assert!(matches!(
+            &style.get("width").unwrap().origin,
+            StyleOrigin::Rule(rule) if rule.selector == ".card"
+        ));
+        assert_eq!(style.get("height").unwrap().origin, StyleOrigin::Inline);
+
+        // Declarations which use variables are reported as coming from the rule which declares them.
+        let border_color = style.get("border-color").unwrap();
+        assert_eq!(border_color.value, Some(format!("{:?}", Color::rgb(255, 255, 0))));
+        assert!(matches!(
+            &border_color.origin,
+            StyleOrigin::Rule(rule) if rule.selector == ".card"
+        ));
+
+        assert_eq!(style.get("blend-mode").unwrap().origin, StyleOrigin::Default);
+        assert_eq!(style.get("blend-mode").unwrap().value, None);
+
+        let child_style = cx.computed_style(child);
+        assert_eq!(child_style.get("color").unwrap().origin, StyleOrigin::Inherited);
+        assert!(child_style.specified().any(|property| property.name == "color"));
+    }
+}
@@ -49,7 +49,8 @@
 //! ```
 //!
 //! To add an external css stylesheet which is read from a file at runtime, use [`add_stylesheet()`](crate::context::Context::add_stylesheet()) on [`Context`].
-//! Stylesheets added this way can be hot-reloaded by pressing the F5 key in the application window.
+//! Stylesheets added this way can be hot-reloaded by pressing the F5 key in the application window, or reloaded
+//! automatically whenever their files change by calling [`watch_stylesheets()`](crate::context::Context::watch_stylesheets()).
 //!
 //! ```
 //! # use vizia_core::prelude::*;
@@ -59,6 +60,39 @@
 //!
 //! Element::new(cx).class("foo");
 //! ```
+//!
+//! ### Media Queries
+//! Rules inside a `@media` block only apply while its query matches the window of a view. Queries can test the
+//! theme mode with `prefers-color-scheme`, the logical window size with `width` and `height`, and the scale
+//! factor with `resolution`, with `min-` and `max-` prefixes for the numeric features.
+//!
+//! ```css
+//! @media (prefers-color-scheme: dark) and (min-width: 600px) {
+//!     .foo {
+//!         background-color: black;
+//!     }
+//! }
+//! ```
+//!
+//! ### Pseudo-Elements
+//! A `::before` or `::after` rule with a `content` property adds an anonymous view as the first or last child of
+//! the matching views, displaying the content as text. A `::selection` rule sets the `background-color` and `color`
+//! of selected text in the views which support selecting text, such as [`Textbox`](crate::views::Textbox),
+//! [`RichTextEditor`](crate::views::RichTextEditor), and [`CodeEditor`](crate::views::CodeEditor). The text of a
+//! [`Label`](crate::views::Label) or [`TextSpan`](crate::views::TextSpan) can't be selected, so these views ignore
+//! `::selection` rules.
+//!
+//! ```css
+//! .required::after {
+//!     content: "*";
+//!     color: red;
+//! }
+//!
+//! textbox::selection {
+//!     background-color: #3366cc;
+//!     color: white;
+//! }
+//! ```
 
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexMap;
@@ -66,29 +100,49 @@ use log::warn;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut, Range};
 use vizia_style::selectors::parser::{AncestorHashes, Selector};
+use vizia_style::selectors::SelectorList;
 
 use crate::prelude::*;
+use crate::systems::matches_selectors;
 
 pub use vizia_style::{
-    Alignment, Angle, BackgroundImage, BackgroundSize, BorderStyleKeyword, ClipPath, Color,
-    CornerShape, CssRule, CursorIcon, Display, Filter, FontFamily, FontSize, FontSlant,
-    FontVariation, FontWeight, FontWeightKeyword, FontWidth, GenericFontFamily, Gradient,
-    HorizontalPosition, HorizontalPositionKeyword, Length, LengthOrPercentage, LengthValue,
-    LineClamp, LineDirection, LinearGradient, Matrix, Opacity, Overflow, PointerEvents, Position,
-    PositionType, Scale, Shadow, TextAlign, TextDecorationLine, TextDecorationStyle, TextOverflow,
-    TextStroke, TextStrokeStyle, Transform, Transition, Translate, VerticalPosition,
-    VerticalPositionKeyword, Visibility, RGBA,
+    Alignment, Angle, AnglePercentage, BackgroundImage, BackgroundSize, BorderStyleKeyword,
+    ClipPath, Color, ColorInterpolation, ColorScheme, ConicGradient, Content, CornerShape, CssRule,
+    CursorIcon, Direction, Display, Filter, FontFamily, FontSize, FontSlant, FontVariation,
+    FontWeight, FontWeightKeyword, FontWidth, GenericFontFamily, Gradient, HorizontalPosition,
+    HorizontalPositionKeyword, HueInterpolation, InterpolationColorSpace, Length,
+    LengthOrPercentage, LengthValue, LineClamp, LineDirection, LineHeight, LinearGradient, Matrix,
+    Opacity, Overflow, PointerEvents, Position, PositionType, RadialGradient, Scale, Shadow,
+    TextAlign, TextDecorationLine, TextDecorationStyle, TextOverflow, TextSpacing, TextStroke,
+    TextStrokeStyle, Transform, Transition, Translate, VerticalPosition, VerticalPositionKeyword,
+    Visibility, RGBA,
 };
 
 use vizia_style::{
-    BlendMode, EasingFunction, KeyframeSelector, ParserOptions, Property, Selectors, StyleSheet,
+    BlendMode, EasingFunction, KeyframeSelector, MediaQueryList, Property, PseudoElement,
+    Selectors, UnresolvedValue,
 };
 
 mod rule;
 pub(crate) use rule::Rule;
 
 mod pseudoclass;
-pub(crate) use pseudoclass::*;
+pub use pseudoclass::PseudoClassFlags;
+
+mod builder;
+pub(crate) use builder::property_setters;
+pub use builder::{SelectorBuilder, StyleRuleBuilder, StyleSheetBuilder};
+
+mod diagnostic;
+pub(crate) use diagnostic::parse_stylesheet;
+pub use diagnostic::{DiagnosticSeverity, StyleDiagnostic};
+
+mod inspect;
+pub(crate) use inspect::{inspect_matched_rules, inspect_style};
+pub use inspect::{ComputedProperty, ComputedStyle, MatchedRule, StyleOrigin};
+
+mod clip;
+pub(crate) use clip::*;
 
 mod transform;
 pub(crate) use transform::*;
@@ -207,6 +261,27 @@ impl StyleRule {
     }
 }
 
+/// A rule created by substituting custom properties into the declarations of a rule which use variables.
+pub(crate) struct ResolvedVarRule {
+    /// The rule which declares the substituted declarations.
+    pub(crate) source: Rule,
+    /// The substituted declarations, which identify the resolved rule.
+    key: String,
+    /// The names of declarations which are invalid at computed-value time, because they reference a missing
+    /// variable or are invalid after substitution.
+    pub(crate) invalid: Vec<String>,
+    /// The number of entities which use the resolved rule.
+    users: usize,
+}
+
+/// The media queries and `@keyframes` animations declared by a stylesheet, which are removed along with its rules.
+#[derive(Debug, Default)]
+pub(crate) struct StylesheetResources {
+    /// The indices of the media queries in `Style::media_queries`.
+    media_queries: Vec<usize>,
+    animations: Vec<Animation>,
+}
+
 /// Stores the style properties of all entities in the application.
 #[derive(Default)]
 pub struct Style {
@@ -215,11 +290,18 @@ pub struct Style {
     // Creates and destroys animation ids
     pub(crate) animation_manager: IdManager<Animation>,
     pub(crate) animations: HashMap<String, Animation>,
+    // Animations declared by `@keyframes` nested within a style rule, which apply to the views matching the rule.
+    pub(crate) scoped_animations: HashMap<String, Vec<(SelectorList<Selectors>, Animation)>>,
     // List of animations to be started on the next frame
     pub(crate) pending_animations: Vec<(Entity, Animation, Duration, Duration)>,
 
     // List of rules
     pub(crate) rules: IndexMap<Rule, StyleRule>,
+    // The number of rules parsed from each stylesheet, which are stored contiguously in the order of the
+    // stylesheets.
+    pub(crate) stylesheet_rules: Vec<usize>,
+    // The media queries and animations declared by each stylesheet, in the order of the stylesheets.
+    pub(crate) stylesheet_resources: Vec<StylesheetResources>,
 
     pub(crate) default_font: Vec<FamilyOwned>,
 
@@ -227,6 +309,12 @@ pub struct Style {
     pub(crate) element: SparseSet<u32>,
     pub(crate) ids: SparseSet<String>,
     pub(crate) classes: SparseSet<HashSet<String>>,
+    pub(crate) attributes: SparseSet<HashMap<String, String>>,
+    // The locale and per-view text direction used to match `:lang()` and `:dir()`.
+    pub(crate) locale: LanguageIdentifier,
+    pub(crate) direction: SparseSet<Direction>,
+    // The pseudo-element of the anonymous views which display the generated content of `::before` and `::after`.
+    pub(crate) pseudo_elements: SparseSet<PseudoElement>,
     pub(crate) pseudo_classes: SparseSet<PseudoClassFlags>,
     pub(crate) disabled: StyleSet<bool>,
     pub(crate) abilities: SparseSet<Abilities>,
@@ -257,6 +345,7 @@ pub struct Style {
     pub(crate) overflowy: StyleSet<Overflow>,
 
     // Filters
+    pub(crate) filter: AnimatableSet<Vec<Filter>>,
     pub(crate) backdrop_filter: AnimatableSet<Filter>,
 
     pub(crate) blend_mode: StyleSet<BlendMode>,
@@ -303,6 +392,7 @@ pub struct Style {
 
     // Shadow
     pub(crate) shadow: AnimatableSet<Vec<Shadow>>,
+    pub(crate) text_shadow: AnimatableSet<Vec<Shadow>>,
 
     // Text
     pub(crate) text: SparseSet<String>,
@@ -326,8 +416,13 @@ pub struct Style {
     pub(crate) font_slant: StyleSet<FontSlant>,
     pub(crate) font_width: StyleSet<FontWidth>,
     pub(crate) font_variation_settings: StyleSet<Vec<FontVariation>>,
+    pub(crate) line_height: AnimatableSet<LineHeight>,
+    pub(crate) letter_spacing: AnimatableSet<TextSpacing>,
+    pub(crate) word_spacing: AnimatableSet<TextSpacing>,
+    pub(crate) text_indent: AnimatableSet<LengthOrPercentage>,
     pub(crate) caret_color: AnimatableSet<Color>,
     pub(crate) selection_color: AnimatableSet<Color>,
+    pub(crate) selected_text_color: StyleSet<Color>,
 
     pub(crate) fill: AnimatableSet<Color>,
 
@@ -401,6 +496,36 @@ pub struct Style {
 
     pub(crate) text_range: SparseSet<Range<usize>>,
     pub(crate) text_span: SparseSet<bool>,
+    // The character which replaces each grapheme of the text when it is displayed, such as in a password field.
+    pub(crate) text_mask: SparseSet<char>,
+
+    // Custom Properties
+    pub(crate) custom_property_rules: HashMap<Rule, Vec<(String, UnresolvedValue)>>,
+    pub(crate) inline_custom_properties: SparseSet<HashMap<String, UnresolvedValue>>,
+    // Computed custom properties, including those inherited from ancestors.
+    pub(crate) custom_properties: SparseSet<HashMap<String, String>>,
+
+    // Declarations which reference custom properties, and the transitions of the rules containing them.
+    pub(crate) var_rules: HashMap<Rule, Vec<(String, UnresolvedValue)>>,
+    pub(crate) var_rule_transitions: HashMap<Rule, Vec<Transition>>,
+    // Rules created by substituting custom properties into the declarations of a rule, keyed by the rule and the
+    // substituted declarations.
+    pub(crate) resolved_var_rules: HashMap<(Rule, String), Rule>,
+    pub(crate) resolved_var_rule_info: HashMap<Rule, ResolvedVarRule>,
+    // Resolved rules which are no longer used by any entity, available for reuse by the rule they were created from.
+    pub(crate) free_var_rules: HashMap<Rule, Vec<Rule>>,
+    // The resolved rules used by each entity.
+    pub(crate) entity_var_rules: SparseSet<Vec<Rule>>,
+
+    // The generated content of `::before` and `::after` rules.
+    pub(crate) content: HashMap<Rule, Content>,
+
+    // Media Queries
+    pub(crate) media_queries: Vec<MediaQueryList>,
+    // The indices of the media queries which must all match for a rule to apply.
+    pub(crate) rule_media: HashMap<Rule, Vec<usize>>,
+    // The result of each media query for each window.
+    pub(crate) media_matches: HashMap<Entity, Vec<bool>>,
 
     /// This includes both the system's HiDPI scaling factor as well as `cx.user_scale_factor`.
     pub(crate) dpi_factor: f64,
@@ -422,15 +547,64 @@ impl Style {
         physical / self.dpi_factor as f32
     }
 
+    /// Returns the text direction of an entity, which is inherited from its ancestors and otherwise
+    /// follows the direction of the script used by the current locale.
+    pub(crate) fn text_direction(&self, tree: &Tree<Entity>, entity: Entity) -> Direction {
+        let mut next = Some(entity);
+        while let Some(current) = next {
+            if let Some(direction) = self.direction.get(current) {
+                return *direction;
+            }
+
+            next = tree.get_parent(current);
+        }
+
+        locale_direction(&self.locale)
+    }
+
     pub(crate) fn remove_rules(&mut self) {
         self.rule_manager.reset();
         self.rules.clear();
+        self.stylesheet_rules.clear();
+        self.stylesheet_resources.clear();
+        self.custom_property_rules.clear();
+        self.var_rules.clear();
+        self.var_rule_transitions.clear();
+        self.resolved_var_rules.clear();
+        self.resolved_var_rule_info.clear();
+        self.free_var_rules.clear();
+        self.entity_var_rules = SparseSet::default();
+        self.content.clear();
+        self.scoped_animations.clear();
+        self.media_queries.clear();
+        self.rule_media.clear();
+        self.media_matches.clear();
     }
 
     pub(crate) fn get_animation(&self, name: &str) -> Option<&Animation> {
         self.animations.get(name)
     }
 
+    /// Returns the animation with the given name for an entity, preferring `@keyframes` nested within the last
+    /// style rule which matches the entity over top-level `@keyframes`.
+    pub(crate) fn get_animation_for(
+        &self,
+        tree: &Tree<Entity>,
+        entity: Entity,
+        name: &str,
+    ) -> Option<Animation> {
+        self.scoped_animations
+            .get(name)
+            .and_then(|scoped| {
+                scoped
+                    .iter()
+                    .rev()
+                    .find(|(selectors, _)| matches_selectors(self, tree, entity, selectors))
+            })
+            .map(|(_, animation)| *animation)
+            .or_else(|| self.animations.get(name).copied())
+    }
+
     pub(crate) fn add_keyframe(
         &mut self,
         animation_id: Animation,
@@ -590,6 +764,15 @@ impl Style {
                     insert_keyframe(&mut self.shadow, animation_id, time, value.clone());
                 }
 
+                Property::TextShadow(value) => {
+                    insert_keyframe(&mut self.text_shadow, animation_id, time, value.clone());
+                }
+
+                // FILTER
+                Property::Filter(value) => {
+                    insert_keyframe(&mut self.filter, animation_id, time, value.clone());
+                }
+
                 // TEXT
                 Property::FontColor(value) => {
                     insert_keyframe(&mut self.font_color, animation_id, time, *value);
@@ -599,6 +782,22 @@ impl Style {
                     insert_keyframe(&mut self.font_size, animation_id, time, *value);
                 }
 
+                Property::LineHeight(value) => {
+                    insert_keyframe(&mut self.line_height, animation_id, time, value.clone());
+                }
+
+                Property::LetterSpacing(value) => {
+                    insert_keyframe(&mut self.letter_spacing, animation_id, time, value.clone());
+                }
+
+                Property::WordSpacing(value) => {
+                    insert_keyframe(&mut self.word_spacing, animation_id, time, value.clone());
+                }
+
+                Property::TextIndent(value) => {
+                    insert_keyframe(&mut self.text_indent, animation_id, time, value.clone());
+                }
+
                 Property::CaretColor(value) => {
                     insert_keyframe(&mut self.caret_color, animation_id, time, *value);
                 }
@@ -740,7 +939,7 @@ impl Style {
     }
 
     pub(crate) fn play_pending_animations(&mut self) {
-        let start_time = Instant::now();
+        let start_time = crate::context::now();
 
         let pending_animations = self.pending_animations.drain(..).collect::<Vec<_>>();
 
@@ -786,9 +985,16 @@ impl Style {
         self.background_size.play_animation(entity, animation, start_time, duration, delay);
 
         self.shadow.play_animation(entity, animation, start_time, duration, delay);
+        self.text_shadow.play_animation(entity, animation, start_time, duration, delay);
+
+        self.filter.play_animation(entity, animation, start_time, duration, delay);
 
         self.font_color.play_animation(entity, animation, start_time, duration, delay);
         self.font_size.play_animation(entity, animation, start_time, duration, delay);
+        self.line_height.play_animation(entity, animation, start_time, duration, delay);
+        self.letter_spacing.play_animation(entity, animation, start_time, duration, delay);
+        self.word_spacing.play_animation(entity, animation, start_time, duration, delay);
+        self.text_indent.play_animation(entity, animation, start_time, duration, delay);
         self.caret_color.play_animation(entity, animation, start_time, duration, delay);
         self.selection_color.play_animation(entity, animation, start_time, duration, delay);
 
@@ -844,8 +1050,14 @@ impl Style {
             | self.background_image.has_active_animation(entity, animation)
             | self.background_size.has_active_animation(entity, animation)
             | self.shadow.has_active_animation(entity, animation)
+            | self.text_shadow.has_active_animation(entity, animation)
+            | self.filter.has_active_animation(entity, animation)
             | self.font_color.has_active_animation(entity, animation)
             | self.font_size.has_active_animation(entity, animation)
+            | self.line_height.has_active_animation(entity, animation)
+            | self.letter_spacing.has_active_animation(entity, animation)
+            | self.word_spacing.has_active_animation(entity, animation)
+            | self.text_indent.has_active_animation(entity, animation)
             | self.caret_color.has_active_animation(entity, animation)
             | self.selection_color.has_active_animation(entity, animation)
             | self.left.has_active_animation(entity, animation)
@@ -872,67 +1084,381 @@ impl Style {
             | self.fill.has_active_animation(entity, animation)
     }
 
-    pub(crate) fn parse_theme(&mut self, stylesheet: &str) {
-        if let Ok(stylesheet) = StyleSheet::parse(stylesheet, ParserOptions::new()) {
-            let rules = stylesheet.rules.0;
-
-            for rule in rules {
-                match rule {
-                    CssRule::Style(style_rule) => {
-                        // let selectors = style_rule.selectors;
-
-                        for selector in style_rule.selectors.slice() {
-                            let rule_id = self.rule_manager.create();
-
-                            for property in style_rule.declarations.declarations.iter() {
-                                match property {
-                                    Property::Transition(transitions) => {
-                                        for transition in transitions.iter() {
-                                            self.insert_transition(rule_id, transition);
-                                        }
-                                    }
-
-                                    _ => {
-                                        self.insert_property(rule_id, property);
-                                    }
-                                }
-                            }
+    // Removes the keyframes of an animation from every property.
+    fn remove_animation(&mut self, animation: Animation) {
+        self.opacity.remove_animation(animation);
+        self.clip_path.remove_animation(animation);
+        self.filter.remove_animation(animation);
+        self.backdrop_filter.remove_animation(animation);
+        self.transform.remove_animation(animation);
+        self.transform_origin.remove_animation(animation);
+        self.translate.remove_animation(animation);
+        self.rotate.remove_animation(animation);
+        self.scale.remove_animation(animation);
+        self.border_width.remove_animation(animation);
+        self.border_color.remove_animation(animation);
+        self.corner_top_left_radius.remove_animation(animation);
+        self.corner_top_right_radius.remove_animation(animation);
+        self.corner_bottom_left_radius.remove_animation(animation);
+        self.corner_bottom_right_radius.remove_animation(animation);
+        self.corner_top_left_smoothing.remove_animation(animation);
+        self.corner_top_right_smoothing.remove_animation(animation);
+        self.corner_bottom_left_smoothing.remove_animation(animation);
+        self.corner_bottom_right_smoothing.remove_animation(animation);
+        self.outline_width.remove_animation(animation);
+        self.outline_color.remove_animation(animation);
+        self.outline_offset.remove_animation(animation);
+        self.background_color.remove_animation(animation);
+        self.background_image.remove_animation(animation);
+        self.background_size.remove_animation(animation);
+        self.shadow.remove_animation(animation);
+        self.text_shadow.remove_animation(animation);
+        self.underline_color.remove_animation(animation);
+        self.overline_color.remove_animation(animation);
+        self.strikethrough_color.remove_animation(animation);
+        self.font_color.remove_animation(animation);
+        self.font_size.remove_animation(animation);
+        self.line_height.remove_animation(animation);
+        self.letter_spacing.remove_animation(animation);
+        self.word_spacing.remove_animation(animation);
+        self.text_indent.remove_animation(animation);
+        self.caret_color.remove_animation(animation);
+        self.selection_color.remove_animation(animation);
+        self.fill.remove_animation(animation);
+        self.display.remove_animation(animation);
+        self.left.remove_animation(animation);
+        self.right.remove_animation(animation);
+        self.top.remove_animation(animation);
+        self.bottom.remove_animation(animation);
+        self.padding_left.remove_animation(animation);
+        self.padding_right.remove_animation(animation);
+        self.padding_top.remove_animation(animation);
+        self.padding_bottom.remove_animation(animation);
+        self.vertical_gap.remove_animation(animation);
+        self.horizontal_gap.remove_animation(animation);
+        self.vertical_scroll.remove_animation(animation);
+        self.horizontal_scroll.remove_animation(animation);
+        self.width.remove_animation(animation);
+        self.height.remove_animation(animation);
+        self.min_width.remove_animation(animation);
+        self.max_width.remove_animation(animation);
+        self.min_height.remove_animation(animation);
+        self.max_height.remove_animation(animation);
+        self.min_horizontal_gap.remove_animation(animation);
+        self.max_horizontal_gap.remove_animation(animation);
+        self.min_vertical_gap.remove_animation(animation);
+        self.max_vertical_gap.remove_animation(animation);
+    }
+
+    /// Parses a stylesheet and adds its rules after those of the other stylesheets, returning the diagnostics for
+    /// any problems found.
+    pub(crate) fn parse_theme(&mut self, filename: &str, stylesheet: &str) -> Vec<StyleDiagnostic> {
+        let (stylesheet, diagnostics) = parse_stylesheet(filename, stylesheet);
+        self.insert_stylesheet(
+            self.stylesheet_rules.len(),
+            stylesheet.map(|stylesheet| stylesheet.rules.0).unwrap_or_default(),
+        );
+
+        diagnostics
+    }
+
+    /// Inserts the rules of a parsed stylesheet at the given position, in the order of the stylesheets, so that
+    /// they take precedence over the rules of earlier stylesheets.
+    pub(crate) fn insert_stylesheet(&mut self, position: usize, rules: Vec<CssRule>) {
+        let start = self.stylesheet_rules[..position].iter().sum::<usize>();
+
+        // The new rules are appended, and then moved into place.
+        let first = self.rules.len();
+        let first_media_query = self.media_queries.len();
+        let mut animations = Vec::new();
+        self.insert_rules(rules, &mut Vec::new(), None, &mut animations);
+        let count = self.rules.len() - first;
+        for offset in 0..count {
+            self.rules.move_index(first + offset, start + offset);
+        }
+
+        self.stylesheet_rules.insert(position, count);
+        self.stylesheet_resources.insert(
+            position,
+            StylesheetResources {
+                media_queries: (first_media_query..self.media_queries.len()).collect(),
+                animations,
+            },
+        );
+    }
+
+    /// Replaces the rules of the stylesheet at the given position, in the order of the stylesheets, with the rules
+    /// of its new content, keeping their precedence relative to the rules of other stylesheets.
+    ///
+    /// Returns false if there is no stylesheet at the position.
+    pub(crate) fn replace_stylesheet(&mut self, position: usize, rules: Vec<CssRule>) -> bool {
+        if !self.remove_stylesheet(position) {
+            return false;
+        }
+
+        self.insert_stylesheet(position, rules);
+
+        true
+    }
+
+    /// Removes the rules of the stylesheet at the given position, in the order of the stylesheets.
+    ///
+    /// Returns false if there is no stylesheet at the position.
+    pub(crate) fn remove_stylesheet(&mut self, position: usize) -> bool {
+        let Some(count) = self.stylesheet_rules.get(position).copied() else {
+            return false;
+        };
+
+        let start = self.stylesheet_rules[..position].iter().sum::<usize>();
+        let removed =
+            self.rules.drain(start..start + count).map(|(rule, _)| rule).collect::<Vec<_>>();
+        self.stylesheet_rules.remove(position);
+        let resources = self.stylesheet_resources.remove(position);
+        self.remove_rule_data(&removed, resources);
+
+        true
+    }
+
+    // Removes the data of the given rules and the media queries and animations of their stylesheet, along with every
+    // rule resolved from declarations which use variables, which are resolved again when entities are restyled.
+    fn remove_rule_data(&mut self, rules: &[Rule], resources: StylesheetResources) {
+        let resolved_rules = self
+            .resolved_var_rule_info
+            .keys()
+            .chain(self.free_var_rules.values().flatten())
+            .copied()
+            .collect::<Vec<_>>();
+
+        self.resolved_var_rules.clear();
+        self.resolved_var_rule_info.clear();
+        self.free_var_rules.clear();
+        self.entity_var_rules = SparseSet::default();
+
+        let rules = rules.iter().copied().chain(resolved_rules).collect::<Vec<_>>();
+
+        self.remove_style_rules(&rules);
+
+        for rule in rules {
+            self.custom_property_rules.remove(&rule);
+            self.var_rules.remove(&rule);
+            self.var_rule_transitions.remove(&rule);
+            self.content.remove(&rule);
+            self.rule_media.remove(&rule);
+            self.rule_manager.destroy(rule);
+        }
+
+        self.remove_media_queries(&resources.media_queries);
+
+        for animation in resources.animations {
+            self.animations.retain(|_, other| *other != animation);
+            for scoped in self.scoped_animations.values_mut() {
+                scoped.retain(|(_, other)| *other != animation);
+            }
+            self.remove_animation(animation);
+            self.animation_manager.destroy(animation);
+        }
+        self.scoped_animations.retain(|_, scoped| !scoped.is_empty());
+    }
+
+    // Removes the media queries with the given indices, moving the indices of the following media queries down.
+    fn remove_media_queries(&mut self, removed: &[usize]) {
+        if removed.is_empty() {
+            return;
+        }
+
+        let mut next = 0;
+        let remap = (0..self.media_queries.len())
+            .map(|index| {
+                (!removed.contains(&index)).then(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect::<Vec<_>>();
+        let keep = |index: usize| remap.get(index).copied().flatten();
+
+        let mut index = 0;
+        self.media_queries.retain(|_| {
+            index += 1;
+            keep(index - 1).is_some()
+        });
+
+        for media in self.rule_media.values_mut() {
+            *media = media.iter().filter_map(|index| keep(*index)).collect();
+        }
+
+        for resources in self.stylesheet_resources.iter_mut() {
+            resources.media_queries =
+                resources.media_queries.iter().filter_map(|index| keep(*index)).collect();
+        }
+
+        for matches in self.media_matches.values_mut() {
+            *matches = matches
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| keep(*index).is_some())
+                .map(|(_, matches)| *matches)
+                .collect();
+        }
+    }
+
+    // Inserts a list of parsed rules, where `media` holds the indices of the media queries enclosing the rules
+    // and `parent` holds the selectors of the enclosing style rule, if the rules are nested.
+    //
+    // Nested style rules are flattened by replacing the `&` parent selector with the selectors of the enclosing
+    // rule, so they are stored and matched in the same way as top-level rules.
+    fn insert_rules(
+        &mut self,
+        rules: Vec<CssRule>,
+        media: &mut Vec<usize>,
+        parent: Option<&SelectorList<Selectors>>,
+        animations: &mut Vec<Animation>,
+    ) {
+        for rule in rules {
+            match rule {
+                CssRule::Style(style_rule) => {
+                    let selectors = match parent {
+                        Some(parent) => SelectorList::from_iter(
+                            style_rule
+                                .selectors
+                                .slice()
+                                .iter()
+                                .map(|selector| selector.replace_parent_selector(parent))
+                                .collect::<Vec<_>>()
+                                .into_iter(),
+                        ),
+                        None => style_rule.selectors,
+                    };
+
+                    for selector in selectors.slice() {
+                        self.insert_style_rule(
+                            selector,
+                            &style_rule.declarations.declarations,
+                            media,
+                        );
+                    }
 
-                            self.rules.insert(rule_id, StyleRule::new(selector.clone()));
+                    self.insert_rules(style_rule.rules.0, media, Some(&selectors), animations);
+                }
+
+                CssRule::Keyframes(keyframes_rule) => {
+                    let name = keyframes_rule.name.as_string();
+
+                    let animation_id = self.animation_manager.create();
+                    animations.push(animation_id);
+
+                    for keyframes in keyframes_rule.keyframes {
+                        for selector in keyframes.selectors.iter() {
+                            let time = match selector {
+                                KeyframeSelector::From => 0.0,
+                                KeyframeSelector::To => 1.0,
+                                KeyframeSelector::Percentage(percentage) => percentage.0 / 100.0,
+                            };
+
+                            self.add_keyframe(
+                                animation_id,
+                                time,
+                                &keyframes.declarations.declarations,
+                            );
                         }
                     }
 
-                    CssRule::Keyframes(keyframes_rule) => {
-                        let name = keyframes_rule.name.as_string();
-
-                        let animation_id = self.animation_manager.create();
-
-                        for keyframes in keyframes_rule.keyframes {
-                            for selector in keyframes.selectors.iter() {
-                                let time = match selector {
-                                    KeyframeSelector::From => 0.0,
-                                    KeyframeSelector::To => 1.0,
-                                    KeyframeSelector::Percentage(percentage) => {
-                                        percentage.0 / 100.0
-                                    }
-                                };
-
-                                self.add_keyframe(
-                                    animation_id,
-                                    time,
-                                    &keyframes.declarations.declarations,
-                                );
-                            }
+                    match parent {
+                        // Keyframes nested within a style rule only apply to views matching the rule.
+                        Some(parent) => self
+                            .scoped_animations
+                            .entry(name)
+                            .or_default()
+                            .push((parent.clone(), animation_id)),
+
+                        None => {
+                            self.animations.insert(name, animation_id);
                         }
+                    }
+                }
 
-                        self.animations.insert(name, animation_id);
+                CssRule::Media(media_rule) => {
+                    media.push(self.media_queries.len());
+                    self.media_queries.push(media_rule.query);
+                    self.insert_rules(media_rule.rules.0, media, parent, animations);
+                    media.pop();
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    // Inserts a rule constructed with a `StyleRuleBuilder`.
+    pub(crate) fn insert_rule_builder(&mut self, rule: &StyleRuleBuilder) {
+        match parse_selectors(&rule.selector.to_css()) {
+            Ok(selectors) => {
+                for selector in selectors.slice() {
+                    self.insert_style_rule(selector, &rule.properties, &[]);
+                }
+            }
+
+            Err(err) => warn!("Failed to build selector: {:?}", err),
+        }
+    }
+
+    // Inserts a rule with a single selector and its declarations, where `media` holds the indices of the media
+    // queries enclosing the rule.
+    pub(crate) fn insert_style_rule(
+        &mut self,
+        selector: &Selector<Selectors>,
+        declarations: &[Property],
+        media: &[usize],
+    ) {
+        let rule_id = self.rule_manager.create();
+
+        let mut rule_transitions = Vec::new();
+
+        let is_selection = selector.pseudo_element() == Some(&PseudoElement::Selection);
+
+        for property in declarations.iter() {
+            match property {
+                _ if is_selection => {
+                    self.insert_selection_property(rule_id, property);
+                }
+
+                Property::Transition(transitions) => {
+                    for transition in transitions.iter() {
+                        self.insert_transition(rule_id, transition);
                     }
+                    rule_transitions.extend(transitions.iter().cloned());
+                }
 
-                    _ => {}
+                _ => {
+                    self.insert_property(rule_id, property);
                 }
             }
-        } else {
-            println!("Failed to parse stylesheet");
+        }
+
+        // Rules resolved from declarations which use variables need the same transitions.
+        if self.var_rules.contains_key(&rule_id) {
+            self.var_rule_transitions.insert(rule_id, rule_transitions);
+        }
+
+        if !media.is_empty() {
+            self.rule_media.insert(rule_id, media.to_vec());
+        }
+
+        self.rules.insert(rule_id, StyleRule::new(selector.clone()));
+    }
+
+    // The `::selection` pseudo-element only supports colors, which apply to the selected text of a view.
+    fn insert_selection_property(&mut self, rule_id: Rule, property: &Property) {
+        match property {
+            Property::BackgroundColor(color) | Property::SelectionColor(color) => {
+                self.selection_color.insert_rule(rule_id, *color);
+            }
+
+            Property::FontColor(color) => {
+                self.selected_text_color.insert_rule(rule_id, *color);
+            }
+
+            _ => {}
         }
     }
 
@@ -1077,6 +1603,16 @@ impl Style {
                 self.shadow.insert_transition(rule_id, animation);
             }
 
+            "text-shadow" => {
+                self.text_shadow.insert_animation(animation, self.add_transition(transition));
+                self.text_shadow.insert_transition(rule_id, animation);
+            }
+
+            "filter" => {
+                self.filter.insert_animation(animation, self.add_transition(transition));
+                self.filter.insert_transition(rule_id, animation);
+            }
+
             "color" => {
                 self.font_color.insert_animation(animation, self.add_transition(transition));
                 self.font_color.insert_transition(rule_id, animation);
@@ -1087,6 +1623,26 @@ impl Style {
                 self.font_size.insert_transition(rule_id, animation);
             }
 
+            "line-height" => {
+                self.line_height.insert_animation(animation, self.add_transition(transition));
+                self.line_height.insert_transition(rule_id, animation);
+            }
+
+            "letter-spacing" => {
+                self.letter_spacing.insert_animation(animation, self.add_transition(transition));
+                self.letter_spacing.insert_transition(rule_id, animation);
+            }
+
+            "word-spacing" => {
+                self.word_spacing.insert_animation(animation, self.add_transition(transition));
+                self.word_spacing.insert_transition(rule_id, animation);
+            }
+
+            "text-indent" => {
+                self.text_indent.insert_animation(animation, self.add_transition(transition));
+                self.text_indent.insert_transition(rule_id, animation);
+            }
+
             "caret-color" => {
                 self.caret_color.insert_animation(animation, self.add_transition(transition));
                 self.caret_color.insert_transition(rule_id, animation);
@@ -1243,6 +1799,10 @@ impl Style {
             }
 
             // Filters
+            Property::Filter(filters) => {
+                self.filter.insert_rule(rule_id, filters);
+            }
+
             Property::BackdropFilter(filter) => {
                 self.backdrop_filter.insert_rule(rule_id, filter);
             }
@@ -1545,6 +2105,23 @@ impl Style {
                 self.font_variation_settings.insert_rule(rule_id, font_variation_settings);
             }
 
+            // Typography
+            Property::LineHeight(line_height) => {
+                self.line_height.insert_rule(rule_id, line_height);
+            }
+
+            Property::LetterSpacing(letter_spacing) => {
+                self.letter_spacing.insert_rule(rule_id, letter_spacing);
+            }
+
+            Property::WordSpacing(word_spacing) => {
+                self.word_spacing.insert_rule(rule_id, word_spacing);
+            }
+
+            Property::TextIndent(text_indent) => {
+                self.text_indent.insert_rule(rule_id, text_indent);
+            }
+
             // Caret Color
             Property::CaretColor(caret_color) => {
                 self.caret_color.insert_rule(rule_id, caret_color);
@@ -1656,6 +2233,11 @@ impl Style {
                 self.shadow.insert_rule(rule_id, shadows);
             }
 
+            // Text Shadows
+            Property::TextShadow(shadows) => {
+                self.text_shadow.insert_rule(rule_id, shadows);
+            }
+
             // Cursor Icon
             Property::Cursor(cursor) => {
                 self.cursor.insert_rule(rule_id, cursor);
@@ -1665,14 +2247,27 @@ impl Style {
                 self.pointer_events.insert_rule(rule_id, pointer_events);
             }
 
-            // Unparsed. TODO: Log the error.
+            // Declarations which use variables are resolved for each entity when styles are linked.
             Property::Unparsed(unparsed) => {
-                warn!("Unparsed: {}", unparsed.name);
+                if unparsed.value.has_var() {
+                    self.var_rules
+                        .entry(rule_id)
+                        .or_default()
+                        .push((unparsed.name.to_string(), UnresolvedValue::from(&unparsed.value)));
+                } else {
+                    warn!("Unparsed: {}", unparsed.name);
+                }
             }
 
-            // TODO: Custom property support
             Property::Custom(custom) => {
-                warn!("Custom Property: {}", custom.name);
+                if custom.name.starts_with("--") {
+                    self.custom_property_rules
+                        .entry(rule_id)
+                        .or_default()
+                        .push((custom.name.to_string(), UnresolvedValue::from(&custom.value)));
+                } else {
+                    warn!("Custom Property: {}", custom.name);
+                }
             }
             Property::TextOverflow(text_overflow) => {
                 self.text_overflow.insert_rule(rule_id, text_overflow);
@@ -1696,10 +2291,248 @@ impl Style {
             Property::Fill(fill) => {
                 self.fill.insert_rule(rule_id, fill);
             }
+
+            // Generated Content
+            Property::Content(content) => {
+                self.content.insert(rule_id, content);
+            }
             _ => {}
         }
     }
 
+    /// Returns a rule containing the declarations of the given rule which use variables, with the variables
+    /// substituted from the given custom properties.
+    ///
+    /// Resolved rules are shared between entities for which the substituted declarations are identical, and must be
+    /// released with [`release_var_rule`](Self::release_var_rule) by each entity which acquires them.
+    pub(crate) fn acquire_var_rule(
+        &mut self,
+        rule: Rule,
+        custom_properties: Option<&HashMap<String, String>>,
+    ) -> Option<Rule> {
+        let declarations = self.var_rules.get(&rule)?;
+
+        let mut key = String::new();
+        let resolved = declarations
+            .iter()
+            .map(|(name, value)| {
+                let value = value.resolve(&mut |var| {
+                    custom_properties.and_then(|properties| properties.get(var)).cloned()
+                });
+
+                key += name;
+                key.push(':');
+                match &value {
+                    Some(value) => key += value,
+                    // A reference to a missing variable, which can't be part of a substituted value.
+                    None => key.push('\0'),
+                }
+                key.push(';');
+
+                (name.clone(), value)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(resolved_rule) = self.resolved_var_rules.get(&(rule, key.clone())).copied() {
+            if let Some(info) = self.resolved_var_rule_info.get_mut(&resolved_rule) {
+                info.users += 1;
+            }
+
+            return Some(resolved_rule);
+        }
+
+        // A rule which is no longer used is reused, with its data overwritten by the new values, so that the number of
+        // rules doesn't grow as the values of custom properties change.
+        let (resolved_rule, reused) =
+            match self.free_var_rules.get_mut(&rule).and_then(|free_rules| free_rules.pop()) {
+                Some(free_rule) => (free_rule, true),
+                None => (self.rule_manager.create(), false),
+            };
+
+        let mut invalid = Vec::new();
+        for (name, value) in resolved.into_iter() {
+            let property = value.as_deref().map(|value| Property::parse_string(&name, value));
+            match property {
+                Some(Ok(Property::Unparsed(_)) | Err(_)) => {
+                    warn!(
+                        "Failed to parse `{}: {}` after substituting variables",
+                        name,
+                        value.unwrap_or_default()
+                    );
+                    invalid.push(name);
+                }
+
+                Some(Ok(property)) => {
+                    // A later declaration of the same property takes precedence over an invalid one.
+                    invalid.retain(|invalid_name| *invalid_name != name);
+                    self.insert_property(resolved_rule, &property);
+                }
+
+                None => invalid.push(name),
+            }
+        }
+
+        // The transitions of a reused rule are already attached to its data.
+        if !reused {
+            if let Some(transitions) = self.var_rule_transitions.get(&rule).cloned() {
+                for transition in transitions.iter() {
+                    self.insert_transition(resolved_rule, transition);
+                }
+            }
+        }
+
+        self.resolved_var_rules.insert((rule, key.clone()), resolved_rule);
+        self.resolved_var_rule_info
+            .insert(resolved_rule, ResolvedVarRule { source: rule, key, invalid, users: 1 });
+
+        Some(resolved_rule)
+    }
+
+    /// Releases a rule acquired with [`acquire_var_rule`](Self::acquire_var_rule), freeing it for reuse once no
+    /// entity uses it.
+    pub(crate) fn release_var_rule(&mut self, resolved_rule: Rule) {
+        let Some(info) = self.resolved_var_rule_info.get_mut(&resolved_rule) else {
+            return;
+        };
+
+        info.users -= 1;
+        if info.users == 0 {
+            if let Some(info) = self.resolved_var_rule_info.remove(&resolved_rule) {
+                self.resolved_var_rules.remove(&(info.source, info.key));
+                self.free_var_rules.entry(info.source).or_default().push(resolved_rule);
+            }
+        }
+    }
+
+    /// Links the data of the property with the given name to the first of the given rules which declares it,
+    /// returning whether any data changed.
+    pub(crate) fn link_property(
+        &mut self,
+        entity: Entity,
+        name: &str,
+        rules: &[(Rule, u32)],
+    ) -> bool {
+        macro_rules! link {
+            ($($field:ident),+) => {
+                false $(| self.$field.link(entity, rules))+
+            };
+        }
+
+        match name {
+            "display" => link!(display),
+            "visibility" => link!(visibility),
+            "overflow" => link!(overflowx, overflowy),
+            "overflow-x" => link!(overflowx),
+            "overflow-y" => link!(overflowy),
+            "clip-path" => link!(clip_path),
+            "opacity" => link!(opacity),
+            "z-index" => link!(z_index),
+            "blend-mode" => link!(blend_mode),
+            "filter" => link!(filter),
+            "backdrop-filter" => link!(backdrop_filter),
+            "layout-type" => link!(layout_type),
+            "position-type" => link!(position_type),
+            "alignment" => link!(alignment),
+            "grid-columns" => link!(grid_columns),
+            "grid-rows" => link!(grid_rows),
+            "column-start" => link!(column_start),
+            "column-span" => link!(column_span),
+            "row-start" => link!(row_start),
+            "row-span" => link!(row_span),
+            "space" => link!(left, right, top, bottom),
+            "left" => link!(left),
+            "right" => link!(right),
+            "top" => link!(top),
+            "bottom" => link!(bottom),
+            "size" => link!(width, height),
+            "width" => link!(width),
+            "height" => link!(height),
+            "padding" => link!(padding_left, padding_right, padding_top, padding_bottom),
+            "padding-left" => link!(padding_left),
+            "padding-right" => link!(padding_right),
+            "padding-top" => link!(padding_top),
+            "padding-bottom" => link!(padding_bottom),
+            "gap" => link!(horizontal_gap, vertical_gap),
+            "horizontal-gap" => link!(horizontal_gap),
+            "vertical-gap" => link!(vertical_gap),
+            "min-size" => link!(min_width, min_height),
+            "min-width" => link!(min_width),
+            "min-height" => link!(min_height),
+            "max-size" => link!(max_width, max_height),
+            "max-width" => link!(max_width),
+            "max-height" => link!(max_height),
+            "min-gap" => link!(min_horizontal_gap, min_vertical_gap),
+            "min-horizontal-gap" => link!(min_horizontal_gap),
+            "min-vertical-gap" => link!(min_vertical_gap),
+            "max-gap" => link!(max_horizontal_gap, max_vertical_gap),
+            "max-horizontal-gap" => link!(max_horizontal_gap),
+            "max-vertical-gap" => link!(max_vertical_gap),
+            "background-color" => link!(background_color),
+            "background-image" => link!(background_image),
+            "background-size" => link!(background_size),
+            "border" => link!(border_color, border_width, border_style),
+            "border-color" => link!(border_color),
+            "border-width" => link!(border_width),
+            "border-style" => link!(border_style),
+            "corner-radius" => link!(
+                corner_top_left_radius,
+                corner_top_right_radius,
+                corner_bottom_left_radius,
+                corner_bottom_right_radius
+            ),
+            "corner-top-left-radius" => link!(corner_top_left_radius),
+            "corner-top-right-radius" => link!(corner_top_right_radius),
+            "corner-bottom-left-radius" => link!(corner_bottom_left_radius),
+            "corner-bottom-right-radius" => link!(corner_bottom_right_radius),
+            "corner-shape" => link!(
+                corner_top_left_shape,
+                corner_top_right_shape,
+                corner_bottom_left_shape,
+                corner_bottom_right_shape
+            ),
+            "corner-top-left-shape" => link!(corner_top_left_shape),
+            "corner-top-right-shape" => link!(corner_top_right_shape),
+            "corner-bottom-left-shape" => link!(corner_bottom_left_shape),
+            "corner-bottom-right-shape" => link!(corner_bottom_right_shape),
+            "outline" => link!(outline_color, outline_width),
+            "outline-color" => link!(outline_color),
+            "outline-width" => link!(outline_width),
+            "outline-offset" => link!(outline_offset),
+            "fill" => link!(fill),
+            "color" => link!(font_color),
+            "font-size" => link!(font_size),
+            "font-family" => link!(font_family),
+            "font-weight" => link!(font_weight),
+            "font-slant" => link!(font_slant),
+            "font-width" => link!(font_width),
+            "font-variation-settings" => link!(font_variation_settings),
+            "line-height" => link!(line_height),
+            "letter-spacing" => link!(letter_spacing),
+            "word-spacing" => link!(word_spacing),
+            "text-indent" => link!(text_indent),
+            "caret-color" => link!(caret_color),
+            "selection-color" => link!(selection_color),
+            "text-wrap" => link!(text_wrap),
+            "text-align" => link!(text_align),
+            "text-overflow" => link!(text_overflow),
+            "line-clamp" => link!(line_clamp),
+            "text-decoration-line" => link!(text_decoration_line),
+            "text-stroke" => link!(text_stroke_width, text_stroke_style),
+            "text-stroke-width" => link!(text_stroke_width),
+            "text-stroke-style" => link!(text_stroke_style),
+            "shadow" => link!(shadow),
+            "text-shadow" => link!(text_shadow),
+            "transform" => link!(transform),
+            "transform-origin" => link!(transform_origin),
+            "translate" => link!(translate),
+            "rotate" => link!(rotate),
+            "scale" => link!(scale),
+            "cursor" => link!(cursor),
+            "pointer-events" => link!(pointer_events),
+            _ => false,
+        }
+    }
+
     // Helper function for generating AnimationState from a transition definition.
     fn add_transition<T: Default + Interpolator>(
         &self,
@@ -1736,8 +2569,15 @@ impl Style {
 
     // Remove style data for the given entity.
     pub(crate) fn remove(&mut self, entity: Entity) {
+        for resolved_rule in self.entity_var_rules.remove(entity).unwrap_or_default() {
+            self.release_var_rule(resolved_rule);
+        }
+
         self.ids.remove(entity);
         self.classes.remove(entity);
+        self.attributes.remove(entity);
+        self.direction.remove(entity);
+        self.pseudo_elements.remove(entity);
         self.pseudo_classes.remove(entity);
         self.disabled.remove(entity);
         self.abilities.remove(entity);
@@ -1765,7 +2605,8 @@ impl Style {
         self.overflowx.remove(entity);
         self.overflowy.remove(entity);
 
-        // Backdrop Filter
+        // Filters
+        self.filter.remove(entity);
         self.backdrop_filter.remove(entity);
 
         // Blend Mode
@@ -1813,6 +2654,7 @@ impl Style {
 
         // Box Shadow
         self.shadow.remove(entity);
+        self.text_shadow.remove(entity);
 
         // Text and Font
         self.text.remove(entity);
@@ -1827,8 +2669,13 @@ impl Style {
         self.font_slant.remove(entity);
         self.font_width.remove(entity);
         self.font_variation_settings.remove(entity);
+        self.line_height.remove(entity);
+        self.letter_spacing.remove(entity);
+        self.word_spacing.remove(entity);
+        self.text_indent.remove(entity);
         self.caret_color.remove(entity);
         self.selection_color.remove(entity);
+        self.selected_text_color.remove(entity);
         self.text_decoration_line.remove(entity);
         self.text_stroke_width.remove(entity);
         self.text_stroke_style.remove(entity);
@@ -1889,8 +2736,12 @@ impl Style {
 
         self.text_range.remove(entity);
         self.text_span.remove(entity);
+        self.text_mask.remove(entity);
 
         self.fill.remove(entity);
+
+        self.inline_custom_properties.remove(entity);
+        self.custom_properties.remove(entity);
     }
 
     pub(crate) fn needs_restyle(&mut self, entity: Entity) {
@@ -1935,7 +2786,8 @@ impl Style {
         // Clipping
         self.clip_path.clear_rules();
 
-        // Backdrop Filer
+        // Filters
+        self.filter.clear_rules();
         self.backdrop_filter.clear_rules();
 
         // Blend Mode
@@ -1985,6 +2837,7 @@ impl Style {
         self.background_size.clear_rules();
 
         self.shadow.clear_rules();
+        self.text_shadow.clear_rules();
 
         self.layout_type.clear_rules();
         self.position_type.clear_rules();
@@ -1995,6 +2848,8 @@ impl Style {
         self.grid_rows.clear_rules();
         self.column_start.clear_rules();
         self.column_span.clear_rules();
+        self.row_start.clear_rules();
+        self.row_span.clear_rules();
 
         // Space
         self.left.clear_rules();
@@ -2037,10 +2892,16 @@ impl Style {
         self.font_family.clear_rules();
         self.font_weight.clear_rules();
         self.font_slant.clear_rules();
+        self.font_width.clear_rules();
         self.font_color.clear_rules();
         self.font_size.clear_rules();
         self.font_variation_settings.clear_rules();
+        self.line_height.clear_rules();
+        self.letter_spacing.clear_rules();
+        self.word_spacing.clear_rules();
+        self.text_indent.clear_rules();
         self.selection_color.clear_rules();
+        self.selected_text_color.clear_rules();
         self.caret_color.clear_rules();
         self.text_decoration_line.clear_rules();
         self.text_stroke_width.clear_rules();
@@ -2054,4 +2915,366 @@ impl Style {
 
         self.fill.clear_rules();
     }
+
+    // Removes the shared data of the given rules from every property.
+    fn remove_style_rules(&mut self, rules: &[Rule]) {
+        self.disabled.remove_rules(rules);
+        // Display
+        self.display.remove_rules(rules);
+        // Visibility
+        self.visibility.remove_rules(rules);
+        // Opacity
+        self.opacity.remove_rules(rules);
+        // Z Order
+        self.z_index.remove_rules(rules);
+
+        // Clipping
+        self.clip_path.remove_rules(rules);
+
+        // Filters
+        self.filter.remove_rules(rules);
+        self.backdrop_filter.remove_rules(rules);
+
+        // Blend Mode
+        self.blend_mode.remove_rules(rules);
+
+        // Transform
+        self.transform.remove_rules(rules);
+        self.transform_origin.remove_rules(rules);
+        self.translate.remove_rules(rules);
+        self.rotate.remove_rules(rules);
+        self.scale.remove_rules(rules);
+
+        self.overflowx.remove_rules(rules);
+        self.overflowy.remove_rules(rules);
+
+        // Border
+        self.border_width.remove_rules(rules);
+        self.border_color.remove_rules(rules);
+        self.border_style.remove_rules(rules);
+
+        // Corner Shape
+        self.corner_bottom_left_shape.remove_rules(rules);
+        self.corner_bottom_right_shape.remove_rules(rules);
+        self.corner_top_left_shape.remove_rules(rules);
+        self.corner_top_right_shape.remove_rules(rules);
+
+        // Corner Radius
+        self.corner_bottom_left_radius.remove_rules(rules);
+        self.corner_bottom_right_radius.remove_rules(rules);
+        self.corner_top_left_radius.remove_rules(rules);
+        self.corner_top_right_radius.remove_rules(rules);
+
+        // Corner Smoothing
+        self.corner_bottom_left_smoothing.remove_rules(rules);
+        self.corner_bottom_right_smoothing.remove_rules(rules);
+        self.corner_top_left_smoothing.remove_rules(rules);
+        self.corner_top_right_smoothing.remove_rules(rules);
+
+        // Outline
+        self.outline_width.remove_rules(rules);
+        self.outline_color.remove_rules(rules);
+        self.outline_offset.remove_rules(rules);
+
+        // Background
+        self.background_color.remove_rules(rules);
+        self.background_image.remove_rules(rules);
+        self.background_size.remove_rules(rules);
+
+        self.shadow.remove_rules(rules);
+        self.text_shadow.remove_rules(rules);
+
+        self.layout_type.remove_rules(rules);
+        self.position_type.remove_rules(rules);
+        self.alignment.remove_rules(rules);
+
+        // Grid
+        self.grid_columns.remove_rules(rules);
+        self.grid_rows.remove_rules(rules);
+        self.column_start.remove_rules(rules);
+        self.column_span.remove_rules(rules);
+        self.row_start.remove_rules(rules);
+        self.row_span.remove_rules(rules);
+
+        // Space
+        self.left.remove_rules(rules);
+        self.right.remove_rules(rules);
+        self.top.remove_rules(rules);
+        self.bottom.remove_rules(rules);
+
+        // Size
+        self.width.remove_rules(rules);
+        self.height.remove_rules(rules);
+
+        // Size Constraints
+        self.min_width.remove_rules(rules);
+        self.max_width.remove_rules(rules);
+        self.min_height.remove_rules(rules);
+        self.max_height.remove_rules(rules);
+
+        self.min_horizontal_gap.remove_rules(rules);
+        self.max_horizontal_gap.remove_rules(rules);
+        self.min_vertical_gap.remove_rules(rules);
+        self.max_vertical_gap.remove_rules(rules);
+
+        // Padding
+        self.padding_left.remove_rules(rules);
+        self.padding_right.remove_rules(rules);
+        self.padding_top.remove_rules(rules);
+        self.padding_bottom.remove_rules(rules);
+        self.horizontal_gap.remove_rules(rules);
+        self.vertical_gap.remove_rules(rules);
+
+        // Scrolling
+        self.horizontal_scroll.remove_rules(rules);
+        self.vertical_scroll.remove_rules(rules);
+
+        // Text and Font
+        self.text_wrap.remove_rules(rules);
+        self.text_overflow.remove_rules(rules);
+        self.line_clamp.remove_rules(rules);
+        self.text_align.remove_rules(rules);
+        self.font_family.remove_rules(rules);
+        self.font_weight.remove_rules(rules);
+        self.font_slant.remove_rules(rules);
+        self.font_width.remove_rules(rules);
+        self.font_color.remove_rules(rules);
+        self.font_size.remove_rules(rules);
+        self.font_variation_settings.remove_rules(rules);
+        self.line_height.remove_rules(rules);
+        self.letter_spacing.remove_rules(rules);
+        self.word_spacing.remove_rules(rules);
+        self.text_indent.remove_rules(rules);
+        self.selection_color.remove_rules(rules);
+        self.selected_text_color.remove_rules(rules);
+        self.caret_color.remove_rules(rules);
+        self.text_decoration_line.remove_rules(rules);
+        self.text_stroke_width.remove_rules(rules);
+        self.text_stroke_style.remove_rules(rules);
+
+        self.cursor.remove_rules(rules);
+
+        self.pointer_events.remove_rules(rules);
+
+        self.name.remove_rules(rules);
+
+        self.fill.remove_rules(rules);
+    }
+}
+
+// Returns the direction of the script used by a locale, from its script subtag if present or otherwise its language.
+fn locale_direction(locale: &LanguageIdentifier) -> Direction {
+    const RTL_SCRIPTS: [&str; 7] = ["Adlm", "Arab", "Hebr", "Nkoo", "Rohg", "Syrc", "Thaa"];
+    const RTL_LANGUAGES: [&str; 14] =
+        ["ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "syr", "ug", "ur", "yi"];
+
+    let rtl = match locale.script {
+        Some(script) => RTL_SCRIPTS.contains(&script.as_str()),
+        None => RTL_LANGUAGES.contains(&locale.language.as_str()),
+    };
+
+    if rtl {
+        Direction::Rtl
+    } else {
+        Direction::Ltr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_properties(accent: &str) -> HashMap<String, String> {
+        [("--accent".to_owned(), accent.to_owned())].into_iter().collect()
+    }
+
+    #[test]
+    fn resolved_var_rules_are_reused_once_released() {
+        let mut style = Style::default();
+        style.parse_theme("<test>", ".swatch { background-color: var(--accent); }");
+        let rule = *style.var_rules.keys().next().unwrap();
+
+        let red = style.acquire_var_rule(rule, Some(&custom_properties("#ff0000"))).unwrap();
+        assert_eq!(style.acquire_var_rule(rule, Some(&custom_properties("#ff0000"))), Some(red));
+
+        style.release_var_rule(red);
+        let blue = style.acquire_var_rule(rule, Some(&custom_properties("#0000ff"))).unwrap();
+        assert_ne!(blue, red);
+
+        style.release_var_rule(red);
+        let green = style.acquire_var_rule(rule, Some(&custom_properties("#00ff00"))).unwrap();
+        assert_eq!(green, red);
+        assert_eq!(style.resolved_var_rules.len(), 2);
+    }
+
+    #[test]
+    fn replaced_stylesheets_keep_their_precedence() {
+        let mut style = Style::default();
+        style.parse_theme("<test>", ".a { width: 10px; }");
+        style.parse_theme("<test>", ".b { width: 20px; } .c { width: 30px; }");
+        style.parse_theme("<test>", ".d { width: 40px; }");
+
+        let (stylesheet, _) = parse_stylesheet("<test>", ".e { width: 50px; }");
+        assert!(style.replace_stylesheet(1, stylesheet.unwrap().rules.0));
+
+        let selectors = style
+            .rules
+            .values()
+            .map(|rule| vizia_style::selector_to_css(&rule.selector))
+            .collect::<Vec<_>>();
+        assert_eq!(selectors, [".a", ".e", ".d"]);
+        assert_eq!(style.stylesheet_rules, [1, 1, 1]);
+        assert_eq!(style.width.shared_data.len(), 3);
+
+        let (stylesheet, _) = parse_stylesheet("<test>", ".f { width: 60px; }");
+        assert!(!style.replace_stylesheet(3, stylesheet.unwrap().rules.0));
+
+        let (stylesheet, _) = parse_stylesheet("<test>", ".g { width: 70px; }");
+        style.insert_stylesheet(1, stylesheet.unwrap().rules.0);
+
+        let selectors = style
+            .rules
+            .values()
+            .map(|rule| vizia_style::selector_to_css(&rule.selector))
+            .collect::<Vec<_>>();
+        assert_eq!(selectors, [".a", ".g", ".e", ".d"]);
+        assert_eq!(style.stylesheet_rules, [1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn reloaded_stylesheets_leave_no_data_behind() {
+        let mut style = Style::default();
+        style.parse_theme("<test>", ".a { width: 10px; }");
+        style.parse_theme(
+            "<test>",
+            r#"
+            .b { row-start: 2; row-span: 3; font-width: condensed; }
+
+            @media (min-width: 100px) {
+                .b { width: 20px; }
+            }
+
+            @keyframes pulse {
+                from { opacity: 0; }
+                to { opacity: 1; }
+            }
+            "#,
+        );
+        style.parse_theme("<test>", "@media (max-width: 50px) { .c { width: 30px; } }");
+
+        let pulse = *style.get_animation("pulse").unwrap();
+        assert_eq!(style.media_queries.len(), 2);
+
+        let (stylesheet, _) = parse_stylesheet("<test>", ".b { width: 40px; }");
+        assert!(style.replace_stylesheet(1, stylesheet.unwrap().rules.0));
+
+        // The rule of the new stylesheet reuses the id of a removed rule, and must not pick up its data.
+        let entity = Entity::new(1, 0);
+        let rule = *style.rules.get_index(1).unwrap().0;
+        style.row_start.link(entity, &[(rule, 0)]);
+        style.row_span.link(entity, &[(rule, 0)]);
+        style.font_width.link(entity, &[(rule, 0)]);
+        assert_eq!(style.row_start.get(entity), None);
+        assert_eq!(style.row_span.get(entity), None);
+        assert_eq!(style.font_width.get(entity), None);
+
+        // The media query of the following stylesheet is moved into the place of the removed query.
+        assert_eq!(style.media_queries.len(), 1);
+        let rule = *style.rules.get_index(2).unwrap().0;
+        assert_eq!(style.rule_media[&rule], [0]);
+        assert_eq!(style.stylesheet_resources[2].media_queries, [0]);
+
+        assert!(style.get_animation("pulse").is_none());
+        assert!(style.opacity.get_animation_mut(pulse).is_none());
+    }
+
+    #[test]
+    fn nested_keyframes_are_scoped_to_the_parent_rule() {
+        let mut style = Style::default();
+        style.parse_theme(
+            "<test>",
+            r#"
+            @keyframes pulse {
+                from { opacity: 0; }
+                to { opacity: 1; }
+            }
+
+            .card {
+                @keyframes pulse {
+                    from { opacity: 1; }
+                    to { opacity: 0; }
+                }
+            }
+            "#,
+        );
+
+        let mut tree = Tree::new();
+        let card = Entity::new(1, 0);
+        let other = Entity::new(2, 0);
+        tree.add(card, Entity::root()).unwrap();
+        tree.add(other, Entity::root()).unwrap();
+        style.classes.insert(card, ["card".to_owned()].into_iter().collect());
+
+        let global = style.get_animation("pulse").copied();
+        assert!(global.is_some());
+        assert_ne!(style.get_animation_for(&tree, card, "pulse"), global);
+        assert_eq!(style.get_animation_for(&tree, other, "pulse"), global);
+    }
+
+    #[test]
+    fn nested_rules_are_flattened() {
+        let mut style = Style::default();
+        style.parse_theme(
+            "<test>",
+            r#"
+            .card {
+                width: 40px;
+
+                &:hover {
+                    width: 50px;
+                }
+
+                > .badge {
+                    width: 10px;
+                }
+            }
+            "#,
+        );
+        assert_eq!(style.rules.len(), 3);
+
+        let mut tree = Tree::new();
+        let card = Entity::new(1, 0);
+        let badge = Entity::new(2, 0);
+        let other = Entity::new(3, 0);
+        tree.add(card, Entity::root()).unwrap();
+        tree.add(badge, card).unwrap();
+        tree.add(other, Entity::root()).unwrap();
+        style.classes.insert(card, ["card".to_owned()].into_iter().collect());
+        style.classes.insert(badge, ["badge".to_owned()].into_iter().collect());
+        style.classes.insert(other, ["badge".to_owned()].into_iter().collect());
+
+        let matched = |style: &Style, entity| {
+            crate::systems::compute_matched_rules(entity, style, &tree, None).len()
+        };
+
+        assert_eq!(matched(&style, card), 1);
+        assert_eq!(matched(&style, badge), 1);
+        assert_eq!(matched(&style, other), 0);
+
+        style.pseudo_classes.insert(card, PseudoClassFlags::HOVER);
+        assert_eq!(matched(&style, card), 2);
+    }
+
+    #[test]
+    fn unresolvable_declarations_are_invalid_at_computed_value_time() {
+        let mut style = Style::default();
+        style.parse_theme(
+            "<test>",
+            ".swatch { background-color: var(--missing); color: var(--accent); width: var(--accent); }",
+        );
+        let rule = *style.var_rules.keys().next().unwrap();
+
+        let resolved = style.acquire_var_rule(rule, Some(&custom_properties("#ff0000"))).unwrap();
+        assert_eq!(style.resolved_var_rule_info[&resolved].invalid, ["background-color", "width"]);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{accessibility::IntoNode, events::ViewHandler, prelude::*};
 use accesskit::{Node, NodeId, Rect, Toggled, Tree, TreeUpdate};
 use hashbrown::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
 use vizia_storage::LayoutTreeIterator;
 
 /// Updates node properties from view properties
@@ -157,12 +158,18 @@ pub(crate) fn get_access_node(
     }
 
     if let Some(value) = cx.style.text_value.get(entity) {
-        node_builder.set_value(value.clone().into_boxed_str());
+        // Masked text, such as a password, is exposed as it is displayed.
+        if let Some(mask) = cx.style.text_mask.get(entity) {
+            let masked = mask.to_string().repeat(value.graphemes(true).count());
+            node_builder.set_value(masked.into_boxed_str());
+        } else {
+            node_builder.set_value(value.clone().into_boxed_str());
+        }
     }
 
-    // if let Some(name) = cx.style.name.get(entity) {
-    //     node_builder.set_name(name.clone().into_boxed_str());
-    // }
+    if let Some(name) = cx.style.name.get(entity) {
+        node_builder.set_label(name.clone().into_boxed_str());
+    }
 
     if let Some(numeric_value) = cx.style.numeric_value.get(entity) {
         node_builder.set_numeric_value(*numeric_value);
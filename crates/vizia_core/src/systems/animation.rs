@@ -112,7 +112,7 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
 
     // Tick all animations
 
-    let time = Instant::now();
+    let time = crate::context::now();
 
     let mut redraw_entities = Vec::new();
     let mut reflow_entities = Vec::new();
@@ -134,6 +134,8 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
     redraw_entities.extend(cx.style.background_size.tick(time));
     // Box Shadow
     redraw_entities.extend(cx.style.shadow.tick(time));
+    // Filter
+    redraw_entities.extend(cx.style.filter.tick(time));
     // Transform
     redraw_entities.extend(cx.style.transform.tick(time));
     redraw_entities.extend(cx.style.transform_origin.tick(time));
@@ -153,6 +155,12 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
     reflow_entities.extend(cx.style.font_color.tick(time));
     // Font Size
     reflow_entities.extend(cx.style.font_size.tick(time));
+    // Typography
+    reflow_entities.extend(cx.style.line_height.tick(time));
+    reflow_entities.extend(cx.style.letter_spacing.tick(time));
+    reflow_entities.extend(cx.style.word_spacing.tick(time));
+    reflow_entities.extend(cx.style.text_indent.tick(time));
+    reflow_entities.extend(cx.style.text_shadow.tick(time));
 
     // Properties which affect layout
     relayout_entities.extend(cx.style.display.tick(time));
@@ -1,7 +1,8 @@
 use crate::{animation::Interpolator, cache::CachedData, prelude::*};
 use morphorm::Node;
 use skia_safe::{
-    canvas::SaveLayerRec, ClipOp, ImageFilter, Matrix, Paint, Rect, SamplingOptions, Surface,
+    canvas::SaveLayerRec, color_filters, image_filters, ClipOp, ImageFilter, Matrix, Paint, Rect,
+    SamplingOptions, Surface,
 };
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
@@ -90,12 +91,13 @@ pub(crate) fn transform_system(cx: &mut Context) {
                 .get(entity)
                 .map(|clip| match clip {
                     ClipPath::Auto => bounds,
-                    ClipPath::Shape(rect) => bounds.shrink_sides(
+                    ClipPath::Shape(rect) | ClipPath::Inset(rect, _) => bounds.shrink_sides(
                         rect.3.to_pixels(bounds.w, scale),
                         rect.0.to_pixels(bounds.h, scale),
                         rect.1.to_pixels(bounds.w, scale),
                         rect.2.to_pixels(bounds.h, scale),
                     ),
+                    _ => bounds,
                 })
                 .unwrap_or(bounds);
 
@@ -217,8 +219,45 @@ pub(crate) fn draw_system(
 
     cx.resource_manager.mark_images_unused();
 
+    draw_tree(cx, window_entity, &dirty_rect, canvas);
+
+    canvas.restore();
+
+    surface.canvas().clear(Color::transparent());
+    dirty_surface.draw(surface.canvas(), (0, 0), SamplingOptions::default(), None);
+
+    // Debug draw dirty rect
+    // if let Some(rect) = dirty_rect.map(Rect::from) {
+    //     let mut paint = Paint::default();
+    //     paint.set_style(skia_safe::PaintStyle::Stroke);
+    //     paint.set_color(Color::red());
+    //     paint.set_stroke_width(1.0);
+    //     surface.canvas().draw_rect(rect, &paint);
+    // }
+
+    true
+}
+
+/// Draws a view and its descendants onto a canvas, without the views behind or in front of it.
+pub(crate) fn draw_subtree_system(cx: &mut Context, entity: Entity, canvas: &Canvas) {
+    if !cx.entity_manager.is_alive(entity) {
+        return;
+    }
+
+    transform_system(cx);
+
+    let window_entity = cx.tree.get_parent_window(entity).unwrap_or(Entity::root());
+    let dirty_rect = Some(cx.cache.get_bounds(window_entity));
+
+    canvas.save();
+    draw_tree(cx, entity, &dirty_rect, canvas);
+    canvas.restore();
+}
+
+// Draws the given entity and its descendants in order of z-index.
+fn draw_tree(cx: &mut Context, root: Entity, dirty_rect: &Option<BoundingBox>, canvas: &Canvas) {
     let mut queue = BinaryHeap::new();
-    queue.push(ZEntity { index: 0, entity: window_entity, visible: true });
+    queue.push(ZEntity { index: 0, entity: root, visible: true });
 
     while let Some(zentity) = queue.pop() {
         canvas.save();
@@ -235,8 +274,9 @@ pub(crate) fn draw_system(
                 modifiers: &cx.modifiers,
                 mouse: &cx.mouse,
                 windows: &mut cx.windows,
+                event_queue: &mut cx.event_queue,
             },
-            &dirty_rect,
+            dirty_rect,
             canvas,
             zentity.index,
             &mut queue,
@@ -244,22 +284,6 @@ pub(crate) fn draw_system(
         );
         canvas.restore();
     }
-
-    canvas.restore();
-
-    surface.canvas().clear(Color::transparent());
-    dirty_surface.draw(surface.canvas(), (0, 0), SamplingOptions::default(), None);
-
-    // Debug draw dirty rect
-    // if let Some(rect) = dirty_rect.map(Rect::from) {
-    //     let mut paint = Paint::default();
-    //     paint.set_style(skia_safe::PaintStyle::Stroke);
-    //     paint.set_color(Color::red());
-    //     paint.set_stroke_width(1.0);
-    //     surface.canvas().draw_rect(rect, &paint);
-    // }
-
-    true
 }
 
 fn draw_entity(
@@ -285,35 +309,37 @@ fn draw_entity(
     }
 
     let backdrop_filter = cx.backdrop_filter();
+    let filter = cx.filter().and_then(|filters| image_filter(filters, None, cx.scale_factor()));
     let blend_mode = cx.style.blend_mode.get(current).copied().unwrap_or_default();
 
     canvas.save();
-    let layer_count =
-        if cx.opacity() != 1.0 || backdrop_filter.is_some() || blend_mode != BlendMode::Normal {
-            let mut paint = Paint::default();
-            paint.set_alpha_f(cx.opacity());
-            paint.set_blend_mode(blend_mode.into());
+    let layer_count = if cx.opacity() != 1.0
+        || backdrop_filter.is_some()
+        || filter.is_some()
+        || blend_mode != BlendMode::Normal
+    {
+        let mut paint = Paint::default();
+        paint.set_alpha_f(cx.opacity());
+        paint.set_blend_mode(blend_mode.into());
+        paint.set_image_filter(filter);
 
+        let backdrop = backdrop_filter.and_then(|backdrop_filter| {
             let rect: Rect = cx.bounds().into();
-            let mut filter = ImageFilter::crop(rect, None, None).unwrap();
-
-            let slr = if let Some(backdrop_filter) = backdrop_filter {
-                match backdrop_filter {
-                    Filter::Blur(radius) => {
-                        let sigma = radius.to_px().unwrap() * cx.scale_factor() / 2.0;
-                        filter = filter.blur(None, (sigma, sigma), None).unwrap();
-                        SaveLayerRec::default().paint(&paint).backdrop(&filter)
-                    }
-                }
-            } else {
-                SaveLayerRec::default().paint(&paint)
-            };
+            let crop = ImageFilter::crop(rect, None, None);
+            image_filter(std::slice::from_ref(backdrop_filter), crop, cx.scale_factor())
+        });
 
-            Some(canvas.save_layer(&slr))
+        let slr = if let Some(backdrop) = &backdrop {
+            SaveLayerRec::default().paint(&paint).backdrop(backdrop)
         } else {
-            None
+            SaveLayerRec::default().paint(&paint)
         };
 
+        Some(canvas.save_layer(&slr))
+    } else {
+        None
+    };
+
     if let Some(transform) = cx.cache.transform.get(current) {
         canvas.set_matrix(&(transform.into()));
     }
@@ -322,6 +348,10 @@ fn draw_entity(
         canvas.clip_path(&clip_path, ClipOp::Intersect, true);
     }
 
+    if let Some(clip_shape) = cx.clip_shape() {
+        canvas.clip_path(&clip_shape, ClipOp::Intersect, true);
+    }
+
     let is_visible = match (visible, cx.visibility()) {
         (v, None) => v,
         (_, Some(Visibility::Hidden)) => false,
@@ -357,6 +387,50 @@ fn draw_entity(
     cx.current = current;
 }
 
+/// Builds a Skia image filter from a list of filters, with each filter applied to the output of the previous one.
+fn image_filter(
+    filters: &[Filter],
+    input: Option<ImageFilter>,
+    scale_factor: f32,
+) -> Option<ImageFilter> {
+    filters.iter().fold(input, |input, filter| {
+        let output = match filter {
+            Filter::Blur(radius) => {
+                let sigma = radius.to_px().unwrap_or(0.0) * scale_factor / 2.0;
+                image_filters::blur((sigma, sigma), None, input.clone(), None)
+            }
+
+            Filter::DropShadow(shadow) => {
+                let x = shadow.x_offset.to_px().unwrap_or(0.0) * scale_factor;
+                let y = shadow.y_offset.to_px().unwrap_or(0.0) * scale_factor;
+                let sigma = shadow
+                    .blur_radius
+                    .as_ref()
+                    .and_then(|blur_radius| blur_radius.to_px())
+                    .unwrap_or(0.0)
+                    * scale_factor
+                    / 2.0;
+                let color = shadow
+                    .color
+                    .filter(|color| *color != Color::CurrentColor)
+                    .unwrap_or(Color::black());
+
+                image_filters::drop_shadow((x, y), (sigma, sigma), color, None, input.clone(), None)
+            }
+
+            filter => filter.color_matrix().and_then(|matrix| {
+                image_filters::color_filter(
+                    color_filters::matrix_row_major(&matrix, None),
+                    input.clone(),
+                    None,
+                )
+            }),
+        };
+
+        output.or(input)
+    })
+}
+
 // Must be called after transform and clipping systems to be valid.
 pub(crate) fn draw_bounds(
     style: &Style,
@@ -2,7 +2,7 @@ use std::{cmp::Ordering, collections::BinaryHeap};
 
 use crate::prelude::*;
 use log::debug;
-use skia_safe::Matrix;
+use skia_safe::{Matrix, Path};
 use vizia_storage::{DrawChildIterator, LayoutParentIterator};
 
 // Determines the hovered entity based on the mouse cursor position.
@@ -22,8 +22,10 @@ pub fn hover_system(cx: &mut Context, window_entity: Entity) {
     let mut hovered = window_entity;
     let transform = Matrix::new_identity();
     // let clip_bounds = cx.cache.get_bounds(window_entity);
-    let clip_bounds: BoundingBox =
-        BoundingBox { x: -f32::MAX / 2.0, y: -f32::MAX / 2.0, w: f32::MAX, h: f32::MAX };
+    let mut clip = ClipState {
+        bounds: BoundingBox { x: -f32::MAX / 2.0, y: -f32::MAX / 2.0, w: f32::MAX, h: f32::MAX },
+        shapes: Vec::new(),
+    };
     while !queue.is_empty() {
         let zentity = queue.pop().unwrap();
         cx.with_current(zentity.entity, |cx| {
@@ -34,7 +36,7 @@ pub fn hover_system(cx: &mut Context, window_entity: Entity) {
                 &mut queue,
                 &mut hovered,
                 transform,
-                &clip_bounds,
+                &mut clip,
             );
         });
     }
@@ -85,6 +87,13 @@ pub fn hover_system(cx: &mut Context, window_entity: Entity) {
     }
 }
 
+// The clipping applied to a view by its ancestors.
+struct ClipState {
+    bounds: BoundingBox,
+    // Clip shapes are stored in window coordinates, so they can be tested against the untransformed cursor.
+    shapes: Vec<Path>,
+}
+
 fn hover_entity(
     cx: &mut EventContext,
     current_z: i32,
@@ -92,7 +101,7 @@ fn hover_entity(
     queue: &mut BinaryHeap<ZEntity>,
     hovered: &mut Entity,
     parent_transform: Matrix,
-    clip_bounds: &BoundingBox,
+    clip: &mut ClipState,
 ) {
     // Skip if non-hoverable (will skip any descendants)
     let hoverable = cx
@@ -149,17 +158,28 @@ fn hover_entity(
     let t = t.map_point((cursor_x, cursor_y));
     let tx = t.x;
     let ty = t.y;
-    let clipping = clip_bounds.intersection(&cx.clip_region());
+    let parent_clip_bounds = clip.bounds;
+    clip.bounds = clip.bounds.intersection(&cx.clip_region());
 
-    let b = bounds.intersection(&clipping);
+    let b = bounds.intersection(&clip.bounds);
     // let b = bounds;
 
+    let clip_shape = cx.clip_shape().map(|shape| shape.with_transform(&transform));
+    let has_clip_shape = clip_shape.is_some();
+    clip.shapes.extend(clip_shape);
+    let inside_clip_shapes = clip.shapes.iter().all(|shape| shape.contains((cursor_x, cursor_y)));
+
     if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(cx.current) {
         pseudo_classes.set(PseudoClassFlags::HOVER, false);
     }
 
     if pointer_events {
-        if tx >= b.left() && tx < b.right() && ty >= b.top() && ty < b.bottom() {
+        if tx >= b.left()
+            && tx < b.right()
+            && ty >= b.top()
+            && ty < b.bottom()
+            && inside_clip_shapes
+        {
             *hovered = cx.current;
 
             if !cx
@@ -195,7 +215,12 @@ fn hover_entity(
     let child_iter = DrawChildIterator::new(cx.tree, cx.current);
     for child in child_iter {
         cx.current = child;
-        hover_entity(cx, current_z, pointer_events, queue, hovered, transform, &clipping);
+        hover_entity(cx, current_z, pointer_events, queue, hovered, transform, clip);
+    }
+
+    clip.bounds = parent_clip_bounds;
+    if has_clip_shape {
+        clip.shapes.pop();
     }
 }
 
@@ -1,12 +1,16 @@
-use crate::{cache::CachedData, prelude::*};
+use crate::{cache::CachedData, prelude::*, style::StyleRule};
 #[cfg(feature = "rayon")]
 use dashmap::{DashMap, ReadOnlyView};
 use hashbrown::HashMap;
+use log::warn;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
-use vizia_storage::{LayoutParentIterator, TreeBreadthIterator};
+use std::borrow::Cow;
+use vizia_storage::{
+    LayoutChildIterator, LayoutParentIterator, LayoutTreeIterator, TreeBreadthIterator,
+};
 use vizia_style::{
-    matches_selector,
+    lang_matches, matches_selector, matches_selector_list, parse_selectors,
     precomputed_hash::PrecomputedHash,
     selectors::{
         attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint},
@@ -14,9 +18,10 @@ use vizia_style::{
         context::{MatchingForInvalidation, NeedsSelectorFlags, SelectorCaches},
         matching::ElementSelectorFlags,
         parser::{Component, NthType},
-        OpaqueElement, SelectorImpl,
+        OpaqueElement, SelectorImpl, SelectorList,
     },
-    Element, MatchingContext, MatchingMode, PseudoClass, QuirksMode, SelectorIdent, Selectors,
+    Element, MatchingContext, MatchingMode, MediaEnvironment, PseudoClass, PseudoElement,
+    QuirksMode, SelectorIdent, Selectors, UnresolvedValue,
 };
 
 /// A node used for style matching.
@@ -25,6 +30,20 @@ pub(crate) struct Node<'s, 't> {
     entity: Entity,
     store: &'s Style,
     tree: &'t Tree<Entity>,
+    // The pseudo-element being matched for the entity, if any.
+    pseudo_element: Option<&'s PseudoElement>,
+}
+
+impl<'s, 't> Node<'s, 't> {
+    fn new(entity: Entity, store: &'s Style, tree: &'t Tree<Entity>) -> Self {
+        Node { entity, store, tree, pseudo_element: None }
+    }
+
+    // Returns whether the entity is an anonymous view displaying generated content, which is skipped
+    // when matching the siblings of other views.
+    fn is_generated(&self, entity: Entity) -> bool {
+        self.store.pseudo_elements.contains(entity)
+    }
 }
 
 impl std::fmt::Debug for Node<'_, '_> {
@@ -54,31 +73,31 @@ impl Element for Node<'_, '_> {
     }
 
     fn parent_element(&self) -> Option<Self> {
-        self.tree.get_layout_parent(self.entity).map(|parent| Node {
-            entity: parent,
-            store: self.store,
-            tree: self.tree,
-        })
+        self.tree
+            .get_layout_parent(self.entity)
+            .map(|parent| Node::new(parent, self.store, self.tree))
     }
 
     fn prev_sibling_element(&self) -> Option<Self> {
-        self.tree.get_prev_layout_sibling(self.entity).map(|parent| Node {
-            entity: parent,
-            store: self.store,
-            tree: self.tree,
-        })
+        let mut sibling = self.tree.get_prev_layout_sibling(self.entity);
+        while let Some(entity) = sibling.filter(|entity| self.is_generated(*entity)) {
+            sibling = self.tree.get_prev_layout_sibling(entity);
+        }
+
+        sibling.map(|sibling| Node::new(sibling, self.store, self.tree))
     }
 
     fn next_sibling_element(&self) -> Option<Self> {
-        self.tree.get_next_layout_sibling(self.entity).map(|parent| Node {
-            entity: parent,
-            store: self.store,
-            tree: self.tree,
-        })
+        let mut sibling = self.tree.get_next_layout_sibling(self.entity);
+        while let Some(entity) = sibling.filter(|entity| self.is_generated(*entity)) {
+            sibling = self.tree.get_next_layout_sibling(entity);
+        }
+
+        sibling.map(|sibling| Node::new(sibling, self.store, self.tree))
     }
 
     fn is_empty(&self) -> bool {
-        !self.tree.has_children(self.entity)
+        LayoutChildIterator::new(self.tree, self.entity).all(|child| self.is_generated(child))
     }
 
     fn is_root(&self) -> bool {
@@ -113,7 +132,11 @@ impl Element for Node<'_, '_> {
     }
 
     fn is_pseudo_element(&self) -> bool {
-        false
+        self.pseudo_element.is_some()
+    }
+
+    fn pseudo_element_originating_element(&self) -> Option<Self> {
+        Some(Node::new(self.entity, self.store, self.tree))
     }
 
     fn is_same_type(&self, other: &Self) -> bool {
@@ -156,19 +179,30 @@ impl Element for Node<'_, '_> {
 
     fn attr_matches(
         &self,
-        _ns: &NamespaceConstraint<&<Self::Impl as SelectorImpl>::NamespaceUrl>,
-        _local_name: &<Self::Impl as SelectorImpl>::LocalName,
-        _operation: &AttrSelectorOperation<&<Self::Impl as SelectorImpl>::AttrValue>,
+        ns: &NamespaceConstraint<&<Self::Impl as SelectorImpl>::NamespaceUrl>,
+        local_name: &<Self::Impl as SelectorImpl>::LocalName,
+        operation: &AttrSelectorOperation<&<Self::Impl as SelectorImpl>::AttrValue>,
     ) -> bool {
-        false
+        // Attributes of views have no namespace.
+        if let NamespaceConstraint::Specific(url) = ns {
+            if !url.0.is_empty() {
+                return false;
+            }
+        }
+
+        self.store
+            .attributes
+            .get(self.entity)
+            .and_then(|attributes| attributes.get(&local_name.0))
+            .is_some_and(|value| operation.eval_str(value))
     }
 
     fn match_pseudo_element(
         &self,
-        _pe: &<Self::Impl as SelectorImpl>::PseudoElement,
+        pe: &<Self::Impl as SelectorImpl>::PseudoElement,
         _context: &mut MatchingContext<'_, Self::Impl>,
     ) -> bool {
-        false
+        self.pseudo_element == Some(pe)
     }
 
     fn match_non_ts_pseudo_class(
@@ -217,8 +251,13 @@ impl Element for Node<'_, '_> {
                 PseudoClass::UserInvalid => {
                     psudeo_class_flag.contains(PseudoClassFlags::USER_INVALID)
                 }
-                PseudoClass::Lang(_) => todo!(),
-                PseudoClass::Dir(_) => todo!(),
+                PseudoClass::Lang(langs) => {
+                    let locale = self.store.locale.to_string();
+                    langs.iter().any(|lang| lang_matches(&locale, lang))
+                }
+                PseudoClass::Dir(direction) => {
+                    self.store.text_direction(self.tree, self.entity) == *direction
+                }
                 PseudoClass::Custom(name) => {
                     println!("custom: {}", name);
                     todo!()
@@ -254,6 +293,7 @@ pub(crate) fn inline_inheritance_system(cx: &mut Context, redraw_entities: &mut
             if cx.style.disabled.inherit_inline(entity, parent)
                 | cx.style.caret_color.inherit_inline(entity, parent)
                 | cx.style.selection_color.inherit_inline(entity, parent)
+                | cx.style.selected_text_color.inherit_inline(entity, parent)
             {
                 redraw_entities.push(entity);
             }
@@ -268,6 +308,11 @@ pub(crate) fn inline_inheritance_system(cx: &mut Context, redraw_entities: &mut
                 | cx.style.text_stroke_width.inherit_inline(entity, parent)
                 | cx.style.text_stroke_style.inherit_inline(entity, parent)
                 | cx.style.font_variation_settings.inherit_inline(entity, parent)
+                | cx.style.text_shadow.inherit_inline(entity, parent)
+                | cx.style.line_height.inherit_inline(entity, parent)
+                | cx.style.letter_spacing.inherit_inline(entity, parent)
+                | cx.style.word_spacing.inherit_inline(entity, parent)
+                | cx.style.text_indent.inherit_inline(entity, parent)
             {
                 cx.style.needs_text_update(entity);
             }
@@ -289,12 +334,18 @@ pub(crate) fn shared_inheritance_system(cx: &mut Context, redraw_entities: &mut
                 | cx.style.text_stroke_width.inherit_shared(entity, parent)
                 | cx.style.text_stroke_style.inherit_shared(entity, parent)
                 | cx.style.font_variation_settings.inherit_shared(entity, parent)
+                | cx.style.text_shadow.inherit_shared(entity, parent)
+                | cx.style.line_height.inherit_shared(entity, parent)
+                | cx.style.letter_spacing.inherit_shared(entity, parent)
+                | cx.style.word_spacing.inherit_shared(entity, parent)
+                | cx.style.text_indent.inherit_shared(entity, parent)
             {
                 cx.style.needs_text_update(entity);
             }
 
             if cx.style.caret_color.inherit_shared(entity, parent)
                 | cx.style.selection_color.inherit_shared(entity, parent)
+                | cx.style.selected_text_color.inherit_shared(entity, parent)
             {
                 redraw_entities.push(entity);
             }
@@ -341,6 +392,10 @@ fn link_style_data(
         should_redraw = true;
     }
 
+    if style.filter.link(entity, matched_rules) {
+        should_redraw = true;
+    }
+
     if style.backdrop_filter.link(entity, matched_rules) {
         should_redraw = true;
     }
@@ -585,6 +640,30 @@ fn link_style_data(
         should_reflow = true;
     }
 
+    if style.line_height.link(entity, matched_rules) {
+        should_redraw = true;
+        should_relayout = true;
+        should_reflow = true;
+    }
+
+    if style.letter_spacing.link(entity, matched_rules) {
+        should_redraw = true;
+        should_relayout = true;
+        should_reflow = true;
+    }
+
+    if style.word_spacing.link(entity, matched_rules) {
+        should_redraw = true;
+        should_relayout = true;
+        should_reflow = true;
+    }
+
+    if style.text_indent.link(entity, matched_rules) {
+        should_redraw = true;
+        should_relayout = true;
+        should_reflow = true;
+    }
+
     if style.text_wrap.link(entity, matched_rules) {
         should_redraw = true;
         should_relayout = true;
@@ -610,6 +689,10 @@ fn link_style_data(
         should_redraw = true;
     }
 
+    if style.selected_text_color.link(entity, matched_rules) {
+        should_redraw = true;
+    }
+
     if style.caret_color.link(entity, matched_rules) {
         should_redraw = true;
     }
@@ -664,6 +747,12 @@ fn link_style_data(
         should_redraw = true;
     }
 
+    // Text Shadow
+    if style.text_shadow.link(entity, matched_rules) {
+        should_redraw = true;
+        should_reflow = true;
+    }
+
     if style.padding_left.link(entity, matched_rules) {
         should_relayout = true;
         should_redraw = true;
@@ -747,28 +836,232 @@ fn link_style_data(
     }
 }
 
-/// Compute a list of matching style rules for a given entity.
+/// Computes the custom properties of an entity from those inherited from its parent, the matched rules and any
+/// inline declarations.
+///
+/// Entities are restyled in breadth-first order so the custom properties of the parent are already up to date.
+fn compute_custom_properties(
+    style: &mut Style,
+    tree: &Tree<Entity>,
+    entity: Entity,
+    matched_rules: &[(Rule, u32)],
+) {
+    let mut declared = HashMap::new();
+
+    // Matched rules are ordered from highest to lowest specificity, so insert in reverse to let the more specific
+    // declarations win.
+    for (rule, _) in matched_rules.iter().rev() {
+        if let Some(declarations) = style.custom_property_rules.get(rule) {
+            declared.extend(declarations.iter().map(|(name, value)| (name.as_str(), value)));
+        }
+    }
+
+    if let Some(declarations) = style.inline_custom_properties.get(entity) {
+        declared.extend(declarations.iter().map(|(name, value)| (name.as_str(), value)));
+    }
+
+    let mut computed = tree
+        .get_layout_parent(entity)
+        .and_then(|parent| style.custom_properties.get(parent))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut resolving = HashMap::new();
+    for name in declared.keys() {
+        resolve_custom_property(name, &declared, &mut computed, &mut resolving);
+    }
+
+    if computed.is_empty() {
+        style.custom_properties.remove(entity);
+    } else {
+        style.custom_properties.insert(entity, computed);
+    }
+}
+
+// Substitutes the variables referenced by a declared custom property. Properties which are part of a cycle, or which
+// reference a missing variable without a fallback, are invalid and have no value.
+fn resolve_custom_property(
+    name: &str,
+    declared: &HashMap<&str, &UnresolvedValue>,
+    computed: &mut HashMap<String, String>,
+    resolving: &mut HashMap<String, bool>,
+) -> Option<String> {
+    match resolving.get(name) {
+        // Already resolved.
+        Some(false) => return computed.get(name).cloned(),
+        // Part of a cycle.
+        Some(true) => return None,
+        None => {}
+    }
+
+    let value = declared.get(name)?;
+
+    resolving.insert(name.to_owned(), true);
+    let resolved = value.resolve(&mut |var| {
+        if declared.contains_key(var) {
+            resolve_custom_property(var, declared, computed, resolving)
+        } else {
+            computed.get(var).cloned()
+        }
+    });
+    resolving.insert(name.to_owned(), false);
+
+    match &resolved {
+        Some(value) => computed.insert(name.to_owned(), value.clone()),
+        None => computed.remove(name),
+    };
+
+    resolved
+}
+
+/// Inserts the rules resolved from declarations which use variables before the rules they were resolved from,
+/// so that they take precedence over any other declarations of the same rule.
+fn resolve_var_rules<'a>(
+    style: &mut Style,
+    entity: Entity,
+    matched_rules: &'a [(Rule, u32)],
+) -> Cow<'a, [(Rule, u32)]> {
+    let previous_var_rules = style.entity_var_rules.remove(entity);
+
+    let rules = if matched_rules.iter().any(|(rule, _)| style.var_rules.contains_key(rule)) {
+        let custom_properties = style.custom_properties.remove(entity);
+
+        let mut rules = Vec::with_capacity(matched_rules.len() + 1);
+        let mut var_rules = Vec::new();
+        for (rule, specificity) in matched_rules {
+            if let Some(resolved_rule) = style.acquire_var_rule(*rule, custom_properties.as_ref()) {
+                rules.push((resolved_rule, *specificity));
+                var_rules.push(resolved_rule);
+            }
+            rules.push((*rule, *specificity));
+        }
+
+        if let Some(custom_properties) = custom_properties {
+            style.custom_properties.insert(entity, custom_properties);
+        }
+
+        style.entity_var_rules.insert(entity, var_rules);
+
+        Cow::Owned(rules)
+    } else {
+        Cow::Borrowed(matched_rules)
+    };
+
+    // The previous rules are released after the new ones are acquired, so that a rule which is still used isn't
+    // freed in between.
+    for resolved_rule in previous_var_rules.unwrap_or_default() {
+        style.release_var_rule(resolved_rule);
+    }
+
+    rules
+}
+
+/// Unsets the properties of declarations which are invalid at computed-value time, by linking each of them to only
+/// the rules which take precedence over the invalid declaration.
+fn unset_invalid_declarations(
+    style: &mut Style,
+    entity: Entity,
+    redraw_entities: &mut Vec<Entity>,
+    matched_rules: &[(Rule, u32)],
+) {
+    let mut invalid: Vec<(String, usize)> = Vec::new();
+    for (index, (rule, _)) in matched_rules.iter().enumerate() {
+        if let Some(resolved_rule) = style.resolved_var_rule_info.get(rule) {
+            for name in resolved_rule.invalid.iter() {
+                if !invalid.iter().any(|(invalid_name, _)| invalid_name == name) {
+                    invalid.push((name.clone(), index));
+                }
+            }
+        }
+    }
+
+    let mut changed = false;
+    for (name, index) in invalid {
+        changed |= style.link_property(entity, &name, &matched_rules[..index]);
+    }
+
+    if changed {
+        style.system_flags.set(SystemFlags::RELAYOUT, true);
+        style.needs_text_update(entity);
+        redraw_entities.push(entity);
+    }
+}
+
+/// Compute a list of matching style rules for a given entity, in order of precedence.
+///
+/// The bloom filter of the ancestors of the entity, if given, is used to quickly reject non-matching rules.
 pub(crate) fn compute_matched_rules(
     entity: Entity,
     store: &Style,
     tree: &Tree<Entity>,
-    bloom: &BloomFilter,
+    bloom: Option<&BloomFilter>,
+) -> Vec<(Rule, u32)> {
+    // Generated content is styled by the rules for its pseudo-element of the originating view.
+    match store.pseudo_elements.get(entity) {
+        Some(pseudo_element) => {
+            let originating = tree.get_parent(entity).unwrap_or(Entity::root());
+            compute_pseudo_element_rules(
+                originating,
+                Some(pseudo_element),
+                store,
+                tree,
+                bloom,
+                store.rules.iter(),
+            )
+        }
+
+        None => compute_pseudo_element_rules(entity, None, store, tree, bloom, store.rules.iter()),
+    }
+}
+
+/// Compute a list of the given style rules which match a pseudo-element of an entity, or the entity itself if
+/// `pseudo_element` is `None`, in order of precedence. Rules for `::selection` apply to the entity itself.
+fn compute_pseudo_element_rules<'s>(
+    entity: Entity,
+    pseudo_element: Option<&PseudoElement>,
+    store: &Style,
+    tree: &Tree<Entity>,
+    bloom: Option<&BloomFilter>,
+    rules: impl Iterator<Item = (&'s Rule, &'s StyleRule)>,
 ) -> Vec<(Rule, u32)> {
     let mut matched_rules = Vec::with_capacity(16);
 
     let mut cache = SelectorCaches::default();
     let mut context = MatchingContext::new(
         MatchingMode::Normal,
-        Some(bloom),
+        bloom,
         &mut cache,
         QuirksMode::NoQuirks,
         NeedsSelectorFlags::Yes,
         MatchingForInvalidation::No,
     );
 
-    let node = Node { entity, store, tree };
+    let window = if tree.is_window(entity) {
+        entity
+    } else {
+        tree.get_parent_window(entity).unwrap_or(Entity::root())
+    };
+    let media_matches = store.media_matches.get(&window);
 
-    for (rule_id, rule) in store.rules.iter() {
+    for (rule_id, rule) in rules {
+        let rule_pseudo_element = match (rule.selector.pseudo_element(), pseudo_element) {
+            (Some(PseudoElement::Selection), None) => Some(&PseudoElement::Selection),
+            (rule_pseudo_element, pseudo_element) if rule_pseudo_element == pseudo_element => {
+                rule_pseudo_element
+            }
+            _ => continue,
+        };
+
+        // Skip rules inside `@media` blocks whose queries do not match the window of the entity.
+        if let Some(media) = store.rule_media.get(rule_id) {
+            if !media.iter().all(|index| {
+                media_matches.and_then(|matches| matches.get(*index)).copied().unwrap_or(false)
+            }) {
+                continue;
+            }
+        }
+
+        let node = Node { entity, store, tree, pseudo_element: rule_pseudo_element };
         let matches = matches_selector(&rule.selector, 0, Some(&rule.hashes), &node, &mut context);
 
         if matches {
@@ -781,6 +1074,55 @@ pub(crate) fn compute_matched_rules(
     matched_rules
 }
 
+/// Returns whether an entity matches any of the given selectors.
+pub(crate) fn matches_selectors(
+    store: &Style,
+    tree: &Tree<Entity>,
+    entity: Entity,
+    selector_list: &SelectorList<Selectors>,
+) -> bool {
+    let mut cache = SelectorCaches::default();
+    let mut context = MatchingContext::new(
+        MatchingMode::Normal,
+        None,
+        &mut cache,
+        QuirksMode::NoQuirks,
+        NeedsSelectorFlags::No,
+        MatchingForInvalidation::No,
+    );
+
+    matches_selector_list(selector_list, &Node::new(entity, store, tree), &mut context)
+}
+
+/// Returns the entities which match the given selector, in tree order.
+pub(crate) fn query_selector(store: &Style, tree: &Tree<Entity>, selector: &str) -> Vec<Entity> {
+    let selector_list = match parse_selectors(selector) {
+        Ok(selector_list) => selector_list,
+        Err(err) => {
+            warn!("Failed to parse selector `{}`: {:?}", selector, err.kind);
+            return Vec::new();
+        }
+    };
+
+    let mut cache = SelectorCaches::default();
+    let mut context = MatchingContext::new(
+        MatchingMode::Normal,
+        None,
+        &mut cache,
+        QuirksMode::NoQuirks,
+        NeedsSelectorFlags::No,
+        MatchingForInvalidation::No,
+    );
+
+    LayoutTreeIterator::full(tree)
+        .filter(|entity| {
+            let node = Node::new(*entity, store, tree);
+            !node.is_generated(*entity)
+                && matches_selector_list(&selector_list, &node, &mut context)
+        })
+        .collect()
+}
+
 fn has_same_selector(style: &Style, entity1: Entity, entity2: Entity) -> bool {
     if let Some(element1) = style.element.get(entity1) {
         if let Some(element2) = style.element.get(entity2) {
@@ -805,6 +1147,18 @@ fn has_same_selector(style: &Style, entity1: Entity, entity2: Entity) -> bool {
         }
     }
 
+    if style.pseudo_elements.get(entity1) != style.pseudo_elements.get(entity2) {
+        return false;
+    }
+
+    if style.attributes.get(entity1) != style.attributes.get(entity2) {
+        return false;
+    }
+
+    if style.direction.get(entity1) != style.direction.get(entity2) {
+        return false;
+    }
+
     if let Some(psudeo_class_flag1) = style.pseudo_classes.get(entity1) {
         if let Some(psudeo_class_flag2) = style.pseudo_classes.get(entity2) {
             if psudeo_class_flag2.bits() != psudeo_class_flag1.bits() {
@@ -928,7 +1282,7 @@ impl MatchedRules {
         }
 
         if matched_index.is_none() {
-            let rules = compute_matched_rules(entity, style, tree, filter);
+            let rules = compute_matched_rules(entity, style, tree, Some(&*filter));
             if !rules.is_empty() {
                 #[cfg(feature = "rayon")]
                 {
@@ -960,6 +1314,155 @@ impl MatchedRules {
     }
 }
 
+// An anonymous view which displays the generated content of a `::before` or `::after` pseudo-element.
+struct GeneratedContent;
+
+impl View for GeneratedContent {}
+
+/// Creates, updates, or removes the `::before` and `::after` views of the entities which need restyling, according
+/// to the `content` of their matching rules, before they are styled.
+pub(crate) fn generated_content_system(cx: &mut Context) {
+    if cx.style.restyle.is_empty() {
+        return;
+    }
+
+    let entities = TreeBreadthIterator::full(&cx.tree)
+        .filter(|entity| {
+            cx.style.restyle.contains(*entity) && !cx.style.pseudo_elements.contains(*entity)
+        })
+        .collect::<Vec<_>>();
+
+    // Only the rules with generated content need to be matched, using the bloom filter of the ancestors of each
+    // entity in the same way as the style system.
+    let content_rules = cx
+        .style
+        .rules
+        .iter()
+        .filter(|(rule, _)| cx.style.content.contains_key(*rule))
+        .collect::<Vec<_>>();
+
+    let mut filter = BloomFilter::default();
+    let contents = entities
+        .into_iter()
+        .map(|entity| {
+            if !content_rules.is_empty() {
+                compute_element_hash(entity, &cx.tree, &cx.style, &mut filter);
+            }
+
+            let content = [PseudoElement::Before, PseudoElement::After].map(|pseudo_element| {
+                if content_rules.is_empty() {
+                    return (pseudo_element, None);
+                }
+
+                let content = compute_pseudo_element_rules(
+                    entity,
+                    Some(&pseudo_element),
+                    &cx.style,
+                    &cx.tree,
+                    Some(&filter),
+                    content_rules.iter().copied(),
+                )
+                .first()
+                .and_then(|(rule, _)| match cx.style.content.get(rule) {
+                    Some(Content::Text(text)) => Some(text.clone()),
+                    _ => None,
+                });
+
+                (pseudo_element, content)
+            });
+
+            (entity, content)
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, content) in contents {
+        for (pseudo_element, content) in content {
+            let existing = LayoutChildIterator::new(&cx.tree, entity)
+                .find(|child| cx.style.pseudo_elements.get(*child) == Some(&pseudo_element));
+
+            match (content, existing) {
+                (Some(text), existing) => {
+                    let child = existing.unwrap_or_else(|| {
+                        let child = cx
+                            .with_current(entity, |cx| GeneratedContent.build(cx, |_| {}).entity());
+                        cx.style.pseudo_elements.insert(child, pseudo_element.clone());
+                        cx.style.pointer_events.insert(child, PointerEvents::None);
+                        child
+                    });
+
+                    // Keep `::before` as the first child and `::after` as the last child of the entity.
+                    match pseudo_element {
+                        PseudoElement::Before => {
+                            if cx.tree.get_first_child(entity) != Some(child) {
+                                cx.tree.set_first_child(child).ok();
+                            }
+                        }
+
+                        _ => {
+                            if let Some(last_child) = cx.tree.get_last_child(entity).copied() {
+                                if last_child != child {
+                                    cx.tree.set_next_sibling(last_child, child).ok();
+                                }
+                            }
+                        }
+                    }
+
+                    if cx.style.text.get(child) != Some(&text) {
+                        cx.style.text.insert(child, text);
+                        cx.style.needs_text_update(child);
+                        cx.style.needs_relayout();
+                    }
+
+                    cx.style.needs_restyle(child);
+                }
+
+                (None, Some(child)) => cx.remove(child),
+
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+// Evaluates the media queries of the stylesheets against each window, restyling any window for which the results change.
+pub(crate) fn media_query_system(cx: &mut Context) {
+    if cx.style.media_queries.is_empty() {
+        return;
+    }
+
+    let color_scheme = match cx.data::<Environment>().map(|env| env.theme.get_current_theme()) {
+        Some(ThemeMode::DarkMode) => ColorScheme::Dark,
+        _ => ColorScheme::Light,
+    };
+
+    let mut windows = cx.windows.keys().copied().collect::<Vec<_>>();
+    if windows.is_empty() {
+        windows.push(Entity::root());
+    }
+
+    for window in windows {
+        let bounds = cx.cache.get_bounds(window);
+        let environment = MediaEnvironment {
+            color_scheme,
+            width: cx.style.physical_to_logical(bounds.w),
+            height: cx.style.physical_to_logical(bounds.h),
+            scale_factor: cx.style.scale_factor(),
+        };
+
+        let matches = cx
+            .style
+            .media_queries
+            .iter()
+            .map(|query| query.matches(&environment))
+            .collect::<Vec<_>>();
+
+        if cx.style.media_matches.get(&window) != Some(&matches) {
+            cx.style.media_matches.insert(window, matches);
+            cx.needs_restyle(window);
+        }
+    }
+}
+
 // Iterates the tree and determines the matching style rules for each entity, then links the entity to the corresponding style rule data.
 pub(crate) fn style_system(cx: &mut Context) {
     let mut redraw_entities = Vec::new();
@@ -987,15 +1490,24 @@ pub(crate) fn style_system(cx: &mut Context) {
 
     //  Apply matched rules to entities
     for entity in entities {
-        if let Some(matched_rules) = matched_rules.get(&entity) {
+        let rules = matched_rules.get(&entity);
+
+        compute_custom_properties(&mut cx.style, &cx.tree, entity, rules.unwrap_or_default());
+
+        if let Some(rules) = rules {
+            let rules = resolve_var_rules(&mut cx.style, entity, rules);
             link_style_data(
                 &mut cx.style,
                 &mut cx.cache,
                 &cx.tree,
                 entity,
                 &mut redraw_entities,
-                matched_rules,
+                &rules,
             );
+            unset_invalid_declarations(&mut cx.style, entity, &mut redraw_entities, &rules);
+        } else {
+            // Releases any rules resolved for the previously matched rules.
+            resolve_var_rules(&mut cx.style, entity, &[]);
         }
     }
     cx.style.restyle.clear();
@@ -1006,3 +1518,224 @@ pub(crate) fn style_system(cx: &mut Context) {
         cx.needs_redraw(entity);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Creates a context with the given stylesheet, in which logical and physical sizes are equal.
+    fn context(stylesheet: &'static str) -> Context {
+        let mut cx = Context::default();
+        cx.style.dpi_factor = 1.0;
+        cx.add_stylesheet(stylesheet).unwrap();
+        cx
+    }
+
+    // Runs the systems which evaluate media queries, create generated content, and link style rules to views.
+    fn restyle(cx: &mut Context) {
+        media_query_system(cx);
+        generated_content_system(cx);
+        style_system(cx);
+    }
+
+    fn background_color(cx: &Context, entity: Entity) -> Option<Color> {
+        cx.style.background_color.get(entity).copied()
+    }
+
+    #[test]
+    fn custom_properties_are_inherited_and_substituted() {
+        let cx = &mut context(
+            r#"
+            .card {
+                --accent: #00ff00;
+            }
+
+            .swatch {
+                background-color: var(--accent, #ff0000);
+            }
+            "#,
+        );
+
+        VStack::new(cx, |cx| {
+            Element::new(cx).class("swatch").id("inherited");
+            Element::new(cx).class("swatch").id("inline").custom_property("--accent", "#0000ff");
+        })
+        .class("card");
+        let fallback = Element::new(cx).class("swatch").entity();
+
+        restyle(cx);
+
+        let inherited = cx.query_first("#inherited").unwrap();
+        let inline = cx.query_first("#inline").unwrap();
+        assert_eq!(background_color(cx, inherited), Some(Color::rgb(0, 255, 0)));
+        assert_eq!(background_color(cx, inline), Some(Color::rgb(0, 0, 255)));
+        assert_eq!(background_color(cx, fallback), Some(Color::rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn media_rules_follow_window_size() {
+        let cx = &mut context(
+            r#"
+            .swatch {
+                background-color: #ff0000;
+            }
+
+            @media (min-width: 200px) {
+                .swatch {
+                    background-color: #0000ff;
+                }
+            }
+            "#,
+        );
+
+        let swatch = Element::new(cx).class("swatch").entity();
+
+        cx.cache.set_width(Entity::root(), 100.0);
+        restyle(cx);
+        assert_eq!(background_color(cx, swatch), Some(Color::rgb(255, 0, 0)));
+
+        cx.cache.set_width(Entity::root(), 300.0);
+        restyle(cx);
+        assert_eq!(background_color(cx, swatch), Some(Color::rgb(0, 0, 255)));
+
+        cx.cache.set_width(Entity::root(), 100.0);
+        restyle(cx);
+        assert_eq!(background_color(cx, swatch), Some(Color::rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn attribute_selectors_match_view_attributes() {
+        let cx = &mut context(
+            r#"
+            .alert[data-severity=error] {
+                background-color: #ff0000;
+            }
+            "#,
+        );
+
+        let info = Element::new(cx).class("alert").attribute("data-severity", "info").entity();
+        let error = Element::new(cx).class("alert").attribute("data-severity", "error").entity();
+
+        assert_eq!(cx.query("[data-severity]"), vec![info, error]);
+        assert_eq!(cx.query("[data-severity^=err]"), vec![error]);
+        assert!(cx.query("[data-severity=warning]").is_empty());
+
+        restyle(cx);
+        assert_eq!(background_color(cx, info), None);
+        assert_eq!(background_color(cx, error), Some(Color::rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn lang_and_dir_follow_locale() {
+        let cx = &mut Context::default();
+        cx.style.locale = langid!("ar-EG");
+
+        VStack::new(cx, |cx| {
+            Element::new(cx).id("inherited");
+        });
+        let explicit = Element::new(cx).direction(Direction::Ltr).entity();
+        let inherited = cx.query_first("#inherited").unwrap();
+
+        assert_eq!(cx.query("element:lang(ar)"), vec![inherited, explicit]);
+        assert!(cx.query("element:lang(en)").is_empty());
+        assert_eq!(cx.query("element:dir(rtl)"), vec![inherited]);
+        assert_eq!(cx.query("element:dir(ltr)"), vec![explicit]);
+    }
+
+    #[test]
+    fn generated_content_is_styled_by_pseudo_element_rules() {
+        let cx = &mut context(
+            r#"
+            .card::before {
+                content: "A";
+                background-color: #ff0000;
+            }
+
+            .card::after {
+                content: "B";
+                background-color: #0000ff;
+            }
+            "#,
+        );
+
+        let card = VStack::new(cx, |cx| {
+            Element::new(cx).id("child");
+        })
+        .class("card")
+        .entity();
+        let child = cx.query_first("#child").unwrap();
+
+        restyle(cx);
+
+        let children = LayoutChildIterator::new(&cx.tree, card).collect::<Vec<_>>();
+        let [before, content, after] = children[..] else {
+            panic!("expected generated content around the child, found {:?}", children);
+        };
+
+        assert_eq!(content, child);
+        assert_eq!(cx.style.pseudo_elements.get(before), Some(&PseudoElement::Before));
+        assert_eq!(cx.style.pseudo_elements.get(after), Some(&PseudoElement::After));
+        assert_eq!(cx.style.text.get(before).map(String::as_str), Some("A"));
+        assert_eq!(cx.style.text.get(after).map(String::as_str), Some("B"));
+        assert_eq!(background_color(cx, before), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(background_color(cx, after), Some(Color::rgb(0, 0, 255)));
+
+        // Generated content is not matched by selectors.
+        assert_eq!(cx.query(".card > *"), vec![child]);
+    }
+
+    #[test]
+    fn view_stylesheets_are_scoped_and_overridable() {
+        struct Badge;
+
+        impl View for Badge {
+            fn element(&self) -> Option<&'static str> {
+                Some("badge")
+            }
+
+            fn stylesheet() -> Option<&'static str> {
+                Some(
+                    r#"
+                    background-color: #ff0000;
+
+                    element {
+                        width: 10px;
+                        background-color: #00ff00;
+                    }
+                    "#,
+                )
+            }
+        }
+
+        let cx = &mut context(
+            r#"
+            .override {
+                background-color: #0000ff;
+            }
+
+            element {
+                width: 20px;
+            }
+            "#,
+        );
+
+        let badge = Badge
+            .build(cx, |cx| {
+                Element::new(cx).id("inside");
+            })
+            .entity();
+        let overridden = Badge.build(cx, |_| {}).class("override").entity();
+        let outside = Element::new(cx).entity();
+        let inside = cx.query_first("#inside").unwrap();
+
+        restyle(cx);
+
+        assert_eq!(background_color(cx, badge), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(background_color(cx, inside), Some(Color::rgb(0, 255, 0)));
+        assert_eq!(background_color(cx, overridden), Some(Color::rgb(0, 0, 255)));
+        assert_eq!(background_color(cx, outside), None);
+
+        // The rule of the application takes precedence over the scoped rule of equal specificity.
+        assert_eq!(cx.style.width.get(inside), Some(&Pixels(20.0)));
+    }
+}
@@ -1,15 +1,20 @@
 use skia_safe::{
     font_arguments::VariationPosition,
     textlayout::{
-        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle,
-        RectWidthStyle, TextStyle,
+        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment,
+        PlaceholderStyle, RectHeightStyle, RectWidthStyle, TextBaseline, TextShadow, TextStyle,
     },
     BlendMode, FontArguments, FontStyle, Paint,
 };
+use unicode_segmentation::UnicodeSegmentation;
 use vizia_storage::{LayoutChildIterator, LayoutTreeIterator};
 
+use crate::text::{EditableText, TextContext};
 use crate::{cache::CachedData, prelude::*};
 
+// Skia stands in an object replacement character for each placeholder in the text of a paragraph.
+const PLACEHOLDER: char = '\u{FFFC}';
+
 pub(crate) fn text_system(cx: &mut Context) {
     let iterator = LayoutTreeIterator::full(&cx.tree);
     for entity in iterator {
@@ -20,9 +25,14 @@ pub(crate) fn text_system(cx: &mut Context) {
         if cx.style.text.contains(entity)
             && cx.style.display.get(entity).copied().unwrap_or_default() != Display::None
         {
-            if let Some(paragraph) =
-                build_paragraph(entity, &mut cx.style, &cx.tree, cx.text_context.font_collection())
-            {
+            let width = text_bounds(&cx.style, &cx.cache, &cx.text_context, entity).width();
+            if let Some(paragraph) = build_paragraph(
+                entity,
+                &mut cx.style,
+                &cx.tree,
+                cx.text_context.font_collection(),
+                width,
+            ) {
                 cx.text_context.text_paragraphs.insert(entity, paragraph);
                 cx.style.needs_relayout();
                 cx.style.needs_text_layout(entity);
@@ -41,33 +51,31 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
             continue;
         }
 
-        if let Some(paragraph) = cx.text_context.text_paragraphs.get_mut(entity) {
-            let bounds = cx.cache.get_bounds(entity);
-            let padding_left = cx
-                .style
-                .padding_left
-                .get(entity)
-                .copied()
-                .unwrap_or_default()
-                .to_px(bounds.width(), 0.0)
-                * cx.style.scale_factor();
-            let padding_right = cx
-                .style
-                .padding_right
-                .get(entity)
-                .copied()
-                .unwrap_or_default()
-                .to_px(bounds.width(), 0.0)
-                * cx.style.scale_factor();
-            let text_bounds = cx
-                .text_context
-                .text_bounds
-                .get(entity)
-                .copied()
-                .unwrap_or(bounds.shrink_sides(padding_left, 0.0, padding_right, 0.0));
+        let bounds = cx.cache.get_bounds(entity);
+        let text_bounds = text_bounds(&cx.style, &cx.cache, &cx.text_context, entity);
+
+        // A percentage text-indent is resolved against the width of the text when the paragraph is built, so the
+        // paragraph is rebuilt for the new width.
+        let mut rebuilt = false;
+        if matches!(cx.style.text_indent.get(entity), Some(LengthOrPercentage::Percentage(_)))
+            && cx.text_context.text_paragraphs.contains(entity)
+        {
+            if let Some(paragraph) = build_paragraph(
+                entity,
+                &mut cx.style,
+                &cx.tree,
+                cx.text_context.font_collection(),
+                text_bounds.width(),
+            ) {
+                cx.text_context.text_paragraphs.insert(entity, paragraph);
+                rebuilt = true;
+            }
+        }
 
-            if !cx.style.width.get(entity).copied().unwrap_or_default().is_auto()
-                && !cx.style.height.get(entity).copied().unwrap_or_default().is_auto()
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get_mut(entity) {
+            if rebuilt
+                || (!cx.style.width.get(entity).copied().unwrap_or_default().is_auto()
+                    && !cx.style.height.get(entity).copied().unwrap_or_default().is_auto())
             {
                 if cx.style.text_overflow.get(entity).copied().unwrap_or_default()
                     == TextOverflow::Clip
@@ -91,6 +99,27 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
     cx.style.text_layout.clear();
 }
 
+// Returns the bounds of the text of a view, which are its bounds less the horizontal padding unless set explicitly.
+fn text_bounds(
+    style: &Style,
+    cache: &CachedData,
+    text_context: &TextContext,
+    entity: Entity,
+) -> BoundingBox {
+    let bounds = cache.get_bounds(entity);
+    let padding_left = style.padding_left.get(entity).copied().unwrap_or_default();
+    let padding_right = style.padding_right.get(entity).copied().unwrap_or_default();
+    let padding_left = padding_left.to_px(bounds.width(), 0.0) * style.scale_factor();
+    let padding_right = padding_right.to_px(bounds.width(), 0.0) * style.scale_factor();
+
+    text_context.text_bounds.get(entity).copied().unwrap_or(bounds.shrink_sides(
+        padding_left,
+        0.0,
+        padding_right,
+        0.0,
+    ))
+}
+
 pub fn layout_span(
     style: &Style,
     cache: &mut CachedData,
@@ -147,6 +176,7 @@ pub fn build_paragraph(
     style: &mut Style,
     tree: &Tree<Entity>,
     font_collection: &FontCollection,
+    width: f32,
 ) -> Option<Paragraph> {
     let mut paragraph_style = ParagraphStyle::default();
     // paragraph_style.turn_hinting_off();
@@ -197,12 +227,84 @@ pub fn build_paragraph(
 
     let mut paragraph_builder = ParagraphBuilder::new(&paragraph_style, font_collection);
 
-    add_block(style, tree, entity, &mut paragraph_builder, &mut 0);
+    // Text Indent
+    // Skia paragraphs have no notion of an indent, so the first line is pushed along by an empty placeholder, which
+    // pulls the line back when it has a negative width.
+    let mut current = 0;
+    let text_indent = style
+        .text_indent
+        .get(entity)
+        .map(|indent| indent.to_pixels(width, style.scale_factor()))
+        .unwrap_or(0.0);
+    if text_indent != 0.0 {
+        paragraph_builder.add_placeholder(&PlaceholderStyle::new(
+            text_indent,
+            0.0,
+            PlaceholderAlignment::Baseline,
+            TextBaseline::Alphabetic,
+            0.0,
+        ));
+        current += 1;
+    }
+
+    add_block(style, tree, entity, &mut paragraph_builder, &mut current);
 
     paragraph_builder.add_text("\u{200B}");
     paragraph_builder.build().into()
 }
 
+/// Returns the number of bytes taken up by the `text-indent` placeholder at the start of a paragraph.
+///
+/// Byte offsets of the text must be shifted by this amount to map them onto the paragraph.
+pub(crate) fn text_indent_offset(paragraph: &Paragraph) -> usize {
+    if has_text_indent(paragraph) {
+        PLACEHOLDER.len_utf8()
+    } else {
+        0
+    }
+}
+
+fn has_text_indent(paragraph: &Paragraph) -> bool {
+    !paragraph.get_placeholder_rects().is_empty()
+}
+
+/// Converts a byte offset of the text into the position of the paragraph used by `get_rects_for_range`, which
+/// counts graphemes and the `text-indent` placeholder.
+pub(crate) fn paragraph_position<T: EditableText>(
+    paragraph: &Paragraph,
+    text: &T,
+    offset: usize,
+) -> usize {
+    text.current_grapheme_offset(offset) + usize::from(has_text_indent(paragraph))
+}
+
+/// Converts a position reported by `get_glyph_position_at_coordinate` into a byte offset of the text.
+pub(crate) fn text_offset(paragraph: &Paragraph, text: &str, position: usize) -> usize {
+    let placeholders = usize::from(has_text_indent(paragraph));
+    text.grapheme_indices(true)
+        .nth(position.saturating_sub(placeholders))
+        .map_or(text.len(), |(offset, _)| offset)
+}
+
+/// Converts an index of a paragraph in UTF-16 code units, as used by its line metrics, into a byte offset of the
+/// text.
+pub(crate) fn text_offset_from_utf16<T: EditableText>(
+    paragraph: &Paragraph,
+    text: &T,
+    index: usize,
+) -> usize {
+    let text = text.slice(0..text.len()).unwrap_or_default();
+    let mut units = if has_text_indent(paragraph) { PLACEHOLDER.len_utf16() } else { 0 };
+    for (offset, c) in text.char_indices() {
+        if units >= index {
+            return offset;
+        }
+        units += c.len_utf16();
+    }
+
+    text.len()
+}
+
 fn add_block(
     style: &mut Style,
     tree: &Tree<Entity>,
@@ -213,6 +315,17 @@ fn add_block(
     // let mut new_current = current;
 
     if let Some(text) = style.text.get(entity) {
+        // The placeholder of a textbox is never masked.
+        let placeholder_shown = style.pseudo_classes.get(entity).is_some_and(|pseudo_classes| {
+            pseudo_classes.contains(PseudoClassFlags::PLACEHOLDER_SHOWN)
+        });
+        let masked = style
+            .text_mask
+            .get(entity)
+            .filter(|_| !placeholder_shown)
+            .map(|mask| mask.to_string().repeat(text.graphemes(true).count()));
+        let text = masked.as_ref().unwrap_or(text);
+
         if !text.is_empty() {
             // Text Style
 
@@ -262,10 +375,50 @@ fn add_block(
                 }
             }
 
+            // Text Shadows
+            if let Some(shadows) = style.text_shadow.get(entity) {
+                let scale_factor = style.scale_factor();
+                for shadow in shadows.iter() {
+                    let x = shadow.x_offset.to_px().unwrap_or(0.0) * scale_factor;
+                    let y = shadow.y_offset.to_px().unwrap_or(0.0) * scale_factor;
+                    let blur_radius = shadow
+                        .blur_radius
+                        .as_ref()
+                        .and_then(|blur_radius| blur_radius.to_px())
+                        .unwrap_or(0.0)
+                        * scale_factor;
+                    let color = shadow
+                        .color
+                        .filter(|color| *color != Color::CurrentColor)
+                        .unwrap_or(font_color);
+
+                    text_style.add_shadow(TextShadow::new(color, (x, y), blur_radius as f64 / 2.0));
+                }
+            }
+
             // Font Size
             let font_size = style.font_size.get(entity).map_or(16.0, |f| f.0);
             text_style.set_font_size(font_size * style.scale_factor());
 
+            // Line Height
+            if let Some(height) = style
+                .line_height
+                .get(entity)
+                .and_then(|line_height| line_height.to_multiplier(font_size))
+            {
+                text_style.set_height(height);
+                text_style.set_height_override(true);
+            }
+
+            // Letter & Word Spacing
+            if let Some(letter_spacing) = style.letter_spacing.get(entity) {
+                text_style.set_letter_spacing(letter_spacing.to_px() * style.scale_factor());
+            }
+
+            if let Some(word_spacing) = style.word_spacing.get(entity) {
+                text_style.set_word_spacing(word_spacing.to_px() * style.scale_factor());
+            }
+
             // Font Style
             match (
                 style.font_weight.get(entity),
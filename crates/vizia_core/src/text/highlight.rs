@@ -0,0 +1,409 @@
+use std::ops::Range;
+
+use crate::prelude::Data;
+
+/// The kind of a [`Token`], which determines the class used to style it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A keyword of the language, such as `fn` or `let`.
+    Keyword,
+    /// The name of a type.
+    Type,
+    /// A string literal.
+    String,
+    /// A number literal.
+    Number,
+    /// A comment.
+    Comment,
+    /// A kind of token defined by a tokenizer, which is styled with the given class name.
+    Custom(&'static str),
+}
+
+impl TokenKind {
+    /// Returns the class name used to style tokens of this kind.
+    pub fn class_name(&self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "keyword",
+            TokenKind::Type => "type",
+            TokenKind::String => "string",
+            TokenKind::Number => "number",
+            TokenKind::Comment => "comment",
+            TokenKind::Custom(class_name) => class_name,
+        }
+    }
+}
+
+/// A styled range of a line of text, as returned by a [`Tokenizer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The byte range of the token within its line.
+    pub range: Range<usize>,
+    /// The kind of the token.
+    pub kind: TokenKind,
+}
+
+impl Token {
+    /// Creates a new token from a byte range and a kind.
+    pub fn new(range: Range<usize>, kind: TokenKind) -> Self {
+        Self { range, kind }
+    }
+}
+
+/// A trait for splitting lines of source code into styled tokens for syntax highlighting.
+///
+/// Lines are tokenized one at a time, so that only the lines which change need to be tokenized again. Constructs
+/// which span multiple lines, such as block comments, are tracked with a state value which is passed from the end
+/// of one line to the start of the next.
+pub trait Tokenizer {
+    /// Returns the tokens of a line, which must be in order and must not overlap. Text which is not part of a token is
+    /// left unstyled.
+    ///
+    /// `state` is the state at the end of the previous line, which is `0` for the first line, and should be updated to
+    /// the state at the end of this line.
+    fn tokenize_line(&self, line: &str, state: &mut u32) -> Vec<Token>;
+}
+
+// The states of a `BasicTokenizer` at the end of a line.
+const STATE_NORMAL: u32 = 0;
+const STATE_BLOCK_COMMENT: u32 = 1;
+// Strings which continue onto the next line are stored as this state plus the index of their quote character.
+const STATE_STRING: u32 = 2;
+
+/// A simple [`Tokenizer`] for languages which share the lexical structure of C, highlighting keywords, types,
+/// strings, numbers and comments.
+#[derive(Debug, Clone)]
+pub struct BasicTokenizer {
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    // Whether identifiers starting with an uppercase letter are highlighted as types.
+    capitalized_types: bool,
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+impl BasicTokenizer {
+    /// Creates a tokenizer for Rust.
+    pub fn rust() -> Self {
+        Self {
+            keywords: &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super",
+                "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            types: &[
+                "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str",
+                "u8", "u16", "u32", "u64", "u128", "usize",
+            ],
+            capitalized_types: true,
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"'],
+        }
+    }
+
+    /// Creates a tokenizer for JSON.
+    pub fn json() -> Self {
+        Self {
+            keywords: &["true", "false", "null"],
+            types: &[],
+            capitalized_types: false,
+            line_comment: None,
+            block_comment: None,
+            quotes: &['"'],
+        }
+    }
+}
+
+impl Tokenizer for BasicTokenizer {
+    fn tokenize_line(&self, line: &str, state: &mut u32) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+
+        // Continue a block comment or string from the previous line.
+        if *state == STATE_BLOCK_COMMENT {
+            if let Some((_, end)) = self.block_comment {
+                let Some(index) = line.find(end) else {
+                    if !line.is_empty() {
+                        tokens.push(Token::new(0..line.len(), TokenKind::Comment));
+                    }
+                    return tokens;
+                };
+
+                offset = index + end.len();
+                tokens.push(Token::new(0..offset, TokenKind::Comment));
+            }
+        } else if let Some(quote) =
+            state.checked_sub(STATE_STRING).and_then(|index| self.quotes.get(index as usize))
+        {
+            let Some(end) = string_end(line, 0, *quote) else {
+                if !line.is_empty() {
+                    tokens.push(Token::new(0..line.len(), TokenKind::String));
+                }
+                return tokens;
+            };
+
+            offset = end;
+            tokens.push(Token::new(0..offset, TokenKind::String));
+        }
+
+        *state = STATE_NORMAL;
+
+        while let Some(c) = line[offset..].chars().next() {
+            let rest = &line[offset..];
+
+            if self.line_comment.is_some_and(|comment| rest.starts_with(comment)) {
+                tokens.push(Token::new(offset..line.len(), TokenKind::Comment));
+                break;
+            }
+
+            if let Some((start, end)) =
+                self.block_comment.filter(|(start, _)| rest.starts_with(start))
+            {
+                let Some(index) = rest[start.len()..].find(end) else {
+                    tokens.push(Token::new(offset..line.len(), TokenKind::Comment));
+                    *state = STATE_BLOCK_COMMENT;
+                    break;
+                };
+
+                let end = offset + start.len() + index + end.len();
+                tokens.push(Token::new(offset..end, TokenKind::Comment));
+                offset = end;
+                continue;
+            }
+
+            if let Some(index) = self.quotes.iter().position(|quote| *quote == c) {
+                let Some(end) = string_end(line, offset + c.len_utf8(), c) else {
+                    tokens.push(Token::new(offset..line.len(), TokenKind::String));
+                    *state = STATE_STRING + index as u32;
+                    break;
+                };
+
+                tokens.push(Token::new(offset..end, TokenKind::String));
+                offset = end;
+                continue;
+            }
+
+            if c.is_ascii_digit() {
+                let len = number_len(rest);
+                tokens.push(Token::new(offset..offset + len, TokenKind::Number));
+                offset += len;
+                continue;
+            }
+
+            if c.is_alphabetic() || c == '_' {
+                let len =
+                    rest.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(rest.len());
+                let word = &rest[..len];
+                let kind = if self.keywords.contains(&word) {
+                    Some(TokenKind::Keyword)
+                } else if self.types.contains(&word) || (self.capitalized_types && c.is_uppercase())
+                {
+                    Some(TokenKind::Type)
+                } else {
+                    None
+                };
+
+                if let Some(kind) = kind {
+                    tokens.push(Token::new(offset..offset + len, kind));
+                }
+                offset += len;
+                continue;
+            }
+
+            offset += c.len_utf8();
+        }
+
+        tokens
+    }
+}
+
+// Returns the byte offset following the closing quote of a string whose contents start at the given offset, or
+// `None` if the string is not closed on this line.
+fn string_end(line: &str, start: usize, quote: char) -> Option<usize> {
+    let mut chars = line[start..].char_indices();
+    while let Some((index, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == quote {
+            return Some(start + index + c.len_utf8());
+        }
+    }
+
+    None
+}
+
+// Returns the length of the number at the start of the text, including any suffix such as `u32`, and a decimal
+// point only if it is followed by a digit, so that ranges such as `0..10` are not treated as one number.
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let byte = bytes[len];
+        let decimal_point =
+            byte == b'.' && bytes.get(len + 1).is_some_and(|next| next.is_ascii_digit());
+        if !byte.is_ascii_alphanumeric() && byte != b'_' && !decimal_point {
+            break;
+        }
+        len += 1;
+    }
+
+    len
+}
+
+/// A line of text along with its tokens.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct HighlightedLine {
+    text: String,
+    #[data(eq)]
+    tokens: Vec<Token>,
+    // The state of the tokenizer at the start and end of the line.
+    start_state: u32,
+    end_state: u32,
+}
+
+impl HighlightedLine {
+    fn new(text: &str, state: &mut u32, tokenizer: Option<&dyn Tokenizer>) -> Self {
+        let start_state = *state;
+        let mut tokens =
+            tokenizer.map(|tokenizer| tokenizer.tokenize_line(text, state)).unwrap_or_default();
+
+        // Tokens which are out of order, overlap, or do not fall on character boundaries are discarded.
+        let mut end = 0;
+        tokens.retain(|token| {
+            let valid = token.range.start >= end
+                && token.range.start < token.range.end
+                && token.range.end <= text.len()
+                && text.is_char_boundary(token.range.start)
+                && text.is_char_boundary(token.range.end);
+            if valid {
+                end = token.range.end;
+            }
+            valid
+        });
+
+        Self { text: text.to_owned(), tokens, start_state, end_state: *state }
+    }
+
+    /// Returns the text of the line.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the tokens of the line.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+}
+
+/// Updates a list of highlighted lines to match the given text, and returns the range of lines which were tokenized.
+///
+/// Only the lines which differ from the text are tokenized, along with any following lines whose state at the
+/// start has changed as a result, such as when a block comment is opened or closed.
+pub fn highlight_lines(
+    lines: &mut Vec<HighlightedLine>,
+    text: &str,
+    tokenizer: Option<&dyn Tokenizer>,
+) -> Range<usize> {
+    let new_lines = text.split('\n').collect::<Vec<_>>();
+
+    // Find the lines at the start and end of the text which have not changed.
+    let prefix = lines.iter().zip(&new_lines).take_while(|(line, new)| line.text == **new).count();
+    let suffix = lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(line, new)| line.text == **new)
+        .count();
+
+    let mut state = prefix.checked_sub(1).map_or(0, |index| lines[index].end_state);
+    let changed = new_lines[prefix..new_lines.len() - suffix]
+        .iter()
+        .map(|line| HighlightedLine::new(line, &mut state, tokenizer))
+        .collect::<Vec<_>>();
+
+    let mut end = prefix + changed.len();
+    let removed = prefix..lines.len() - suffix;
+    lines.splice(removed, changed);
+
+    // The following lines are tokenized again until one starts in the same state as before.
+    while end < lines.len() && lines[end].start_state != state {
+        let text = std::mem::take(&mut lines[end].text);
+        lines[end] = HighlightedLine::new(&text, &mut state, tokenizer);
+        end += 1;
+    }
+
+    prefix..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &str, state: &mut u32) -> Vec<(&str, TokenKind)> {
+        BasicTokenizer::rust()
+            .tokenize_line(line, state)
+            .into_iter()
+            .map(|token| (&line[token.range], token.kind))
+            .collect()
+    }
+
+    #[test]
+    fn rust_tokens() {
+        let mut state = 0;
+        assert_eq!(
+            kinds("let x: Vec<u8> = \"a \\\" b\"; // 0..10", &mut state),
+            vec![
+                ("let", TokenKind::Keyword),
+                ("Vec", TokenKind::Type),
+                ("u8", TokenKind::Type),
+                ("\"a \\\" b\"", TokenKind::String),
+                ("// 0..10", TokenKind::Comment),
+            ]
+        );
+
+        assert_eq!(
+            kinds("for i in 0..10 { 1.5f32 }", &mut state),
+            vec![
+                ("for", TokenKind::Keyword),
+                ("in", TokenKind::Keyword),
+                ("0", TokenKind::Number),
+                ("10", TokenKind::Number),
+                ("1.5f32", TokenKind::Number),
+            ]
+        );
+        assert_eq!(state, 0);
+
+        assert_eq!(kinds("x /* start", &mut state), vec![("/* start", TokenKind::Comment)]);
+        assert_eq!(
+            kinds("end */ fn", &mut state),
+            vec![("end */", TokenKind::Comment), ("fn", TokenKind::Keyword)]
+        );
+        assert_eq!(state, 0);
+    }
+
+    #[test]
+    fn only_changed_lines_are_tokenized() {
+        let tokenizer = BasicTokenizer::rust();
+        let mut lines = Vec::new();
+
+        let text = "fn a() {}\nlet b = 1;\nlet c = 2;\nlet d = 3;";
+        assert_eq!(highlight_lines(&mut lines, text, Some(&tokenizer)), 0..4);
+        assert_eq!(highlight_lines(&mut lines, text, Some(&tokenizer)), 4..4);
+
+        let text = "fn a() {}\nlet b = 10;\nlet c = 2;\nlet d = 3;";
+        assert_eq!(highlight_lines(&mut lines, text, Some(&tokenizer)), 1..2);
+
+        // Opening a block comment changes the state of the following lines.
+        let text = "fn a() {}\nlet b = 10; /*\nlet c = 2;\nlet d = 3;";
+        assert_eq!(highlight_lines(&mut lines, text, Some(&tokenizer)), 1..4);
+        assert_eq!(lines[3].tokens(), &[Token::new(0..10, TokenKind::Comment)]);
+
+        // Inserting a line within the comment leaves the lines after it unchanged.
+        let text = "fn a() {}\nlet b = 10; /*\n\nlet c = 2;\nlet d = 3;";
+        assert_eq!(highlight_lines(&mut lines, text, Some(&tokenizer)), 2..3);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[4].text(), "let d = 3;");
+    }
+}
@@ -0,0 +1,333 @@
+use std::ops::Range;
+
+use super::{EditableText, Selection};
+
+/// Describes the kind of an edit, which determines whether it is grouped with the previous edit in the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKind {
+    /// Typing text at the caret. Consecutive typing is grouped into a single history entry.
+    Typing,
+    /// Deleting text next to the caret. Consecutive deletions are grouped into a single history entry.
+    Deleting,
+    /// Any other edit, such as pasting or cutting text, which always creates a new history entry.
+    Other,
+}
+
+/// A reversible edit of a text buffer.
+#[derive(Debug, Clone)]
+struct TextEdit {
+    kind: EditKind,
+    /// The byte offset of the start of the edit.
+    start: usize,
+    /// The text which was removed by the edit.
+    removed: String,
+    /// The text which was inserted by the edit.
+    inserted: String,
+    /// The selection before the edit was applied.
+    selection_before: Selection,
+    /// The selection after the edit was applied.
+    selection_after: Selection,
+}
+
+impl TextEdit {
+    /// Tries to merge a following edit into this edit, returning false if the edits cannot be grouped.
+    fn merge(&mut self, next: &TextEdit) -> bool {
+        if self.kind != next.kind {
+            return false;
+        }
+
+        match next.kind {
+            EditKind::Typing => {
+                if !next.removed.is_empty() || next.start != self.start + self.inserted.len() {
+                    return false;
+                }
+
+                self.inserted.push_str(&next.inserted);
+            }
+
+            EditKind::Deleting => {
+                if !self.inserted.is_empty() || !next.inserted.is_empty() {
+                    return false;
+                }
+
+                if next.start + next.removed.len() == self.start {
+                    // Deleting backwards.
+                    self.removed.insert_str(0, &next.removed);
+                    self.start = next.start;
+                } else if next.start == self.start {
+                    // Deleting forwards.
+                    self.removed.push_str(&next.removed);
+                } else {
+                    return false;
+                }
+            }
+
+            EditKind::Other => return false,
+        }
+
+        self.selection_after = next.selection_after;
+
+        true
+    }
+}
+
+/// The undo and redo history of edits to a text buffer.
+///
+/// Edits are applied to the text with [`edit`](EditHistory::edit), which records them so that they can later be
+/// reverted with [`undo`](EditHistory::undo) and reapplied with [`redo`](EditHistory::redo).
+#[derive(Debug, Clone, Default)]
+pub struct EditHistory {
+    undo_stack: Vec<TextEdit>,
+    redo_stack: Vec<TextEdit>,
+    // Whether the next edit can be grouped with the most recent edit.
+    grouping: bool,
+}
+
+impl EditHistory {
+    /// Creates a new empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces a range of the text with new text and records the edit.
+    ///
+    /// `selection` is the selection before the edit, which is restored when the edit is undone, and `selection_after`
+    /// is the selection after the edit, which is restored when the edit is redone.
+    pub fn edit<T: EditableText>(
+        &mut self,
+        text: &mut T,
+        range: Range<usize>,
+        new: &str,
+        selection: Selection,
+        selection_after: Selection,
+        kind: EditKind,
+    ) {
+        let removed =
+            text.slice(range.clone()).map(|removed| removed.into_owned()).unwrap_or_default();
+
+        text.edit(range.clone(), T::from_str(new));
+
+        let edit = TextEdit {
+            kind,
+            start: range.start,
+            removed,
+            inserted: new.to_owned(),
+            selection_before: selection,
+            selection_after,
+        };
+
+        self.redo_stack.clear();
+
+        let merged = self.grouping
+            && self.undo_stack.last_mut().map(|last| last.merge(&edit)).unwrap_or(false);
+        if !merged {
+            self.undo_stack.push(edit);
+        }
+
+        self.grouping = kind != EditKind::Other;
+    }
+
+    /// Stops the next edit from being grouped with the most recent edit, such as when the caret is moved.
+    pub fn break_group(&mut self) {
+        self.grouping = false;
+    }
+
+    /// Reverts the most recent edit, returning the selection from before the edit.
+    ///
+    /// Returns `None` if there is nothing to undo. If the text no longer matches the recorded edit, because it was
+    /// changed without being recorded, the history is cleared.
+    pub fn undo<T: EditableText>(&mut self, text: &mut T) -> Option<Selection> {
+        let edit = self.undo_stack.pop()?;
+        self.grouping = false;
+
+        let range = edit.start..edit.start + edit.inserted.len();
+        if text.slice(range.clone()).as_deref() != Some(edit.inserted.as_str()) {
+            self.clear();
+            return None;
+        }
+
+        text.edit(range, T::from_str(&edit.removed));
+        let selection = edit.selection_before;
+        self.redo_stack.push(edit);
+
+        Some(selection)
+    }
+
+    /// Reapplies the most recently undone edit, returning the selection from after the edit.
+    ///
+    /// Returns `None` if there is nothing to redo. If the text no longer matches the recorded edit, because it was
+    /// changed without being recorded, the history is cleared.
+    pub fn redo<T: EditableText>(&mut self, text: &mut T) -> Option<Selection> {
+        let edit = self.redo_stack.pop()?;
+        self.grouping = false;
+
+        let range = edit.start..edit.start + edit.removed.len();
+        if text.slice(range.clone()).as_deref() != Some(edit.removed.as_str()) {
+            self.clear();
+            return None;
+        }
+
+        text.edit(range, T::from_str(&edit.inserted));
+        let selection = edit.selection_after;
+        self.undo_stack.push(edit);
+
+        Some(selection)
+    }
+
+    /// Removes all edits from the history.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.grouping = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(
+        history: &mut EditHistory,
+        text: &mut String,
+        selection: &mut Selection,
+        new: &str,
+    ) {
+        for c in new.chars() {
+            let mut buffer = [0; 4];
+            let after = Selection::caret(selection.min() + c.len_utf8());
+            history.edit(
+                text,
+                selection.range(),
+                c.encode_utf8(&mut buffer),
+                *selection,
+                after,
+                EditKind::Typing,
+            );
+            *selection = after;
+        }
+    }
+
+    #[test]
+    fn typing_is_grouped_into_one_entry() {
+        let mut history = EditHistory::new();
+        let mut text = String::new();
+        let mut selection = Selection::caret(0);
+
+        type_text(&mut history, &mut text, &mut selection, "hello");
+        history.break_group();
+        type_text(&mut history, &mut text, &mut selection, "world");
+        assert_eq!(text, "helloworld");
+
+        assert_eq!(history.undo(&mut text).map(|selection| selection.active), Some(5));
+        assert_eq!(text, "hello");
+        assert_eq!(history.undo(&mut text).map(|selection| selection.active), Some(0));
+        assert_eq!(text, "");
+        assert!(history.undo(&mut text).is_none());
+
+        assert_eq!(history.redo(&mut text).map(|selection| selection.active), Some(5));
+        assert_eq!(text, "hello");
+        assert_eq!(history.redo(&mut text).map(|selection| selection.active), Some(10));
+        assert_eq!(text, "helloworld");
+        assert!(history.redo(&mut text).is_none());
+    }
+
+    #[test]
+    fn typing_and_deleting_are_separate_entries() {
+        let mut history = EditHistory::new();
+        let mut text = String::new();
+        let mut selection = Selection::caret(0);
+
+        type_text(&mut history, &mut text, &mut selection, "abc");
+        history.edit(&mut text, 2..3, "", selection, Selection::caret(2), EditKind::Deleting);
+        assert_eq!(text, "ab");
+
+        assert_eq!(history.undo(&mut text).map(|selection| selection.active), Some(3));
+        assert_eq!(text, "abc");
+        assert_eq!(history.undo(&mut text).map(|selection| selection.active), Some(0));
+        assert_eq!(text, "");
+
+        assert_eq!(history.redo(&mut text).map(|selection| selection.active), Some(3));
+        assert_eq!(text, "abc");
+    }
+
+    #[test]
+    fn deletions_are_grouped_and_restore_selection() {
+        let mut history = EditHistory::new();
+        let mut text = String::from("abcdef");
+
+        let mut selection = Selection::caret(4);
+        for _ in 0..2 {
+            let after = Selection::caret(selection.active - 1);
+            history.edit(
+                &mut text,
+                selection.active - 1..selection.active,
+                "",
+                selection,
+                after,
+                EditKind::Deleting,
+            );
+            selection = after;
+        }
+        assert_eq!(text, "abef");
+
+        history.edit(
+            &mut text,
+            1..3,
+            "X",
+            Selection::new(1, 3),
+            Selection::caret(2),
+            EditKind::Typing,
+        );
+        assert_eq!(text, "aXf");
+
+        let restored = history.undo(&mut text).unwrap();
+        assert_eq!(text, "abef");
+        assert_eq!(restored.range(), 1..3);
+
+        let restored = history.undo(&mut text).unwrap();
+        assert_eq!(text, "abcdef");
+        assert_eq!(restored.active, 4);
+    }
+
+    #[test]
+    fn redo_restores_the_selection_from_the_caller() {
+        let mut history = EditHistory::new();
+        let mut text = String::from("__/__");
+
+        // An input mask may rewrite more text than was typed and place the caret within it.
+        history.edit(
+            &mut text,
+            0..5,
+            "1_/__",
+            Selection::caret(0),
+            Selection::caret(1),
+            EditKind::Other,
+        );
+        history.undo(&mut text);
+        assert_eq!(text, "__/__");
+
+        assert_eq!(history.redo(&mut text).map(|selection| selection.active), Some(1));
+        assert_eq!(text, "1_/__");
+    }
+
+    #[test]
+    fn stale_history_is_cleared() {
+        let mut history = EditHistory::new();
+        let mut text = String::new();
+        history.edit(
+            &mut text,
+            0..0,
+            "abc",
+            Selection::caret(0),
+            Selection::caret(3),
+            EditKind::Other,
+        );
+
+        text = String::from("xyz");
+        assert!(history.undo(&mut text).is_none());
+        assert_eq!(text, "xyz");
+
+        text.clear();
+        assert!(history.undo(&mut text).is_none());
+    }
+}
@@ -0,0 +1,243 @@
+use std::ops::Range;
+
+/// A single position of an [`InputMask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaskSlot {
+    /// Accepts an ASCII digit.
+    Digit,
+    /// Accepts an alphabetic character.
+    Letter,
+    /// Accepts an alphanumeric character.
+    Alphanumeric,
+    /// A fixed character which is inserted automatically.
+    Literal(char),
+}
+
+impl MaskSlot {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            MaskSlot::Digit => c.is_ascii_digit(),
+            MaskSlot::Letter => c.is_alphabetic(),
+            MaskSlot::Alphanumeric => c.is_alphanumeric(),
+            MaskSlot::Literal(literal) => c == literal,
+        }
+    }
+}
+
+/// A pattern which restricts the characters that can be entered into a text field, such as `####-####` for a
+/// licence key or `##/##/####` for a date.
+///
+/// Each character of the pattern describes one character of the text:
+/// - `#` accepts an ASCII digit.
+/// - `A` accepts an alphabetic character.
+/// - `*` accepts an alphanumeric character.
+/// - `\` makes the following character a literal.
+/// - Any other character is a literal, which is inserted automatically and skipped over by the caret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMask {
+    slots: Vec<MaskSlot>,
+}
+
+impl InputMask {
+    /// Creates a new input mask from a pattern.
+    pub fn new(pattern: &str) -> Self {
+        let mut slots = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '#' => MaskSlot::Digit,
+                'A' => MaskSlot::Letter,
+                '*' => MaskSlot::Alphanumeric,
+                '\\' => MaskSlot::Literal(chars.next().unwrap_or('\\')),
+                c => MaskSlot::Literal(c),
+            });
+        }
+
+        Self { slots }
+    }
+
+    fn is_literal(&self, index: usize) -> bool {
+        matches!(self.slots.get(index), Some(MaskSlot::Literal(_)))
+    }
+
+    /// Formats input characters into the mask, starting at the slot with the given index, and returns the formatted
+    /// text along with the index of the slot following it.
+    ///
+    /// Literals are inserted in front of accepted characters, while characters which are not accepted by the next
+    /// slot are discarded.
+    fn format(&self, start: usize, input: impl IntoIterator<Item = char>) -> (String, usize) {
+        let mut output = String::new();
+        let mut index = start;
+        for c in input {
+            // Find the next slot which accepts the character, which may be one of the literals before the next
+            // editable slot.
+            let mut next = index;
+            while next < self.slots.len() {
+                if self.slots[next].accepts(c) || !self.is_literal(next) {
+                    break;
+                }
+                next += 1;
+            }
+
+            if next < self.slots.len() && self.slots[next].accepts(c) {
+                for slot in &self.slots[index..next] {
+                    if let MaskSlot::Literal(literal) = slot {
+                        output.push(*literal);
+                    }
+                }
+                output.push(c);
+                index = next + 1;
+            }
+        }
+
+        (output, index)
+    }
+
+    /// Returns the characters of a section of text, starting at the slot with the given index, which were entered
+    /// by the user rather than inserted by the mask.
+    fn unformat<'a>(&'a self, start: usize, text: &'a str) -> impl Iterator<Item = char> + 'a {
+        text.chars().enumerate().filter(move |(i, _)| !self.is_literal(start + i)).map(|(_, c)| c)
+    }
+
+    /// Returns the edit which replaces a range of text, that already matches the mask, with new text.
+    ///
+    /// The edit is returned as the byte range to replace, the replacement text, and the byte offset of the caret
+    /// after the inserted characters. Rejected characters are removed from the new text, and the text following the
+    /// range is reformatted so that the result still matches the mask. Returns `None` if a non-empty insertion has
+    /// no accepted characters.
+    pub fn edit(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        new: &str,
+    ) -> Option<(Range<usize>, String, usize)> {
+        let mut range = range;
+        let mut start = text[..range.start].chars().count();
+
+        // Deleting only literals would have no effect, as they are reinserted by the mask, so the deletion is
+        // extended backwards to the character before them.
+        if new.is_empty()
+            && !range.is_empty()
+            && (start..start + text[range.clone()].chars().count()).all(|i| self.is_literal(i))
+        {
+            while let Some(c) = text[..range.start].chars().next_back() {
+                range.start -= c.len_utf8();
+                start -= 1;
+                if !self.is_literal(start) {
+                    break;
+                }
+            }
+        }
+
+        let end = start + text[range.clone()].chars().count();
+
+        let (inserted, next) = self.format(start, new.chars());
+        if inserted.is_empty() && !new.is_empty() {
+            return None;
+        }
+
+        let (following, _) = self.format(next, self.unformat(end, &text[range.end..]));
+
+        // Deleting the end of the text also deletes the literals before it, which were only inserted in front of
+        // the deleted characters.
+        if new.is_empty() && following.is_empty() {
+            while start > 0 && self.is_literal(start - 1) {
+                let Some(c) = text[..range.start].chars().next_back() else {
+                    break;
+                };
+                range.start -= c.len_utf8();
+                start -= 1;
+            }
+        }
+
+        let caret = range.start + inserted.len();
+
+        Some((range.start..text.len(), inserted + &following, caret))
+    }
+
+    /// Moves a byte offset over any literals in the given direction, so that the caret does not stop after only
+    /// crossing a literal.
+    pub fn skip_literals(&self, text: &str, offset: usize, upstream: bool) -> usize {
+        let mut offset = offset;
+        let mut index = text[..offset].chars().count();
+        if upstream {
+            while index > 0 && self.is_literal(index) {
+                let Some(c) = text[..offset].chars().next_back() else {
+                    break;
+                };
+                offset -= c.len_utf8();
+                index -= 1;
+            }
+        } else {
+            while index > 0 && self.is_literal(index - 1) {
+                let Some(c) = text[offset..].chars().next() else {
+                    break;
+                };
+                offset += c.len_utf8();
+                index += 1;
+            }
+        }
+
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(mask: &InputMask, text: &str, range: Range<usize>, new: &str) -> (String, usize) {
+        let (range, replacement, caret) = mask.edit(text, range, new).unwrap();
+        let mut text = text.to_owned();
+        text.replace_range(range, &replacement);
+        (text, caret)
+    }
+
+    #[test]
+    fn literals_are_inserted_and_rejected_characters_removed() {
+        let mask = InputMask::new("####-####");
+        assert_eq!(apply(&mask, "", 0..0, "12ab345678"), (String::from("1234-5678"), 9));
+        assert_eq!(apply(&mask, "", 0..0, "1234-5"), (String::from("1234-5"), 6));
+        assert_eq!(mask.edit("1234", 4..4, "x"), None);
+
+        // Characters beyond the end of the mask are rejected.
+        assert_eq!(mask.edit("1234-5678", 9..9, "9"), None);
+    }
+
+    #[test]
+    fn following_text_is_reformatted() {
+        let mask = InputMask::new("####-####");
+        assert_eq!(apply(&mask, "1234-567", 1..1, "9"), (String::from("1923-4567"), 2));
+        assert_eq!(apply(&mask, "1234-567", 2..3, ""), (String::from("1245-67"), 2));
+
+        // Deleting a literal deletes the character before it.
+        assert_eq!(apply(&mask, "1234-567", 4..5, ""), (String::from("1235-67"), 3));
+
+        // Literals are not left at the end of the text.
+        assert_eq!(apply(&mask, "1234-5", 5..6, ""), (String::from("1234"), 4));
+    }
+
+    #[test]
+    fn deleting_from_the_end_removes_literals() {
+        let mask = InputMask::new("####-####");
+        let mut text = String::from("1234-5678");
+        for _ in 0..4 {
+            let end = text.len();
+            (text, _) = apply(&mask, &text, end - 1..end, "");
+        }
+        assert_eq!(text, "1234");
+    }
+
+    #[test]
+    fn caret_skips_literals() {
+        let mask = InputMask::new("##/##/####");
+        assert_eq!(mask.skip_literals("12/34/5678", 2, true), 1);
+        assert_eq!(mask.skip_literals("12/34/5678", 3, true), 3);
+        assert_eq!(mask.skip_literals("12/34/5678", 3, false), 4);
+        assert_eq!(mask.skip_literals("12/34/5678", 5, false), 5);
+
+        let mask = InputMask::new("(###) ###");
+        assert_eq!(mask.skip_literals("(123) 456", 5, true), 3);
+        assert_eq!(mask.skip_literals("(123) 456", 5, false), 7);
+    }
+}
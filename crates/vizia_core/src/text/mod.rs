@@ -15,3 +15,12 @@ pub use selection::*;
 
 pub mod backspace;
 pub use backspace::*;
+
+pub mod history;
+pub use history::*;
+
+pub mod mask;
+pub use mask::*;
+
+pub mod highlight;
+pub use highlight::*;
@@ -1,7 +1,9 @@
 use log::warn;
 use skia_safe::textlayout::Paragraph;
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::{EditableText, Selection};
+use crate::systems::{text_indent_offset, text_offset_from_utf16};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Direction {
@@ -83,6 +85,9 @@ pub fn apply_movement<T: EditableText>(
 
     let writing_direction = WritingDirection::LeftToRight;
 
+    // Positions in the paragraph are shifted by the text-indent placeholder, if any.
+    let indent = text_indent_offset(paragraph);
+
     let (offset, h_pos) = match m {
         Movement::Grapheme(d) if d.is_upstream_for_direction(writing_direction) => {
             if s.is_caret() || modify {
@@ -101,9 +106,9 @@ pub fn apply_movement<T: EditableText>(
             }
         }
         Movement::Vertical(VerticalMovement::LineUp) => {
-            let cluster = paragraph.get_glyph_cluster_at(s.active).unwrap();
+            let cluster = paragraph.get_glyph_cluster_at(s.active + indent).unwrap();
             let glyph_bounds = cluster.bounds;
-            let line = paragraph.get_line_number_at(s.active).unwrap();
+            let line = paragraph.get_line_number_at(s.active + indent).unwrap();
             let h_pos = s.h_pos.unwrap_or(glyph_bounds.x());
             if line == 0 {
                 (0, Some(h_pos))
@@ -116,7 +121,8 @@ pub fn apply_movement<T: EditableText>(
                     up_pos.text_range.start
                 } else {
                     up_pos.text_range.end
-                };
+                }
+                .saturating_sub(indent);
                 // if up_pos.is_inside {
                 (s, Some(h_pos))
                 // } else {
@@ -132,9 +138,9 @@ pub fn apply_movement<T: EditableText>(
             }
         }
         Movement::Vertical(VerticalMovement::LineDown) => {
-            let cluster = paragraph.get_glyph_cluster_at(s.active).unwrap();
+            let cluster = paragraph.get_glyph_cluster_at(s.active + indent).unwrap();
             let h_pos = s.h_pos.unwrap_or(cluster.bounds.x());
-            let line = paragraph.get_line_number_at(s.active).unwrap();
+            let line = paragraph.get_line_number_at(s.active + indent).unwrap();
             if line == paragraph.line_number() - 1 {
                 (text.len(), Some(h_pos))
             } else {
@@ -147,7 +153,8 @@ pub fn apply_movement<T: EditableText>(
                     down_pos.text_range.start
                 } else {
                     down_pos.text_range.end
-                };
+                }
+                .saturating_sub(indent);
                 (s.min(text.len()), Some(h_pos))
             }
         }
@@ -183,15 +190,15 @@ pub fn apply_movement<T: EditableText>(
         | Movement::Vertical(VerticalMovement::PageUp) => (s.active, s.h_pos),
 
         Movement::LineStart => {
-            let line = paragraph.get_line_number_at(s.active).unwrap();
+            let line = paragraph.get_line_number_at(s.active + indent).unwrap();
             let lm = paragraph.get_line_metrics_at(line).unwrap();
-            (lm.start_index, None)
+            (text_offset_from_utf16(paragraph, text, lm.start_index), None)
         }
 
         Movement::LineEnd => {
-            let line = paragraph.get_line_number_at(s.active).unwrap();
+            let line = paragraph.get_line_number_at(s.active + indent).unwrap();
             let lm = paragraph.get_line_metrics_at(line).unwrap();
-            (lm.end_index - 1, None)
+            (text_offset_from_utf16(paragraph, text, lm.end_index - 1), None)
         }
 
         other => {
@@ -203,3 +210,146 @@ pub fn apply_movement<T: EditableText>(
     let start = if modify { s.anchor } else { offset };
     Selection::new(start, offset).with_h_pos(h_pos)
 }
+
+/// Compute the result of a [`Movement`] on a [`Selection`] of text which is laid out with every grapheme replaced by
+/// a mask character, such as the hidden text of a secure textbox.
+///
+/// The movement is applied to the masked text of the paragraph, and the resulting offsets are mapped back onto the
+/// text by grapheme index.
+pub fn apply_masked_movement(
+    m: Movement,
+    s: Selection,
+    text: &str,
+    mask: char,
+    paragraph: &Paragraph,
+    modify: bool,
+) -> Selection {
+    let masked = mask.to_string().repeat(text.graphemes(true).count());
+
+    let to_masked = |offset: usize| text[..offset].graphemes(true).count() * mask.len_utf8();
+    let from_masked = |offset: usize| {
+        text.grapheme_indices(true).nth(offset / mask.len_utf8()).map_or(text.len(), |(i, _)| i)
+    };
+
+    let masked_selection =
+        Selection::new(to_masked(s.anchor), to_masked(s.active)).with_h_pos(s.h_pos);
+    let moved = apply_movement(m, masked_selection, &masked, paragraph, modify);
+
+    Selection::new(from_masked(moved.anchor), from_masked(moved.active)).with_h_pos(moved.h_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use skia_safe::textlayout::{
+        FontCollection, ParagraphBuilder, ParagraphStyle, PlaceholderAlignment, PlaceholderStyle,
+        TextBaseline, TextStyle,
+    };
+    use skia_safe::FontMgr;
+
+    use super::*;
+    use crate::systems::{paragraph_position, text_offset};
+
+    // Lays out text in the same way as the text system, which ends every paragraph with a zero width space.
+    fn layout(text: &str) -> Paragraph {
+        layout_indented(text, 0.0)
+    }
+
+    // Lays out text after a text-indent placeholder of the given width, if it is not zero.
+    fn layout_indented(text: &str, indent: f32) -> Paragraph {
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::default(), None);
+
+        let mut builder = ParagraphBuilder::new(&ParagraphStyle::new(), font_collection);
+        if indent != 0.0 {
+            builder.add_placeholder(&PlaceholderStyle::new(
+                indent,
+                0.0,
+                PlaceholderAlignment::Baseline,
+                TextBaseline::Alphabetic,
+                0.0,
+            ));
+        }
+        let mut text_style = TextStyle::new();
+        text_style.set_font_size(16.0);
+        builder.push_style(&text_style);
+        builder.add_text(text);
+        builder.add_text("\u{200B}");
+
+        let mut paragraph = builder.build();
+        paragraph.layout(1000.0);
+        paragraph
+    }
+
+    #[test]
+    fn line_start_and_end_of_non_ascii_text() {
+        let text = String::from("héllo wörld");
+        let paragraph = layout(&text);
+
+        let end = apply_movement(Movement::LineEnd, Selection::caret(0), &text, &paragraph, false);
+        assert_eq!(end.active, text.len());
+
+        let start = apply_movement(Movement::LineStart, end, &text, &paragraph, false);
+        assert_eq!(start.active, 0);
+    }
+
+    #[test]
+    fn masked_movements_map_back_onto_the_text() {
+        // A secure textbox lays out one mask character per grapheme of its text.
+        let text = "abc";
+        let paragraph = layout("•••");
+
+        let end = apply_masked_movement(
+            Movement::LineEnd,
+            Selection::caret(0),
+            text,
+            '•',
+            &paragraph,
+            false,
+        );
+        assert_eq!(end.active, text.len());
+
+        let start = apply_masked_movement(Movement::LineStart, end, text, '•', &paragraph, false);
+        assert_eq!(start.active, 0);
+
+        // Offsets are mapped by grapheme, so multi-byte characters are not split.
+        let text = "é👍x";
+        let paragraph = layout("•••");
+        let selection = Selection::caret(text.len());
+        let left = apply_masked_movement(
+            Movement::Grapheme(Direction::Left),
+            selection,
+            text,
+            '•',
+            &paragraph,
+            false,
+        );
+        assert_eq!(left.active, text.len() - 1);
+
+        let start =
+            apply_masked_movement(Movement::LineStart, selection, text, '•', &paragraph, false);
+        assert_eq!(start.active, 0);
+    }
+
+    #[test]
+    fn text_indent_offsets_are_bytes_of_the_text() {
+        let text = String::from("héllo wörld");
+
+        for indent in [20.0, -20.0] {
+            let paragraph = layout_indented(&text, indent);
+            assert_eq!(text_indent_offset(&paragraph), '\u{FFFC}'.len_utf8());
+
+            let end =
+                apply_movement(Movement::LineEnd, Selection::caret(0), &text, &paragraph, false);
+            assert_eq!(end.active, text.len());
+
+            let start = apply_movement(Movement::LineStart, end, &text, &paragraph, false);
+            assert_eq!(start.active, 0);
+
+            // Positions of the paragraph map back onto the same byte offsets.
+            for offset in [0, 3, text.len()] {
+                let position = paragraph_position(&paragraph, &text, offset);
+                assert_eq!(text_offset(&paragraph, &text, position), offset);
+            }
+        }
+    }
+}
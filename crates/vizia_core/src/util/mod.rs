@@ -4,6 +4,11 @@ use std::path::{Path, PathBuf};
 pub trait IntoCssStr: 'static {
     /// Returns a string containing CSS.
     fn get_style(&self) -> Result<String, std::io::Error>;
+
+    /// Returns the path of the file the CSS is read from, if any.
+    fn path(&self) -> Option<&Path> {
+        None
+    }
 }
 
 impl IntoCssStr for CSS {
@@ -14,6 +19,14 @@ impl IntoCssStr for CSS {
             CSS::String(style_string) => Ok(style_string.to_owned()),
         }
     }
+
+    fn path(&self) -> Option<&Path> {
+        match self {
+            CSS::Path(path) => Some(path),
+
+            CSS::String(_) => None,
+        }
+    }
 }
 
 impl IntoCssStr for &'static str {
@@ -26,12 +39,20 @@ impl IntoCssStr for PathBuf {
     fn get_style(&self) -> Result<String, std::io::Error> {
         std::fs::read_to_string(self)
     }
+
+    fn path(&self) -> Option<&Path> {
+        Some(self)
+    }
 }
 
 impl IntoCssStr for Path {
     fn get_style(&self) -> Result<String, std::io::Error> {
         std::fs::read_to_string(self)
     }
+
+    fn path(&self) -> Option<&Path> {
+        Some(self)
+    }
 }
 
 #[doc(hidden)]
@@ -125,6 +125,8 @@ pub trait View: 'static + Sized {
             cx.style.element.insert(id, fxhash::hash32(element));
         }
 
+        cx.add_view_stylesheet::<Self>(self.element());
+
         cx.views.insert(id, Box::new(self));
 
         let parent_id = cx.tree.get_layout_parent(id).unwrap();
@@ -189,6 +191,51 @@ pub trait View: 'static + Sized {
         None
     }
 
+    /// Specifies a stylesheet for the view type, which is added to the application the first time a view of the type
+    /// is built.
+    ///
+    /// The stylesheet is scoped to the element name of the view. Declarations at the top level of the stylesheet apply
+    /// to the view itself, `&` refers to the view, and other selectors match its descendants. Stylesheets added with
+    /// [`add_stylesheet`](Context::add_stylesheet) take precedence, so users of the view can still
+    /// override its style.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// pub struct CustomView{}
+    ///
+    /// impl CustomView {
+    ///     pub fn new(cx: &mut Context) -> Handle<Self> {
+    ///         Self{}.build(cx, |cx| {
+    ///             Label::new(cx, "Hello");
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// impl View for CustomView {
+    ///     fn element(&self) -> Option<&'static str> {
+    ///         Some("custom_view")
+    ///     }
+    ///
+    ///     fn stylesheet() -> Option<&'static str> {
+    ///         Some(r#"
+    ///             background-color: red;
+    ///
+    ///             &:hover {
+    ///                 background-color: blue;
+    ///             }
+    ///
+    ///             label {
+    ///                 color: white;
+    ///             }
+    ///         "#)
+    ///     }
+    /// }
+    /// ```
+    fn stylesheet() -> Option<&'static str> {
+        None
+    }
+
     /// Handles any events received by the view.
     ///
     /// # Example
@@ -0,0 +1,1300 @@
+use std::cell::Cell;
+use std::ops::Range;
+
+use skia_safe::textlayout::{
+    Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle, RectWidthStyle, TextStyle,
+};
+use skia_safe::{ClipOp, Paint, PaintStyle, Rect};
+
+use crate::prelude::*;
+use crate::systems::{paragraph_position, text_indent_offset, text_offset};
+use crate::text::{
+    apply_movement, highlight_lines, offset_for_delete_backwards, Direction, EditHistory, EditKind,
+    HighlightedLine, Movement, Selection, VerticalMovement,
+};
+pub use crate::text::{BasicTokenizer, Token, TokenKind, Tokenizer};
+use crate::views::scrollview::SCROLL_SENSITIVITY;
+
+/// Events for editing a [`CodeEditor`].
+pub enum CodeEditorEvent {
+    /// Insert text at the caret, replacing the selected text.
+    InsertText(String),
+    /// Insert a new line at the caret, keeping the indentation of the current line.
+    NewLine,
+    /// Delete a section of text, determined by the `Movement`.
+    DeleteText(Movement),
+    /// Move the caret, extending the selection if the flag is true.
+    MoveCursor(Movement, bool),
+    /// Select all text.
+    SelectAll,
+    /// Indent the selected lines, or insert spaces up to the next tab stop if the selection is within one line.
+    Indent,
+    /// Remove one level of indentation from the selected lines.
+    Outdent,
+    /// Undo the most recent edit.
+    Undo,
+    /// Redo the most recently undone edit.
+    Redo,
+    /// Specify the 'hit' position of the mouse cursor.
+    Hit(f32, f32, bool),
+    /// Specify the 'drag' position of the mouse cursor.
+    Drag(f32, f32),
+    /// Scroll the text vertically by the given number of lines.
+    Scroll(f32),
+    /// Copy the selected text to the clipboard.
+    Copy,
+    /// Paste the text in the clipboard at the caret.
+    Paste,
+    /// Cut the selected text and place it in the clipboard.
+    Cut,
+}
+
+/// A view for editing source code, with syntax highlighting and a gutter of line numbers.
+///
+/// The text is split into styled tokens by a [`Tokenizer`], such as the [`BasicTokenizer`], and each token is
+/// styled with the class name of its [`TokenKind`]. Only the lines which change are tokenized again after an edit.
+///
+/// While editing, the tab key indents the selected lines rather than moving focus to the next view. Pressing escape
+/// stops editing, so that the tab key moves focus as usual, and pressing enter starts editing again.
+///
+/// # Example
+/// ```rust
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     script: String,
+/// # }
+/// #
+/// # enum AppEvent {
+/// #     SetScript(String),
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { script: String::new() }.build(cx);
+/// #
+/// CodeEditor::new(cx, AppData::script)
+///     .tokenizer(BasicTokenizer::rust())
+///     .on_edit(|cx, text| cx.emit(AppEvent::SetScript(text)));
+/// ```
+#[derive(Lens)]
+pub struct CodeEditor<L: Lens> {
+    lens: L,
+    #[lens(ignore)]
+    lines: Vec<HighlightedLine>,
+    // Changes to the lines which have not yet been applied to the views of the lines.
+    #[lens(ignore)]
+    splices: Vec<LineSplice>,
+    // The views of the lines, in order. Each holds the spans of one line.
+    #[lens(ignore)]
+    line_views: Vec<Entity>,
+    // Incremented whenever the lines change, so that the views of the changed lines are rebuilt.
+    revision: usize,
+    #[lens(ignore)]
+    text: String,
+    #[lens(ignore)]
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    #[lens(ignore)]
+    selection: Selection,
+    #[lens(ignore)]
+    history: EditHistory,
+    #[lens(ignore)]
+    tab_size: usize,
+    edit: bool,
+    // The vertical scroll offset of the text in physical pixels. The paragraph of the text is only up to date when
+    // drawing, so the offset is clamped and moved to show the caret while drawing.
+    #[lens(ignore)]
+    scroll: Cell<f32>,
+    #[lens(ignore)]
+    follow_caret: Cell<bool>,
+    on_edit: Option<Box<dyn Fn(&mut EventContext, String) + Send + Sync>>,
+}
+
+impl<L> CodeEditor<L>
+where
+    L: Lens<Target = String>,
+{
+    /// Creates a new code editor for the text of the given lens.
+    pub fn new(cx: &mut Context, lens: L) -> Handle<Self> {
+        Self {
+            lens,
+            lines: Vec::new(),
+            splices: Vec::new(),
+            line_views: Vec::new(),
+            revision: 0,
+            text: String::new(),
+            tokenizer: None,
+            selection: Selection::caret(0),
+            history: EditHistory::new(),
+            tab_size: 4,
+            edit: false,
+            scroll: Cell::new(0.0),
+            follow_caret: Cell::new(false),
+            on_edit: None,
+        }
+        .build(cx, |cx| {
+            let editor = cx.current();
+
+            // While editing, the tab key indents the text instead of moving focus, so it is handled before the
+            // focus is moved to the next view. Listeners see key events before they are targeted at the focused
+            // view, so the focus is checked directly.
+            cx.add_listener(|editor: &mut Self, cx, event| {
+                event.map(|window_event, meta| {
+                    if let WindowEvent::KeyDown(Code::Tab, _) = window_event {
+                        if editor.edit && cx.focused() == cx.current() && !cx.is_read_only() {
+                            if cx.modifiers.shift() {
+                                cx.emit(CodeEditorEvent::Outdent);
+                            } else {
+                                cx.emit(CodeEditorEvent::Indent);
+                            }
+                            meta.consume();
+                        }
+                    }
+                });
+            });
+
+            // Each line is built as a separate view, so that only the views of the lines which change are rebuilt
+            // and the views of the lines after them are kept as they are.
+            Binding::new(cx, Self::revision, move |cx, _| {
+                let Some(splices) = cx
+                    .views
+                    .get_mut(&editor)
+                    .and_then(|view| view.downcast_mut::<Self>())
+                    .map(|editor| std::mem::take(&mut editor.splices))
+                else {
+                    return;
+                };
+
+                let binding = cx.current();
+                for splice in splices {
+                    apply_splice::<L>(cx, editor, binding, splice);
+                }
+
+                cx.style.needs_text_update(editor);
+            });
+        })
+        .text("")
+        .navigable(true)
+        .role(Role::TextInput)
+        .bind(lens, |handle, lens| {
+            let text = lens.get(&handle);
+            handle.modify(|editor: &mut Self| {
+                // Edits are submitted to the bound data by the `on_edit` callback, so the history is only reset when
+                // the data changes to something other than the edited text.
+                if editor.text != text {
+                    let len = text.len();
+                    editor.selection = Selection::new(
+                        editor.selection.anchor.min(len),
+                        editor.selection.active.min(len),
+                    );
+                    editor.text = text;
+                    editor.history.clear();
+                    editor.highlight();
+                }
+            });
+        })
+    }
+}
+
+// A change to a range of lines, where the lines at `start..start + removed` are replaced with `lines`.
+struct LineSplice {
+    start: usize,
+    removed: usize,
+    lines: Vec<HighlightedLine>,
+}
+
+// Returns the range of lines whose views need to be rebuilt after the lines in the given range were tokenized,
+// along with the number of previous lines they replace, or `None` if no lines changed.
+fn splice_range(
+    range: Range<usize>,
+    previous_len: usize,
+    len: usize,
+) -> Option<(Range<usize>, usize)> {
+    // The lines after the range are unchanged, so the number of lines the range replaces is found from the change in
+    // the number of lines.
+    let start = range.start;
+    let mut end = range.end;
+    let mut removed = range.end + previous_len - len - range.start;
+
+    // Only lines after the first start with a line break, so the first unchanged line is rebuilt if it becomes the
+    // first line or stops being the first line.
+    if start == 0 && end < len && (end == 0) != (removed == 0) {
+        end += 1;
+        removed += 1;
+    }
+
+    (start != end || removed != 0).then_some((start..end, removed))
+}
+
+// The view of one line of a code editor, which is ignored by layout so that its spans are part of the text of the
+// editor.
+struct CodeLine;
+
+impl View for CodeLine {}
+
+// Replaces the views of a range of lines, moving the views of the new lines into place after the view of the
+// previous line, or after the binding which builds them if the lines are at the start of the text.
+fn apply_splice<L: Lens>(cx: &mut Context, editor: Entity, binding: Entity, splice: LineSplice) {
+    let Some(mut views) = cx
+        .views
+        .get_mut(&editor)
+        .and_then(|view| view.downcast_mut::<CodeEditor<L>>())
+        .map(|editor| std::mem::take(&mut editor.line_views))
+    else {
+        return;
+    };
+
+    let end = (splice.start + splice.removed).min(views.len());
+    let start = splice.start.min(end);
+
+    let mut previous = if start > 0 { views[start - 1] } else { binding };
+    let mut new_views = Vec::with_capacity(splice.lines.len());
+    for (offset, line) in splice.lines.iter().enumerate() {
+        let index = start + offset;
+        let view = cx.with_current(editor, |cx| {
+            CodeLine.build(cx, |cx| build_line(cx, index, line)).ignore().entity()
+        });
+
+        cx.tree.set_next_sibling(previous, view).ok();
+        previous = view;
+        new_views.push(view);
+    }
+
+    for view in views.splice(start..end, new_views) {
+        cx.remove(view);
+    }
+
+    if let Some(editor) =
+        cx.views.get_mut(&editor).and_then(|view| view.downcast_mut::<CodeEditor<L>>())
+    {
+        editor.line_views = views;
+    }
+}
+
+// Builds the spans of a line, starting with the line break which separates it from the previous line.
+fn build_line(cx: &mut Context, index: usize, line: &HighlightedLine) {
+    if index > 0 {
+        TextSpan::new(cx, "\n", |_| {});
+    }
+
+    let text = line.text();
+    let mut offset = 0;
+    for token in line.tokens() {
+        if token.range.start > offset {
+            TextSpan::new(cx, &text[offset..token.range.start], |_| {});
+        }
+
+        TextSpan::new(cx, &text[token.range.clone()], |_| {}).class(token.kind.class_name());
+        offset = token.range.end;
+    }
+
+    if offset < text.len() {
+        TextSpan::new(cx, &text[offset..], |_| {});
+    }
+}
+
+impl<L: Lens> CodeEditor<L> {
+    fn highlight(&mut self) {
+        let removed = self.lines.len();
+        let range = highlight_lines(&mut self.lines, &self.text, self.tokenizer.as_deref());
+        self.splice_lines(range, removed);
+    }
+
+    // Tokenizes every line again, such as after the tokenizer changes.
+    fn highlight_all(&mut self) {
+        let removed = self.lines.len();
+        self.lines.clear();
+        highlight_lines(&mut self.lines, &self.text, self.tokenizer.as_deref());
+        self.splice_lines(0..self.lines.len(), removed);
+    }
+
+    // Records that the lines in the given range were tokenized, given the number of lines before they changed, so
+    // that the views of the lines are rebuilt.
+    fn splice_lines(&mut self, range: Range<usize>, previous_len: usize) {
+        if let Some((range, removed)) = splice_range(range, previous_len, self.lines.len()) {
+            let lines = self.lines[range.clone()].to_vec();
+            self.splices.push(LineSplice { start: range.start, removed, lines });
+            self.revision = self.revision.wrapping_add(1);
+        }
+    }
+
+    fn edited(&mut self, cx: &mut EventContext) {
+        self.highlight();
+        self.follow_caret.set(true);
+        if let Some(callback) = &self.on_edit {
+            (callback)(cx, self.text.clone());
+        }
+    }
+
+    /// Replaces a range of the text, records the edit in the history and sets the selection after the edit.
+    fn replace_range(
+        &mut self,
+        cx: &mut EventContext,
+        range: Range<usize>,
+        new: &str,
+        selection: Selection,
+        kind: EditKind,
+    ) {
+        self.history.edit(&mut self.text, range, new, self.selection, selection, kind);
+        self.selection = selection;
+        self.edited(cx);
+    }
+
+    fn insert_text(&mut self, cx: &mut EventContext, text: &str) {
+        // Consecutive characters are grouped into one history entry, while pasted text is not.
+        let kind = if text.chars().count() == 1 { EditKind::Typing } else { EditKind::Other };
+        let range = self.selection.range();
+        let caret = Selection::caret(range.start + text.len());
+        self.replace_range(cx, range, text, caret, kind);
+    }
+
+    fn new_line(&mut self, cx: &mut EventContext) {
+        let range = self.selection.range();
+        let (new, caret) = new_line_text(&self.text, range.clone(), self.tab_size);
+        let caret = Selection::caret(range.start + caret);
+        self.replace_range(cx, range, &new, caret, EditKind::Other);
+    }
+
+    fn indent(&mut self, cx: &mut EventContext) {
+        let range = self.selection.range();
+        if self.text[range.clone()].contains('\n') {
+            self.indent_lines(cx, false);
+        } else {
+            let spaces = " ".repeat(spaces_to_tab_stop(&self.text, range.start, self.tab_size));
+            let caret = Selection::caret(range.start + spaces.len());
+            self.replace_range(cx, range, &spaces, caret, EditKind::Other);
+        }
+    }
+
+    /// Adds or removes one level of indentation at the start of each line touched by the selection.
+    fn indent_lines(&mut self, cx: &mut EventContext, outdent: bool) {
+        if let Some((range, new, selection)) =
+            indent_lines_edit(&self.text, self.selection, self.tab_size, outdent)
+        {
+            self.replace_range(cx, range, &new, selection, EditKind::Other);
+        }
+    }
+
+    fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        let range = if !self.selection.is_caret() {
+            self.selection.range()
+        } else if movement == Movement::Grapheme(Direction::Upstream) {
+            let caret = self.selection.active;
+            let start = indentation_deletion_start(&self.text, caret, self.tab_size)
+                .unwrap_or_else(|| offset_for_delete_backwards(&self.selection, &self.text));
+            start..caret
+        } else if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            apply_movement(movement, self.selection, &self.text, paragraph, true).range()
+        } else {
+            return;
+        };
+
+        if range.is_empty() {
+            return;
+        }
+
+        let kind = if self.selection.is_caret() { EditKind::Deleting } else { EditKind::Other };
+        let caret = Selection::caret(range.start);
+        self.replace_range(cx, range, "", caret, kind);
+    }
+
+    fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        self.history.break_group();
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            self.selection =
+                apply_movement(movement, self.selection, &self.text, paragraph, selection);
+            self.follow_caret.set(true);
+            cx.needs_redraw();
+        }
+    }
+
+    fn undo(&mut self, cx: &mut EventContext) {
+        if let Some(selection) = self.history.undo(&mut self.text) {
+            self.selection = selection;
+            self.edited(cx);
+        }
+    }
+
+    fn redo(&mut self, cx: &mut EventContext) {
+        if let Some(selection) = self.history.redo(&mut self.text) {
+            self.selection = selection;
+            self.edited(cx);
+        }
+    }
+
+    fn scroll(&mut self, cx: &mut EventContext, lines: f32) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let bounds = cx.bounds();
+            let logical_height = cx.physical_to_logical(bounds.h);
+            let padding_top = cx.style.padding_top.get(cx.current).copied().unwrap_or_default();
+            let padding_bottom =
+                cx.style.padding_bottom.get(cx.current).copied().unwrap_or_default();
+            let view_height = bounds.h
+                - (padding_top.to_px(logical_height, 0.0)
+                    + padding_bottom.to_px(logical_height, 0.0))
+                    * cx.scale_factor();
+
+            let max = (paragraph.height() - view_height).max(0.0);
+            let scroll = self.scroll.get() - lines * SCROLL_SENSITIVITY * cx.scale_factor();
+            self.scroll.set(scroll.clamp(0.0, max));
+            cx.needs_redraw();
+        }
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let bounds = cx.bounds();
+            let logical_width = cx.physical_to_logical(bounds.w);
+            let logical_height = cx.physical_to_logical(bounds.h);
+            let padding_left = cx.style.padding_left.get(cx.current).copied().unwrap_or_default();
+            let padding_top = cx.style.padding_top.get(cx.current).copied().unwrap_or_default();
+            let x = x - bounds.x - padding_left.to_px(logical_width, 0.0) * cx.scale_factor();
+            let y = y - bounds.y - padding_top.to_px(logical_height, 0.0) * cx.scale_factor()
+                + self.scroll.get();
+
+            let gp = paragraph.get_glyph_position_at_coordinate((x, y));
+            let cursor = text_offset(paragraph, &self.text, gp.position as usize);
+
+            if selection {
+                self.selection.active = cursor;
+            } else {
+                self.selection = Selection::caret(cursor);
+            }
+
+            self.history.break_group();
+            self.follow_caret.set(true);
+            cx.needs_redraw();
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn selected_text(&self) -> String {
+        self.text[self.selection.range()].to_owned()
+    }
+
+    // Returns the rectangle of the caret, relative to the paragraph of the text.
+    fn caret_rect(&self, paragraph: &Paragraph) -> Option<Rect> {
+        let current = paragraph_position(paragraph, &self.text, self.selection.active);
+        paragraph
+            .get_rects_for_range(
+                current..current + 1,
+                RectHeightStyle::Tight,
+                RectWidthStyle::Tight,
+            )
+            .first()
+            .map(|rect| rect.rect)
+    }
+
+    // Returns the offset of the text from the bounds of the editor when drawing.
+    fn draw_offset(cx: &DrawContext) -> (f32, f32) {
+        let padding_left = match cx.padding_left() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        let padding_top = match cx.padding_top() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        (padding_left, padding_top)
+    }
+
+    // Clamps the scroll offset to the height of the text, and moves it to show the caret if it has moved.
+    fn update_scroll(&self, cx: &mut DrawContext) {
+        let bounds = cx.bounds();
+        let padding_top = match cx.padding_top() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+        let padding_bottom = match cx.padding_bottom() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+        let view_height = (bounds.h - padding_top - padding_bottom).max(0.0);
+
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let mut scroll = self.scroll.get();
+            if self.follow_caret.replace(false) {
+                if let Some(caret) = self.caret_rect(paragraph) {
+                    if caret.top < scroll {
+                        scroll = caret.top;
+                    } else if caret.bottom > scroll + view_height {
+                        scroll = caret.bottom - view_height;
+                    }
+                }
+            }
+
+            let max = (paragraph.height() - view_height).max(0.0);
+            self.scroll.set(scroll.clamp(0.0, max));
+        }
+    }
+
+    fn draw_current_line(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let Some(caret) = self.caret_rect(paragraph) else {
+                return;
+            };
+
+            let bounds = cx.bounds();
+            let (_, dy) = Self::draw_offset(cx);
+            let y = bounds.y + dy + caret.top;
+
+            let color = cx.font_color();
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(Color::rgba(color.r(), color.g(), color.b(), 16));
+
+            canvas.draw_rect(Rect::new(bounds.x, y, bounds.right(), y + caret.height()), &paint);
+        }
+    }
+
+    // Draws the selection and returns the rectangles it covers.
+    fn draw_selection(&self, cx: &mut DrawContext, canvas: &Canvas) -> Vec<Rect> {
+        if self.selection.is_caret() {
+            return Vec::new();
+        }
+
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return Vec::new();
+        };
+
+        let min = paragraph_position(paragraph, &self.text, self.selection.min());
+        let max = paragraph_position(paragraph, &self.text, self.selection.max());
+
+        let rects =
+            paragraph.get_rects_for_range(min..max, RectHeightStyle::Tight, RectWidthStyle::Tight);
+
+        let bounds = cx.bounds();
+        let (dx, dy) = Self::draw_offset(cx);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(cx.selection_color());
+
+        rects
+            .iter()
+            .map(|rect| {
+                let x = bounds.x + dx + rect.rect.left;
+                let y = bounds.y + dy + rect.rect.top;
+                let rect = Rect::new(x, y, x + rect.rect.width(), y + rect.rect.height());
+                canvas.draw_rect(rect, &paint);
+                rect
+            })
+            .collect()
+    }
+
+    fn draw_matching_brackets(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some((first, second)) = matching_brackets(&self.text, self.selection.active) else {
+            return;
+        };
+
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let bounds = cx.bounds();
+            let (dx, dy) = Self::draw_offset(cx);
+
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Stroke);
+            paint.set_stroke_width(1.0);
+            paint.set_color(cx.caret_color());
+
+            for offset in [first, second] {
+                let current = paragraph_position(paragraph, &self.text, offset);
+                let rects = paragraph.get_rects_for_range(
+                    current..current + 1,
+                    RectHeightStyle::Tight,
+                    RectWidthStyle::Tight,
+                );
+
+                for rect in rects {
+                    let x = bounds.x + dx + rect.rect.left;
+                    let y = bounds.y + dy + rect.rect.top;
+                    canvas.draw_rect(
+                        Rect::new(x, y, x + rect.rect.width(), y + rect.rect.height()),
+                        &paint,
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let Some(caret) = self.caret_rect(paragraph) else {
+                return;
+            };
+
+            let bounds = cx.bounds();
+            let (dx, dy) = Self::draw_offset(cx);
+
+            let x = (bounds.x + dx + caret.left).round();
+            let y = (bounds.y + dy + caret.top).round();
+
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(cx.caret_color());
+
+            canvas.draw_rect(Rect::new(x, y, x + 1.0, y + caret.height()), &paint);
+        }
+    }
+
+    /// Draws the number of each visible line within the left padding of the editor.
+    fn draw_gutter(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return;
+        };
+
+        let bounds = cx.bounds();
+        let (dx, dy) = Self::draw_offset(cx);
+        // The numbers are right aligned, leaving a space of one em between them and the text.
+        let width = dx - cx.font_size();
+        if width <= 0.0 {
+            return;
+        }
+
+        let color = cx.font_color();
+        let mut text_style = TextStyle::new();
+        text_style.set_font_families(
+            cx.style
+                .font_family
+                .get(cx.current)
+                .map(Vec::as_slice)
+                .unwrap_or(&[FamilyOwned::Generic(GenericFontFamily::SansSerif)]),
+        );
+        text_style.set_font_size(cx.font_size());
+        text_style.set_color(Color::rgba(color.r(), color.g(), color.b(), 128));
+
+        let mut paragraph_style = ParagraphStyle::default();
+        paragraph_style.set_text_align(TextAlign::Right.into());
+
+        let indent = text_indent_offset(paragraph);
+        let scroll = self.scroll.get();
+        let mut offset = 0;
+        for (index, line) in self.text.split('\n').enumerate() {
+            let line_offset = offset;
+            offset += line.len() + 1;
+
+            let Some(metrics) = paragraph
+                .get_line_number_at(line_offset + indent)
+                .and_then(|line_number| paragraph.get_line_metrics_at(line_number))
+            else {
+                continue;
+            };
+
+            let top = (metrics.baseline - metrics.ascent) as f32;
+            if top > scroll + bounds.h - dy {
+                break;
+            }
+
+            if top + (metrics.height as f32) < scroll - dy {
+                continue;
+            }
+
+            let mut builder =
+                ParagraphBuilder::new(&paragraph_style, &cx.text_context.font_collection);
+            builder.push_style(&text_style);
+            builder.add_text((index + 1).to_string());
+            let mut number = builder.build();
+            number.layout(width);
+            number.paint(canvas, (bounds.x, bounds.y + dy + top));
+        }
+    }
+}
+
+// Returns the closing bracket for an opening bracket.
+fn closing_bracket(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+// Returns the byte offset of the start of the line containing the given offset.
+fn line_start(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |index| index + 1)
+}
+
+/// Returns the text which replaces a range to start a new line, along with the byte offset of the caret within it.
+///
+/// The new line keeps the indentation of the current line. A new line after an opening bracket is indented by one
+/// more level, and a closing bracket directly after the range is moved onto a line of its own.
+fn new_line_text(text: &str, range: Range<usize>, tab_size: usize) -> (String, usize) {
+    let indentation = text[line_start(text, range.start)..range.start]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect::<String>();
+
+    let mut new = format!("\n{}", indentation);
+    let mut caret = None;
+
+    let before = text[..range.start].chars().next_back();
+    if let Some(open) = before.filter(|c| matches!(c, '(' | '[' | '{')) {
+        new.push_str(&" ".repeat(tab_size));
+        if text[range.end..].chars().next() == closing_bracket(open) {
+            caret = Some(new.len());
+            new.push('\n');
+            new.push_str(&indentation);
+        }
+    }
+
+    let caret = caret.unwrap_or(new.len());
+    (new, caret)
+}
+
+// Returns the number of spaces from an offset to the next tab stop of its line.
+fn spaces_to_tab_stop(text: &str, offset: usize, tab_size: usize) -> usize {
+    let column = text[line_start(text, offset)..offset].chars().count();
+    tab_size - column % tab_size
+}
+
+/// Returns the edit which adds or removes one level of indentation at the start of each line touched by a
+/// selection, as the byte range to replace, the replacement text and the selection after the edit.
+///
+/// Returns `None` if the edit would not change the text.
+fn indent_lines_edit(
+    text: &str,
+    selection: Selection,
+    tab_size: usize,
+    outdent: bool,
+) -> Option<(Range<usize>, String, Selection)> {
+    let range = selection.range();
+    let start = line_start(text, range.start);
+    let end = text[range.end..].find('\n').map_or(text.len(), |index| range.end + index);
+    let indentation = " ".repeat(tab_size);
+
+    let mut new = String::new();
+    // The old start, removed length, inserted length and new start of each line.
+    let mut lines = Vec::new();
+    let mut line_start = start;
+    for line in text[start..end].split('\n') {
+        if line_start > start {
+            new.push('\n');
+        }
+
+        let (removed, inserted) = if !outdent {
+            (0, indentation.as_str())
+        } else if line.starts_with('\t') {
+            (1, "")
+        } else {
+            (line.bytes().take(tab_size).take_while(|byte| *byte == b' ').count(), "")
+        };
+
+        lines.push((line_start, removed, inserted.len(), start + new.len()));
+        new.push_str(inserted);
+        new.push_str(&line[removed..]);
+        line_start += line.len() + 1;
+    }
+
+    if new == text[start..end] {
+        return None;
+    }
+
+    // The selection is moved along with the text of the lines.
+    let map = |offset: usize| {
+        lines.iter().rev().find(|(line_start, ..)| *line_start <= offset).map_or(
+            offset,
+            |(line_start, removed, inserted, new_start)| {
+                new_start + inserted + (offset - line_start).saturating_sub(*removed)
+            },
+        )
+    };
+    let selection = Selection::new(map(selection.anchor), map(selection.active));
+
+    Some((start..end, new, selection))
+}
+
+// Returns the byte offset to delete back to from a caret within the indentation of a line, which removes spaces back
+// to the previous tab stop, or `None` if the caret is not within the indentation.
+fn indentation_deletion_start(text: &str, caret: usize, tab_size: usize) -> Option<usize> {
+    let leading = &text[line_start(text, caret)..caret];
+    if leading.is_empty() || !leading.bytes().all(|byte| byte == b' ') {
+        return None;
+    }
+
+    Some(caret - ((leading.len() - 1) % tab_size + 1))
+}
+
+/// Returns the byte offsets of the bracket next to the caret and the bracket which matches it, preferring the
+/// bracket after the caret.
+fn matching_brackets(text: &str, caret: usize) -> Option<(usize, usize)> {
+    let after = text[caret..].chars().next().map(|c| (caret, c));
+    let before = text[..caret].chars().next_back().map(|c| (caret - c.len_utf8(), c));
+
+    [after, before]
+        .into_iter()
+        .flatten()
+        .find_map(|(offset, c)| matching_bracket(text, offset, c).map(|other| (offset, other)))
+}
+
+// Returns the byte offset of the bracket which matches the bracket at the given offset.
+fn matching_bracket(text: &str, offset: usize, bracket: char) -> Option<usize> {
+    let (open, close, forwards) = match bracket {
+        '(' | '[' | '{' => (bracket, closing_bracket(bracket)?, true),
+        ')' => ('(', ')', false),
+        ']' => ('[', ']', false),
+        '}' => ('{', '}', false),
+        _ => return None,
+    };
+
+    // Scanning backwards, closing brackets open a level instead.
+    let (inner, outer) = if forwards { (open, close) } else { (close, open) };
+    let mut depth = 0;
+    let mut visit = |index: usize, c: char| {
+        if c == inner {
+            depth += 1;
+        } else if c == outer {
+            depth -= 1;
+        }
+        (depth == 0).then_some(index)
+    };
+
+    if forwards {
+        text[offset..].char_indices().find_map(|(index, c)| visit(offset + index, c))
+    } else {
+        text[..=offset].char_indices().rev().find_map(|(index, c)| visit(index, c))
+    }
+}
+
+impl<L: Lens> Handle<'_, CodeEditor<L>> {
+    /// Sets the callback triggered when the text is edited, which provides the edited text.
+    pub fn on_edit<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, String) + Send + Sync,
+    {
+        self.modify(|editor: &mut CodeEditor<L>| editor.on_edit = Some(Box::new(callback)))
+    }
+
+    /// Sets the tokenizer used to highlight the text.
+    pub fn tokenizer(self, tokenizer: impl Tokenizer + 'static) -> Self {
+        self.modify(|editor: &mut CodeEditor<L>| {
+            editor.tokenizer = Some(Box::new(tokenizer));
+            editor.highlight_all();
+        })
+    }
+
+    /// Sets the number of spaces in one level of indentation. Defaults to 4.
+    pub fn tab_size(self, tab_size: usize) -> Self {
+        self.modify(|editor: &mut CodeEditor<L>| editor.tab_size = tab_size.max(1))
+    }
+}
+
+impl<L: Lens> View for CodeEditor<L> {
+    fn element(&self) -> Option<&'static str> {
+        Some("code-editor")
+    }
+
+    fn stylesheet() -> Option<&'static str> {
+        Some(
+            r#"
+            width: 1s;
+            height: 1s;
+            padding: 8px;
+            padding-left: 56px;
+            alignment: top-left;
+            font-family: monospace;
+            font-size: 14;
+            cursor: text;
+            border-width: 1px;
+            border-color: #888888;
+            corner-radius: 4px;
+            caret-color: #888888;
+            selection-color: #6464c888;
+
+            &:focus {
+                border-color: #51afef;
+            }
+
+            .keyword {
+                color: #c678dd;
+            }
+
+            .type {
+                color: #e5c07b;
+            }
+
+            .string {
+                color: #98c379;
+            }
+
+            .number {
+                color: #d19a66;
+            }
+
+            .comment {
+                color: #7f848e;
+                font-slant: italic;
+            }
+            "#,
+        )
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                if cx.is_disabled() {
+                    return;
+                }
+
+                cx.focus_with_visibility(false);
+                cx.capture();
+                self.edit = true;
+                cx.emit(CodeEditorEvent::Hit(
+                    cx.mouse.cursor_x,
+                    cx.mouse.cursor_y,
+                    cx.modifiers.shift(),
+                ));
+            }
+
+            WindowEvent::MouseMove(_, _) => {
+                if cx.mouse.left.state == MouseButtonState::Pressed
+                    && cx.mouse.left.pressed == cx.current
+                {
+                    cx.emit(CodeEditorEvent::Drag(cx.mouse.cursor_x, cx.mouse.cursor_y));
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                cx.release();
+            }
+
+            WindowEvent::MouseScroll(_, y) => {
+                cx.emit(CodeEditorEvent::Scroll(*y));
+                meta.consume();
+            }
+
+            WindowEvent::FocusIn => {
+                self.edit = true;
+                cx.needs_redraw();
+            }
+
+            WindowEvent::FocusOut => {
+                self.edit = false;
+                cx.needs_redraw();
+            }
+
+            WindowEvent::CharInput(c) => {
+                if *c != '\u{1b}' && // Escape
+                    *c != '\u{8}' && // Backspace
+                    *c != '\u{9}' && // Tab
+                    *c != '\u{7f}' && // Delete
+                    *c != '\u{0d}' && // Carriage return
+                    !cx.modifiers.ctrl() &&
+                    !cx.modifiers.logo() &&
+                    self.edit &&
+                    !cx.is_read_only()
+                {
+                    cx.emit(CodeEditorEvent::InsertText(String::from(*c)));
+                }
+            }
+
+            WindowEvent::KeyDown(Code::Enter, _) if !self.edit => {
+                self.edit = true;
+                cx.needs_redraw();
+            }
+
+            WindowEvent::KeyDown(code, _) if self.edit => {
+                #[cfg(target_os = "macos")]
+                let modifier = Modifiers::SUPER;
+                #[cfg(not(target_os = "macos"))]
+                let modifier = Modifiers::CTRL;
+
+                let shift = cx.modifiers.shift();
+                let word = cx.modifiers.ctrl();
+
+                match code {
+                    Code::Escape => {
+                        self.edit = false;
+                        cx.needs_redraw();
+                    }
+
+                    Code::Enter if !cx.is_read_only() => {
+                        cx.emit(CodeEditorEvent::NewLine);
+                    }
+
+                    Code::ArrowLeft => {
+                        let movement = if word {
+                            Movement::Word(Direction::Left)
+                        } else {
+                            Movement::Grapheme(Direction::Left)
+                        };
+                        cx.emit(CodeEditorEvent::MoveCursor(movement, shift));
+                    }
+
+                    Code::ArrowRight => {
+                        let movement = if word {
+                            Movement::Word(Direction::Right)
+                        } else {
+                            Movement::Grapheme(Direction::Right)
+                        };
+                        cx.emit(CodeEditorEvent::MoveCursor(movement, shift));
+                    }
+
+                    Code::ArrowUp => {
+                        cx.emit(CodeEditorEvent::MoveCursor(
+                            Movement::Vertical(VerticalMovement::LineUp),
+                            shift,
+                        ));
+                    }
+
+                    Code::ArrowDown => {
+                        cx.emit(CodeEditorEvent::MoveCursor(
+                            Movement::Vertical(VerticalMovement::LineDown),
+                            shift,
+                        ));
+                    }
+
+                    Code::Home => {
+                        let movement = if word {
+                            Movement::Vertical(VerticalMovement::DocumentStart)
+                        } else {
+                            Movement::LineStart
+                        };
+                        cx.emit(CodeEditorEvent::MoveCursor(movement, shift));
+                    }
+
+                    Code::End => {
+                        let movement = if word {
+                            Movement::Vertical(VerticalMovement::DocumentEnd)
+                        } else {
+                            Movement::LineEnd
+                        };
+                        cx.emit(CodeEditorEvent::MoveCursor(movement, shift));
+                    }
+
+                    Code::Backspace if !cx.is_read_only() => {
+                        let direction = Direction::Upstream;
+                        cx.emit(CodeEditorEvent::DeleteText(if word {
+                            Movement::Word(direction)
+                        } else {
+                            Movement::Grapheme(direction)
+                        }));
+                    }
+
+                    Code::Delete if !cx.is_read_only() => {
+                        let direction = Direction::Downstream;
+                        cx.emit(CodeEditorEvent::DeleteText(if word {
+                            Movement::Word(direction)
+                        } else {
+                            Movement::Grapheme(direction)
+                        }));
+                    }
+
+                    Code::KeyA if cx.modifiers == &modifier => {
+                        cx.emit(CodeEditorEvent::SelectAll);
+                    }
+
+                    Code::KeyC if cx.modifiers == &modifier => {
+                        cx.emit(CodeEditorEvent::Copy);
+                    }
+
+                    Code::KeyV if cx.modifiers == &modifier && !cx.is_read_only() => {
+                        cx.emit(CodeEditorEvent::Paste);
+                    }
+
+                    Code::KeyX if cx.modifiers == &modifier && !cx.is_read_only() => {
+                        cx.emit(CodeEditorEvent::Cut);
+                    }
+
+                    Code::KeyZ if !cx.is_read_only() => {
+                        if cx.modifiers == &modifier {
+                            cx.emit(CodeEditorEvent::Undo);
+                        } else if cx.modifiers == &(modifier | Modifiers::SHIFT) {
+                            cx.emit(CodeEditorEvent::Redo);
+                        }
+                    }
+
+                    Code::KeyY if cx.modifiers == &Modifiers::CTRL && !cx.is_read_only() => {
+                        cx.emit(CodeEditorEvent::Redo);
+                    }
+
+                    _ => {}
+                }
+            }
+
+            _ => {}
+        });
+
+        event.map(|code_editor_event, _| match code_editor_event {
+            CodeEditorEvent::InsertText(text) => self.insert_text(cx, text),
+
+            CodeEditorEvent::NewLine => self.new_line(cx),
+
+            CodeEditorEvent::DeleteText(movement) => self.delete_text(cx, *movement),
+
+            CodeEditorEvent::MoveCursor(movement, selection) => {
+                self.move_cursor(cx, *movement, *selection)
+            }
+
+            CodeEditorEvent::SelectAll => {
+                self.history.break_group();
+                self.selection = Selection::new(0, self.text.len());
+                cx.needs_redraw();
+            }
+
+            CodeEditorEvent::Indent => self.indent(cx),
+
+            CodeEditorEvent::Outdent => self.indent_lines(cx, true),
+
+            CodeEditorEvent::Undo => self.undo(cx),
+
+            CodeEditorEvent::Redo => self.redo(cx),
+
+            CodeEditorEvent::Hit(x, y, selection) => self.hit(cx, *x, *y, *selection),
+
+            CodeEditorEvent::Drag(x, y) => self.hit(cx, *x, *y, true),
+
+            CodeEditorEvent::Scroll(lines) => self.scroll(cx, *lines),
+
+            CodeEditorEvent::Copy =>
+            {
+                #[cfg(feature = "clipboard")]
+                if !self.selection.is_caret() {
+                    cx.set_clipboard(self.selected_text())
+                        .expect("Failed to add text to clipboard");
+                }
+            }
+
+            CodeEditorEvent::Paste =>
+            {
+                #[cfg(feature = "clipboard")]
+                if let Ok(text) = cx.get_clipboard() {
+                    self.insert_text(cx, &text);
+                }
+            }
+
+            CodeEditorEvent::Cut =>
+            {
+                #[cfg(feature = "clipboard")]
+                if !self.selection.is_caret() {
+                    cx.set_clipboard(self.selected_text())
+                        .expect("Failed to add text to clipboard");
+                    self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
+                }
+            }
+        });
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        cx.draw_shadows(canvas);
+        cx.draw_background(canvas);
+        cx.draw_border(canvas);
+        cx.draw_outline(canvas);
+
+        self.update_scroll(cx);
+
+        let bounds = cx.bounds();
+        canvas.save();
+        canvas.clip_rect(
+            Rect::new(bounds.x, bounds.y, bounds.right(), bounds.bottom()),
+            ClipOp::Intersect,
+            true,
+        );
+        canvas.translate((0.0, -self.scroll.get()));
+
+        let mut selection_rects = Vec::new();
+        if self.edit {
+            self.draw_current_line(cx, canvas);
+            selection_rects = self.draw_selection(cx, canvas);
+        }
+        cx.draw_text(canvas);
+        cx.draw_selected_text(canvas, &selection_rects);
+        if self.edit {
+            self.draw_matching_brackets(cx, canvas);
+            self.draw_caret(cx, canvas);
+        }
+        self.draw_gutter(cx, canvas);
+
+        canvas.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brackets_are_matched_across_nesting() {
+        let text = "fn a(b: [u8; 2]) { c(d) }";
+        // After the caret.
+        assert_eq!(matching_brackets(text, 4), Some((4, 15)));
+        // Before the caret.
+        assert_eq!(matching_brackets(text, 16), Some((15, 4)));
+        assert_eq!(matching_brackets(text, 17), Some((17, 24)));
+        assert_eq!(matching_brackets(text, 2), None);
+
+        // Unbalanced brackets have no match.
+        assert_eq!(matching_brackets("(()", 0), None);
+    }
+
+    #[test]
+    fn new_lines_keep_and_extend_indentation() {
+        let text = "fn main() {\n    let a = [];\n}";
+
+        // The indentation of the current line is kept.
+        assert_eq!(new_line_text(text, 27..27, 4), (String::from("\n    "), 5));
+        // A new line after an opening bracket adds one level of indentation.
+        assert_eq!(new_line_text(text, 11..11, 4), (String::from("\n    "), 5));
+        assert_eq!(new_line_text(text, 11..11, 2), (String::from("\n  "), 3));
+        // A closing bracket directly after the caret is moved onto its own line.
+        assert_eq!(new_line_text(text, 25..25, 4), (String::from("\n        \n    "), 9));
+    }
+
+    #[test]
+    fn tabs_insert_spaces_to_the_next_tab_stop() {
+        assert_eq!(spaces_to_tab_stop("x", 1, 4), 3);
+        assert_eq!(spaces_to_tab_stop("abcd", 4, 4), 4);
+        assert_eq!(spaces_to_tab_stop("line\nab", 7, 4), 2);
+    }
+
+    #[test]
+    fn lines_are_indented_and_outdented() {
+        let text = "a\n  b\nc";
+
+        let (range, new, selection) =
+            indent_lines_edit(text, Selection::new(0, 5), 4, false).unwrap();
+        assert_eq!((range, new.as_str()), (0..5, "    a\n      b"));
+        assert_eq!((selection.anchor, selection.active), (4, 13));
+
+        let (range, new, selection) =
+            indent_lines_edit(text, Selection::caret(5), 4, true).unwrap();
+        assert_eq!((range, new.as_str()), (2..5, "b"));
+        assert_eq!(selection.active, 3);
+
+        // Lines without indentation are not changed.
+        assert!(indent_lines_edit(text, Selection::caret(7), 4, true).is_none());
+    }
+
+    #[test]
+    fn backspace_in_indentation_deletes_to_the_previous_tab_stop() {
+        assert_eq!(indentation_deletion_start("        x", 8, 4), Some(4));
+        assert_eq!(indentation_deletion_start("a\n      ", 8, 4), Some(6));
+        assert_eq!(indentation_deletion_start("  x", 3, 4), None);
+        assert_eq!(indentation_deletion_start("x\n", 2, 4), None);
+    }
+
+    #[test]
+    fn only_spliced_lines_are_rebuilt() {
+        // Editing the second of four lines.
+        assert_eq!(splice_range(1..2, 4, 4), Some((1..2, 1)));
+        // Inserting a line after the second line rebuilds both lines, leaving the following lines in place.
+        assert_eq!(splice_range(1..3, 4, 5), Some((1..3, 1)));
+        // Removing the third line.
+        assert_eq!(splice_range(2..2, 4, 3), Some((2..2, 1)));
+        assert_eq!(splice_range(2..2, 3, 3), None);
+
+        // The line which becomes the first line loses its line break.
+        assert_eq!(splice_range(0..0, 3, 2), Some((0..1, 2)));
+        // The first line gains a line break when a line is inserted before it.
+        assert_eq!(splice_range(0..1, 2, 3), Some((0..2, 1)));
+    }
+}
@@ -5,6 +5,7 @@ mod badge;
 mod button;
 mod checkbox;
 mod chip;
+mod code_editor;
 mod collapsible;
 mod combobox;
 mod datepicker;
@@ -23,6 +24,7 @@ mod popup;
 mod progressbar;
 mod radio;
 mod rating;
+mod rich_text_editor;
 mod scrollbar;
 mod scrollview;
 mod slider;
@@ -42,6 +44,7 @@ pub use badge::*;
 pub use button::*;
 pub use checkbox::*;
 pub use chip::*;
+pub use code_editor::*;
 pub use collapsible::*;
 pub use combobox::*;
 pub use datepicker::*;
@@ -61,6 +64,7 @@ pub use popup::*;
 pub use progressbar::*;
 pub use radio::*;
 pub use rating::*;
+pub use rich_text_editor::*;
 pub use scrollbar::*;
 pub use scrollview::*;
 pub use slider::*;
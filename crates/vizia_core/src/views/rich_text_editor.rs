@@ -0,0 +1,1155 @@
+use std::ops::Range;
+
+#[cfg(feature = "markdown")]
+use std::cell::RefCell;
+
+#[cfg(feature = "markdown")]
+use comrak::nodes::{Ast, NodeValue};
+#[cfg(feature = "markdown")]
+use comrak::{parse_document, Arena, Options};
+use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle};
+use skia_safe::{Paint, PaintStyle, Rect};
+
+use crate::prelude::*;
+use crate::systems::{paragraph_position, text_offset};
+use crate::text::{
+    apply_movement, offset_for_delete_backwards, Direction, Movement, Selection, VerticalMovement,
+};
+
+/// The formatting of a span of rich text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    /// Whether the text is bold.
+    pub bold: bool,
+    /// Whether the text is italic.
+    pub italic: bool,
+    /// The color of the text, or `None` to use the color of the editor.
+    pub color: Option<Color>,
+    /// The font size of the text, or `None` to use the font size of the editor.
+    pub font_size: Option<f32>,
+    /// The URL which the text links to.
+    pub link: Option<String>,
+}
+
+/// A run of text with a single style within a [`RichTextDocument`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichTextSpan {
+    /// The text of the span.
+    pub text: String,
+    /// The style of the text.
+    pub style: SpanStyle,
+}
+
+impl RichTextSpan {
+    /// Creates a new span of text with the given style.
+    pub fn new(text: impl Into<String>, style: SpanStyle) -> Self {
+        Self { text: text.into(), style }
+    }
+}
+
+/// A formatting command which can be applied to a range of a [`RichTextDocument`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichTextFormat {
+    /// Toggles bold text.
+    Bold,
+    /// Toggles italic text.
+    Italic,
+    /// Sets the color of the text, or resets it to the color of the editor.
+    Color(Option<Color>),
+    /// Sets the font size of the text, or resets it to the font size of the editor.
+    FontSize(Option<f32>),
+    /// Sets the URL which the text links to, or removes the link.
+    Link(Option<String>),
+}
+
+impl RichTextFormat {
+    /// Returns true if the format is applied to text with the given style.
+    pub fn is_applied(&self, style: &SpanStyle) -> bool {
+        match self {
+            RichTextFormat::Bold => style.bold,
+            RichTextFormat::Italic => style.italic,
+            RichTextFormat::Color(color) => style.color == *color,
+            RichTextFormat::FontSize(font_size) => style.font_size == *font_size,
+            RichTextFormat::Link(link) => style.link == *link,
+        }
+    }
+
+    // Applies the format to a style, where `enable` determines whether toggled formats are switched on or off.
+    fn apply(&self, style: &mut SpanStyle, enable: bool) {
+        match self {
+            RichTextFormat::Bold => style.bold = enable,
+            RichTextFormat::Italic => style.italic = enable,
+            RichTextFormat::Color(color) => style.color = *color,
+            RichTextFormat::FontSize(font_size) => style.font_size = *font_size,
+            RichTextFormat::Link(link) => style.link.clone_from(link),
+        }
+    }
+}
+
+/// A document of styled spans of text, which is edited by a [`RichTextEditor`].
+///
+/// Offsets and ranges into the document are byte offsets into its text, as returned by
+/// [`text`](RichTextDocument::text). Adjacent spans with the same style are merged, and empty spans are removed.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct RichTextDocument {
+    #[data(eq)]
+    spans: Vec<RichTextSpan>,
+}
+
+impl RichTextDocument {
+    /// Creates a new empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a document from a list of spans.
+    pub fn from_spans(spans: impl IntoIterator<Item = RichTextSpan>) -> Self {
+        let mut document = Self { spans: spans.into_iter().collect() };
+        document.normalize();
+        document
+    }
+
+    /// Returns the spans of the document.
+    pub fn spans(&self) -> &[RichTextSpan] {
+        &self.spans
+    }
+
+    /// Returns the text of the document without any formatting.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Returns the length of the text of the document in bytes.
+    pub fn len(&self) -> usize {
+        self.spans.iter().map(|span| span.text.len()).sum()
+    }
+
+    /// Returns true if the document has no text.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the style of the text before the given offset, which is the style given to text typed at the offset.
+    pub fn style_at(&self, offset: usize) -> SpanStyle {
+        let mut start = 0;
+        for span in &self.spans {
+            let end = start + span.text.len();
+            if offset > start && offset <= end {
+                return span.style.clone();
+            }
+            start = end;
+        }
+
+        self.spans.first().map(|span| span.style.clone()).unwrap_or_default()
+    }
+
+    /// Inserts text with the given style at an offset.
+    pub fn insert(&mut self, offset: usize, text: &str, style: SpanStyle) {
+        let index = self.split_at(offset);
+        self.spans.insert(index, RichTextSpan::new(text, style));
+        self.normalize();
+    }
+
+    /// Deletes a range of the text.
+    pub fn delete(&mut self, range: Range<usize>) {
+        let start = self.split_at(range.start);
+        let end = self.split_at(range.end);
+        self.spans.drain(start..end);
+        self.normalize();
+    }
+
+    /// Returns true if the format is applied to all of the text in a range.
+    pub fn is_formatted(&self, range: Range<usize>, format: &RichTextFormat) -> bool {
+        let mut start = 0;
+        self.spans.iter().all(|span| {
+            let span_range = start..start + span.text.len();
+            start = span_range.end;
+            span_range.end <= range.start
+                || span_range.start >= range.end
+                || format.is_applied(&span.style)
+        })
+    }
+
+    /// Applies a formatting command to a range of the text.
+    ///
+    /// Bold and italic are toggled, so that they are removed if they are already applied to all of the text in the
+    /// range, and otherwise applied to all of it.
+    pub fn format(&mut self, range: Range<usize>, format: &RichTextFormat) {
+        if range.is_empty() {
+            return;
+        }
+
+        let enable = !self.is_formatted(range.clone(), format);
+        let start = self.split_at(range.start);
+        let end = self.split_at(range.end);
+        for span in &mut self.spans[start..end] {
+            format.apply(&mut span.style, enable);
+        }
+
+        self.normalize();
+    }
+
+    // Splits the span containing an offset so that a span starts at the offset, and returns the index of that span.
+    fn split_at(&mut self, offset: usize) -> usize {
+        let mut start = 0;
+        for index in 0..self.spans.len() {
+            let len = self.spans[index].text.len();
+            if offset <= start {
+                return index;
+            }
+
+            if offset < start + len {
+                let text = self.spans[index].text.split_off(offset - start);
+                let style = self.spans[index].style.clone();
+                self.spans.insert(index + 1, RichTextSpan::new(text, style));
+                return index + 1;
+            }
+
+            start += len;
+        }
+
+        self.spans.len()
+    }
+
+    fn normalize(&mut self) {
+        let mut spans: Vec<RichTextSpan> = Vec::with_capacity(self.spans.len());
+        for span in self.spans.drain(..).filter(|span| !span.text.is_empty()) {
+            match spans.last_mut() {
+                Some(last) if last.style == span.style => last.text.push_str(&span.text),
+                _ => spans.push(span),
+            }
+        }
+
+        self.spans = spans;
+    }
+}
+
+#[cfg(feature = "markdown")]
+impl RichTextDocument {
+    /// Parses a document from markdown.
+    ///
+    /// Bold, italic and links are kept, while other formatting is discarded. Blocks, such as paragraphs and headings,
+    /// are separated by new lines.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &Options::default());
+
+        let mut document = Self::default();
+        parse_markdown_node(root, &SpanStyle::default(), &mut document.spans);
+        document.normalize();
+        document
+    }
+
+    /// Converts the document to markdown.
+    ///
+    /// Bold, italic and links are written as markdown, using `<strong>` and `<em>` tags where emphasis delimiters
+    /// would not be recognized, while colors and font sizes, which markdown cannot represent, are discarded.
+    /// New lines are written as hard line breaks, so that converting the markdown back with
+    /// [`from_markdown`](RichTextDocument::from_markdown) gives the same text.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let mut line_start = true;
+
+        // Spans which only differ by formatting that markdown cannot represent are written together, as adjacent
+        // emphasis delimiters would otherwise merge.
+        let mut runs: Vec<(String, &SpanStyle)> = Vec::new();
+        for span in &self.spans {
+            match runs.last_mut() {
+                Some((text, style))
+                    if style.bold == span.style.bold
+                        && style.italic == span.style.italic
+                        && style.link == span.style.link =>
+                {
+                    text.push_str(&span.text);
+                }
+                _ => runs.push((span.text.clone(), &span.style)),
+            }
+        }
+
+        // The lines of each run, where `None` is a line break.
+        let mut lines = Vec::new();
+        for (text, style) in &runs {
+            for (index, line) in text.split('\n').enumerate() {
+                if index > 0 {
+                    lines.push(None);
+                }
+
+                if !line.is_empty() {
+                    lines.push(Some((line, *style)));
+                }
+            }
+        }
+
+        for (index, line) in lines.iter().enumerate() {
+            let Some((text, style)) = line else {
+                markdown.push_str("\\\n");
+                line_start = true;
+                continue;
+            };
+
+            // The first character written after the line of the run, which decides whether its emphasis can close.
+            let next = match lines.get(index + 1) {
+                Some(Some((_, next_style))) if next_style.link.is_some() => Some('['),
+                Some(Some((_, next_style))) if next_style.bold || next_style.italic => Some('*'),
+                Some(Some((next_text, _))) => next_text.chars().next(),
+                _ => None,
+            };
+
+            write_markdown_run(&mut markdown, text, style, line_start, next);
+            line_start = false;
+        }
+
+        markdown
+    }
+}
+
+#[cfg(feature = "markdown")]
+fn parse_markdown_node<'a>(
+    node: &'a comrak::arena_tree::Node<'a, RefCell<Ast>>,
+    style: &SpanStyle,
+    spans: &mut Vec<RichTextSpan>,
+) {
+    let base = style;
+    let mut style = style.clone();
+    match &node.data.borrow().value {
+        NodeValue::Paragraph | NodeValue::Heading(_) => {
+            if !spans.is_empty() {
+                spans.push(RichTextSpan::new("\n", SpanStyle::default()));
+            }
+        }
+
+        NodeValue::CodeBlock(code_block) => {
+            if !spans.is_empty() {
+                spans.push(RichTextSpan::new("\n", SpanStyle::default()));
+            }
+            let code = code_block.literal.strip_suffix('\n').unwrap_or(&code_block.literal);
+            spans.push(RichTextSpan::new(code, style));
+            return;
+        }
+
+        NodeValue::Text(text) => {
+            spans.push(RichTextSpan::new(text.as_str(), style));
+            return;
+        }
+
+        NodeValue::Code(code) => {
+            spans.push(RichTextSpan::new(code.literal.as_str(), style));
+            return;
+        }
+
+        NodeValue::SoftBreak | NodeValue::LineBreak => {
+            spans.push(RichTextSpan::new("\n", style));
+            return;
+        }
+
+        NodeValue::Strong => style.bold = true,
+        NodeValue::Emph => style.italic = true,
+        NodeValue::Link(link) => style.link = Some(link.url.clone()),
+
+        _ => {}
+    }
+
+    for child in node.children() {
+        // Inline `<strong>` and `<em>` tags, which are written where emphasis delimiters would not be recognized,
+        // format the text between them.
+        if let NodeValue::HtmlInline(html) = &child.data.borrow().value {
+            match html.as_str() {
+                "<strong>" => style.bold = true,
+                "</strong>" => style.bold = base.bold,
+                "<em>" => style.italic = true,
+                "</em>" => style.italic = base.italic,
+                _ => {}
+            }
+            continue;
+        }
+
+        parse_markdown_node(child, &style, spans);
+    }
+}
+
+// Writes a line of text with a single style as markdown, where `next` is the first character written after it.
+#[cfg(feature = "markdown")]
+fn write_markdown_run(
+    markdown: &mut String,
+    text: &str,
+    style: &SpanStyle,
+    line_start: bool,
+    next: Option<char>,
+) {
+    // Emphasis delimiters must be next to non-whitespace characters, so surrounding whitespace is written outside of
+    // them.
+    let content = text.trim();
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[leading.len() + content.len()..];
+
+    if line_start {
+        // Whitespace at the start of a line would otherwise be removed, or turn the line into a code block.
+        for c in leading.chars() {
+            markdown.push_str(&format!("&#{};", c as u32));
+        }
+    } else {
+        markdown.push_str(leading);
+    }
+
+    if !content.is_empty() {
+        let delimiter = match (style.bold, style.italic) {
+            (true, true) => "***",
+            (true, false) => "**",
+            (false, true) => "*",
+            (false, false) => "",
+        };
+
+        let mut escaped = String::new();
+        escape_markdown(
+            &mut escaped,
+            content,
+            line_start && leading.is_empty() && delimiter.is_empty(),
+        );
+
+        // Delimiters only open and close emphasis when they follow the flanking rules of CommonMark, and delimiters
+        // written back-to-back would merge into one run, so emphasis is otherwise written with HTML tags.
+        let (before, after) = match style.link {
+            Some(_) => (Some('['), Some(']')),
+            None => (markdown.chars().next_back(), trailing.chars().next().or(next)),
+        };
+        let use_delimiter = before != Some('*')
+            && after != Some('*')
+            && escaped.chars().next().is_some_and(|first| can_delimit(first, before))
+            && escaped.chars().next_back().is_some_and(|last| can_delimit(last, after));
+
+        let (open, close) = match (style.bold, style.italic) {
+            _ if use_delimiter => (delimiter, delimiter),
+            (true, true) => ("<strong><em>", "</em></strong>"),
+            (true, false) => ("<strong>", "</strong>"),
+            (false, true) => ("<em>", "</em>"),
+            (false, false) => ("", ""),
+        };
+
+        if style.link.is_some() {
+            markdown.push('[');
+        }
+
+        markdown.push_str(open);
+        markdown.push_str(&escaped);
+        markdown.push_str(close);
+
+        if let Some(link) = &style.link {
+            if link.contains([' ', '(', ')']) {
+                markdown.push_str(&format!("](<{}>)", link));
+            } else {
+                markdown.push_str(&format!("]({})", link));
+            }
+        }
+    }
+
+    markdown.push_str(trailing);
+}
+
+// Returns whether an emphasis delimiter next to a character of the emphasized text, with the given character on its
+// other side, can open or close the emphasis. Characters which are neither alphanumeric nor whitespace are treated
+// as punctuation.
+#[cfg(feature = "markdown")]
+fn can_delimit(inner: char, outer: Option<char>) -> bool {
+    inner.is_alphanumeric()
+        || outer.map_or(true, |outer| outer.is_whitespace() || outer.is_ascii_punctuation())
+}
+
+// Writes text as markdown, escaping characters which would otherwise be interpreted as markdown syntax.
+#[cfg(feature = "markdown")]
+fn escape_markdown(markdown: &mut String, text: &str, line_start: bool) {
+    // A number followed by `.` or `)` at the start of a line would start an ordered list.
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+
+    for (index, c) in text.char_indices() {
+        let escape = match c {
+            '\\' | '*' | '_' | '[' | ']' | '`' | '<' | '&' => true,
+            '#' | '-' | '+' | '=' | '>' => line_start && index == 0,
+            '.' | ')' => line_start && digits > 0 && index == digits,
+            _ => false,
+        };
+
+        if escape {
+            markdown.push('\\');
+        }
+        markdown.push(c);
+    }
+}
+
+/// Events for editing a [`RichTextEditor`].
+pub enum RichTextEvent {
+    /// Insert text at the caret, replacing the selected text.
+    InsertText(String),
+    /// Delete a section of text, determined by the `Movement`.
+    DeleteText(Movement),
+    /// Move the caret, extending the selection if the flag is true.
+    MoveCursor(Movement, bool),
+    /// Select all text.
+    SelectAll,
+    /// Apply a formatting command to the selected text, or to the text typed next if nothing is selected.
+    Format(RichTextFormat),
+    /// Specify the 'hit' position of the mouse cursor.
+    Hit(f32, f32, bool),
+    /// Specify the 'drag' position of the mouse cursor.
+    Drag(f32, f32),
+    /// Copy the selected text to the clipboard.
+    Copy,
+    /// Paste the text in the clipboard at the caret.
+    Paste,
+    /// Cut the selected text and place it in the clipboard.
+    Cut,
+}
+
+/// A view for editing a [`RichTextDocument`] of styled text.
+///
+/// The editor takes a lens to a document. Edits are applied to a copy of the document held by the editor and
+/// reported by the `on_edit` callback, which can be used to update the bound data. Formatting commands are applied
+/// to the selection with [`RichTextEvent::Format`], or with the `Ctrl+B` and `Ctrl+I` shortcuts for bold and italic.
+///
+/// # Example
+/// ```rust
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     notes: RichTextDocument,
+/// # }
+/// #
+/// # enum AppEvent {
+/// #     SetNotes(RichTextDocument),
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { notes: RichTextDocument::new() }.build(cx);
+/// #
+/// RichTextEditor::new(cx, AppData::notes)
+///     .on_edit(|cx, document| cx.emit(AppEvent::SetNotes(document)));
+/// ```
+#[derive(Lens)]
+pub struct RichTextEditor<L: Lens> {
+    lens: L,
+    document: RichTextDocument,
+    #[lens(ignore)]
+    selection: Selection,
+    // The style of the text typed at the caret, when it has been formatted while nothing is selected.
+    #[lens(ignore)]
+    caret_style: Option<SpanStyle>,
+    edit: bool,
+    on_edit: Option<Box<dyn Fn(&mut EventContext, RichTextDocument) + Send + Sync>>,
+}
+
+impl<L> RichTextEditor<L>
+where
+    L: Lens<Target = RichTextDocument>,
+{
+    /// Creates a new rich text editor for the document of the given lens.
+    pub fn new(cx: &mut Context, lens: L) -> Handle<Self> {
+        Self {
+            lens,
+            document: RichTextDocument::default(),
+            selection: Selection::caret(0),
+            caret_style: None,
+            edit: false,
+            on_edit: None,
+        }
+        .build(cx, |cx| {
+            let editor = cx.current();
+            Binding::new(cx, Self::document, move |cx, document| {
+                let document = document.get(cx);
+                for span in document.spans() {
+                    build_span(cx, span);
+                }
+
+                // The spans make up the text of the editor, so its paragraph is rebuilt when they change.
+                cx.style.needs_text_update(editor);
+            });
+        })
+        .text("")
+        .navigable(true)
+        .role(Role::TextInput)
+        .bind(lens, |handle, lens| {
+            let document = lens.get(&handle);
+            handle.modify(|editor: &mut Self| {
+                if editor.document != document {
+                    let len = document.len();
+                    editor.selection = Selection::new(
+                        editor.selection.anchor.min(len),
+                        editor.selection.active.min(len),
+                    );
+                    editor.document = document;
+                }
+            });
+        })
+    }
+}
+
+fn build_span(cx: &mut Context, span: &RichTextSpan) {
+    let style = &span.style;
+    let mut handle = TextSpan::new(cx, &span.text, |_| {});
+
+    if style.bold {
+        handle = handle.font_weight(FontWeightKeyword::Bold);
+    }
+
+    if style.italic {
+        handle = handle.font_slant(FontSlant::Italic);
+    }
+
+    if let Some(color) = style.color {
+        handle = handle.color(color);
+    }
+
+    if let Some(font_size) = style.font_size {
+        handle = handle.font_size(font_size);
+    }
+
+    handle.toggle_class("link", style.link.is_some());
+}
+
+impl<L: Lens> RichTextEditor<L> {
+    fn edited(&mut self, cx: &mut EventContext) {
+        if let Some(callback) = &self.on_edit {
+            (callback)(cx, self.document.clone());
+        }
+    }
+
+    fn insert_text(&mut self, cx: &mut EventContext, text: &str) {
+        let range = self.selection.range();
+        let style = self.caret_style.take().unwrap_or_else(|| self.document.style_at(range.start));
+
+        self.document.delete(range.clone());
+        self.document.insert(range.start, text, style);
+        self.selection = Selection::caret(range.start + text.len());
+        self.edited(cx);
+    }
+
+    fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        let text = self.document.text();
+        let range = if !self.selection.is_caret() {
+            self.selection.range()
+        } else if movement == Movement::Grapheme(Direction::Upstream) {
+            offset_for_delete_backwards(&self.selection, &text)..self.selection.active
+        } else if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            apply_movement(movement, self.selection, &text, paragraph, true).range()
+        } else {
+            return;
+        };
+
+        if range.is_empty() {
+            return;
+        }
+
+        self.caret_style = None;
+        self.document.delete(range.clone());
+        self.selection = Selection::caret(range.start);
+        self.edited(cx);
+    }
+
+    fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        let text = self.document.text();
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            self.selection = apply_movement(movement, self.selection, &text, paragraph, selection);
+            self.caret_style = None;
+            cx.needs_redraw();
+        }
+    }
+
+    fn format(&mut self, cx: &mut EventContext, format: &RichTextFormat) {
+        if self.selection.is_caret() {
+            // With nothing selected, the format applies to the text typed next.
+            let mut style = self
+                .caret_style
+                .take()
+                .unwrap_or_else(|| self.document.style_at(self.selection.active));
+            let enable = !format.is_applied(&style);
+            format.apply(&mut style, enable);
+            self.caret_style = Some(style);
+        } else {
+            self.document.format(self.selection.range(), format);
+            self.edited(cx);
+        }
+    }
+
+    // Returns the position of the text of the editor, which is laid out from its top left corner.
+    fn text_origin(cx: &EventContext) -> (f32, f32) {
+        let bounds = cx.bounds();
+        let padding_left = cx.style.padding_left.get(cx.current).copied().unwrap_or_default();
+        let padding_top = cx.style.padding_top.get(cx.current).copied().unwrap_or_default();
+        let logical_width = cx.physical_to_logical(bounds.w);
+        let logical_height = cx.physical_to_logical(bounds.h);
+
+        (
+            bounds.x + padding_left.to_px(logical_width, 0.0) * cx.scale_factor(),
+            bounds.y + padding_top.to_px(logical_height, 0.0) * cx.scale_factor(),
+        )
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let text = self.document.text();
+            let (origin_x, origin_y) = Self::text_origin(cx);
+            let gp = paragraph.get_glyph_position_at_coordinate((x - origin_x, y - origin_y));
+            let cursor = text_offset(paragraph, &text, gp.position as usize);
+
+            if selection {
+                self.selection.active = cursor;
+            } else {
+                self.selection = Selection::caret(cursor);
+            }
+
+            self.caret_style = None;
+            cx.needs_redraw();
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn selected_text(&self) -> String {
+        self.document.text()[self.selection.range()].to_owned()
+    }
+
+    // Returns the offset of the text from the bounds of the editor when drawing.
+    fn draw_offset(cx: &mut DrawContext) -> (f32, f32) {
+        let padding_left = match cx.padding_left() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        let padding_top = match cx.padding_top() {
+            Units::Pixels(val) => val,
+            _ => 0.0,
+        };
+
+        (padding_left, padding_top)
+    }
+
+    // Draws the selection and returns the rectangles it covers.
+    fn draw_selection(&self, cx: &mut DrawContext, canvas: &Canvas) -> Vec<Rect> {
+        if self.selection.is_caret() {
+            return Vec::new();
+        }
+
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return Vec::new();
+        };
+
+        let text = self.document.text();
+        let min = paragraph_position(paragraph, &text, self.selection.min());
+        let max = paragraph_position(paragraph, &text, self.selection.max());
+
+        let rects =
+            paragraph.get_rects_for_range(min..max, RectHeightStyle::Tight, RectWidthStyle::Tight);
+
+        let bounds = cx.bounds();
+        let (dx, dy) = Self::draw_offset(cx);
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(cx.selection_color());
+
+        rects
+            .iter()
+            .map(|rect| {
+                let x = bounds.x + dx + rect.rect.left;
+                let y = bounds.y + dy + rect.rect.top;
+                let rect = Rect::new(x, y, x + rect.rect.width(), y + rect.rect.height());
+                canvas.draw_rect(rect, &paint);
+                rect
+            })
+            .collect()
+    }
+
+    fn draw_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            let text = self.document.text();
+            let current = paragraph_position(paragraph, &text, self.selection.active);
+
+            let rects = paragraph.get_rects_for_range(
+                current..current + 1,
+                RectHeightStyle::Tight,
+                RectWidthStyle::Tight,
+            );
+
+            let Some(caret) = rects.first() else {
+                return;
+            };
+
+            let bounds = cx.bounds();
+            let (dx, dy) = Self::draw_offset(cx);
+
+            let x = (bounds.x + dx + caret.rect.left).round();
+            let y = (bounds.y + dy + caret.rect.top).round();
+
+            let mut paint = Paint::default();
+            paint.set_anti_alias(true);
+            paint.set_style(PaintStyle::Fill);
+            paint.set_color(cx.caret_color());
+
+            canvas.draw_rect(Rect::new(x, y, x + 1.0, y + caret.rect.height()), &paint);
+        }
+    }
+}
+
+impl<L: Lens> Handle<'_, RichTextEditor<L>> {
+    /// Sets the callback triggered when the document is edited, which provides the edited document.
+    pub fn on_edit<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, RichTextDocument) + Send + Sync,
+    {
+        self.modify(|editor: &mut RichTextEditor<L>| editor.on_edit = Some(Box::new(callback)))
+    }
+}
+
+impl<L: Lens> View for RichTextEditor<L> {
+    fn element(&self) -> Option<&'static str> {
+        Some("rich-text-editor")
+    }
+
+    fn stylesheet() -> Option<&'static str> {
+        Some(
+            r#"
+            width: 1s;
+            height: auto;
+            min-height: 64px;
+            padding: 8px;
+            alignment: top-left;
+            text-wrap: true;
+            cursor: text;
+            border-width: 1px;
+            border-color: #888888;
+            corner-radius: 4px;
+            caret-color: #888888;
+            selection-color: #6464c888;
+
+            &:focus {
+                border-color: #51afef;
+            }
+
+            .link {
+                color: #51afef;
+                text-decoration-line: underline;
+            }
+            "#,
+        )
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, _| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                if cx.is_disabled() {
+                    return;
+                }
+
+                cx.focus_with_visibility(false);
+                cx.capture();
+                cx.emit(RichTextEvent::Hit(
+                    cx.mouse.cursor_x,
+                    cx.mouse.cursor_y,
+                    cx.modifiers.shift(),
+                ));
+            }
+
+            WindowEvent::MouseMove(_, _) => {
+                if cx.mouse.left.state == MouseButtonState::Pressed
+                    && cx.mouse.left.pressed == cx.current
+                {
+                    cx.emit(RichTextEvent::Drag(cx.mouse.cursor_x, cx.mouse.cursor_y));
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                cx.release();
+            }
+
+            WindowEvent::FocusIn => {
+                self.edit = true;
+                cx.needs_redraw();
+            }
+
+            WindowEvent::FocusOut => {
+                self.edit = false;
+                cx.needs_redraw();
+            }
+
+            WindowEvent::CharInput(c) => {
+                if *c != '\u{1b}' && // Escape
+                    *c != '\u{8}' && // Backspace
+                    *c != '\u{9}' && // Tab
+                    *c != '\u{7f}' && // Delete
+                    *c != '\u{0d}' && // Carriage return
+                    !cx.modifiers.ctrl() &&
+                    !cx.modifiers.logo() &&
+                    self.edit &&
+                    !cx.is_read_only()
+                {
+                    cx.emit(RichTextEvent::InsertText(String::from(*c)));
+                }
+            }
+
+            WindowEvent::KeyDown(code, _) if self.edit => {
+                #[cfg(target_os = "macos")]
+                let modifier = Modifiers::SUPER;
+                #[cfg(not(target_os = "macos"))]
+                let modifier = Modifiers::CTRL;
+
+                let shift = cx.modifiers.shift();
+                let word = cx.modifiers.ctrl();
+
+                match code {
+                    Code::Enter if !cx.is_read_only() => {
+                        cx.emit(RichTextEvent::InsertText(String::from("\n")));
+                    }
+
+                    Code::ArrowLeft => {
+                        let movement = if word {
+                            Movement::Word(Direction::Left)
+                        } else {
+                            Movement::Grapheme(Direction::Left)
+                        };
+                        cx.emit(RichTextEvent::MoveCursor(movement, shift));
+                    }
+
+                    Code::ArrowRight => {
+                        let movement = if word {
+                            Movement::Word(Direction::Right)
+                        } else {
+                            Movement::Grapheme(Direction::Right)
+                        };
+                        cx.emit(RichTextEvent::MoveCursor(movement, shift));
+                    }
+
+                    Code::ArrowUp => {
+                        cx.emit(RichTextEvent::MoveCursor(
+                            Movement::Vertical(VerticalMovement::LineUp),
+                            shift,
+                        ));
+                    }
+
+                    Code::ArrowDown => {
+                        cx.emit(RichTextEvent::MoveCursor(
+                            Movement::Vertical(VerticalMovement::LineDown),
+                            shift,
+                        ));
+                    }
+
+                    Code::Home => {
+                        cx.emit(RichTextEvent::MoveCursor(Movement::LineStart, shift));
+                    }
+
+                    Code::End => {
+                        cx.emit(RichTextEvent::MoveCursor(Movement::LineEnd, shift));
+                    }
+
+                    Code::Backspace if !cx.is_read_only() => {
+                        let direction = Direction::Upstream;
+                        cx.emit(RichTextEvent::DeleteText(if word {
+                            Movement::Word(direction)
+                        } else {
+                            Movement::Grapheme(direction)
+                        }));
+                    }
+
+                    Code::Delete if !cx.is_read_only() => {
+                        let direction = Direction::Downstream;
+                        cx.emit(RichTextEvent::DeleteText(if word {
+                            Movement::Word(direction)
+                        } else {
+                            Movement::Grapheme(direction)
+                        }));
+                    }
+
+                    Code::KeyA if cx.modifiers == &modifier => {
+                        cx.emit(RichTextEvent::SelectAll);
+                    }
+
+                    Code::KeyB if cx.modifiers == &modifier && !cx.is_read_only() => {
+                        cx.emit(RichTextEvent::Format(RichTextFormat::Bold));
+                    }
+
+                    Code::KeyI if cx.modifiers == &modifier && !cx.is_read_only() => {
+                        cx.emit(RichTextEvent::Format(RichTextFormat::Italic));
+                    }
+
+                    Code::KeyC if cx.modifiers == &modifier => {
+                        cx.emit(RichTextEvent::Copy);
+                    }
+
+                    Code::KeyV if cx.modifiers == &modifier && !cx.is_read_only() => {
+                        cx.emit(RichTextEvent::Paste);
+                    }
+
+                    Code::KeyX if cx.modifiers == &modifier && !cx.is_read_only() => {
+                        cx.emit(RichTextEvent::Cut);
+                    }
+
+                    _ => {}
+                }
+            }
+
+            _ => {}
+        });
+
+        event.map(|rich_text_event, _| match rich_text_event {
+            RichTextEvent::InsertText(text) => self.insert_text(cx, text),
+
+            RichTextEvent::DeleteText(movement) => self.delete_text(cx, *movement),
+
+            RichTextEvent::MoveCursor(movement, selection) => {
+                self.move_cursor(cx, *movement, *selection)
+            }
+
+            RichTextEvent::SelectAll => {
+                self.selection = Selection::new(0, self.document.len());
+                self.caret_style = None;
+                cx.needs_redraw();
+            }
+
+            RichTextEvent::Format(format) => self.format(cx, format),
+
+            RichTextEvent::Hit(x, y, selection) => self.hit(cx, *x, *y, *selection),
+
+            RichTextEvent::Drag(x, y) => self.hit(cx, *x, *y, true),
+
+            RichTextEvent::Copy =>
+            {
+                #[cfg(feature = "clipboard")]
+                if !self.selection.is_caret() {
+                    cx.set_clipboard(self.selected_text())
+                        .expect("Failed to add text to clipboard");
+                }
+            }
+
+            RichTextEvent::Paste =>
+            {
+                #[cfg(feature = "clipboard")]
+                if let Ok(text) = cx.get_clipboard() {
+                    self.insert_text(cx, &text);
+                }
+            }
+
+            RichTextEvent::Cut =>
+            {
+                #[cfg(feature = "clipboard")]
+                if !self.selection.is_caret() {
+                    cx.set_clipboard(self.selected_text())
+                        .expect("Failed to add text to clipboard");
+                    self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
+                }
+            }
+        });
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        cx.draw_shadows(canvas);
+        cx.draw_background(canvas);
+        cx.draw_border(canvas);
+        cx.draw_outline(canvas);
+        let mut selection_rects = Vec::new();
+        if self.edit {
+            selection_rects = self.draw_selection(cx, canvas);
+        }
+        cx.draw_text(canvas);
+        cx.draw_selected_text(canvas, &selection_rects);
+        if self.edit {
+            self.draw_caret(cx, canvas);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bold() -> SpanStyle {
+        SpanStyle { bold: true, ..Default::default() }
+    }
+
+    #[test]
+    fn editing_merges_spans_with_the_same_style() {
+        let mut document = RichTextDocument::new();
+        document.insert(0, "Hello", SpanStyle::default());
+        document.insert(5, " world", SpanStyle::default());
+        assert_eq!(document.spans(), &[RichTextSpan::new("Hello world", SpanStyle::default())]);
+
+        document.insert(5, ",", bold());
+        assert_eq!(document.spans().len(), 3);
+
+        document.delete(4..7);
+        assert_eq!(document.text(), "Hellworld");
+        assert_eq!(document.spans(), &[RichTextSpan::new("Hellworld", SpanStyle::default())]);
+    }
+
+    #[test]
+    fn formatting_toggles_over_a_range() {
+        let mut document = RichTextDocument::from_spans([RichTextSpan::new(
+            "one two three",
+            SpanStyle::default(),
+        )]);
+
+        document.format(4..7, &RichTextFormat::Bold);
+        assert_eq!(
+            document.spans(),
+            &[
+                RichTextSpan::new("one ", SpanStyle::default()),
+                RichTextSpan::new("two", bold()),
+                RichTextSpan::new(" three", SpanStyle::default()),
+            ]
+        );
+        assert_eq!(document.style_at(7), bold());
+        assert_eq!(document.style_at(8), SpanStyle::default());
+
+        // Part of the range is not bold, so all of it is made bold.
+        document.format(0..7, &RichTextFormat::Bold);
+        assert!(document.is_formatted(0..7, &RichTextFormat::Bold));
+
+        document.format(0..7, &RichTextFormat::Bold);
+        assert_eq!(document.spans(), &[RichTextSpan::new("one two three", SpanStyle::default())]);
+    }
+
+    #[test]
+    fn typed_text_continues_the_style_before_it() {
+        let mut document = RichTextDocument::new();
+        document.insert(0, "hello", SpanStyle::default());
+        document.format(0..5, &RichTextFormat::Bold);
+        assert_eq!(document.spans(), &[RichTextSpan::new("hello", bold())]);
+
+        document.insert(5, "!", document.style_at(5));
+        assert_eq!(document.spans(), &[RichTextSpan::new("hello!", bold())]);
+
+        // Text typed at the start takes the style of the first span.
+        document.insert(0, ">", document.style_at(0));
+        assert_eq!(document.spans(), &[RichTextSpan::new(">hello!", bold())]);
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_round_trip() {
+        let link =
+            SpanStyle { link: Some(String::from("https://vizia.dev")), ..Default::default() };
+        let document = RichTextDocument::from_spans([
+            RichTextSpan::new("Some ", SpanStyle::default()),
+            RichTextSpan::new("bold", bold()),
+            RichTextSpan::new(" and ", SpanStyle::default()),
+            RichTextSpan::new("italic", SpanStyle { italic: true, ..Default::default() }),
+            RichTextSpan::new(" text with a ", SpanStyle::default()),
+            RichTextSpan::new("link", link),
+            RichTextSpan::new(".\n# Not a heading *\nx", SpanStyle::default()),
+            // Delimiters which would not be recognized next to punctuation or other delimiters.
+            RichTextSpan::new("(y)", bold()),
+            RichTextSpan::new(" ", SpanStyle::default()),
+            RichTextSpan::new("a", SpanStyle { italic: true, ..Default::default() }),
+            RichTextSpan::new("b", bold()),
+            RichTextSpan::new(".", SpanStyle::default()),
+        ]);
+
+        let markdown = document.to_markdown();
+        assert_eq!(
+            markdown,
+            "Some **bold** and *italic* text with a [link](https://vizia.dev).\\\n\\# Not a heading \\*\\\nx<strong>(y)</strong> <em>a</em>**b**."
+        );
+        assert_eq!(RichTextDocument::from_markdown(&markdown), document);
+    }
+}
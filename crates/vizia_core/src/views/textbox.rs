@@ -1,15 +1,17 @@
 // use crate::accessibility::IntoNode;
 use crate::prelude::*;
+use crate::systems::{paragraph_position, text_indent_offset, text_offset, text_offset_from_utf16};
 
 use crate::text::{
-    apply_movement, offset_for_delete_backwards, Direction, EditableText, Movement, Selection,
-    VerticalMovement,
+    apply_masked_movement, apply_movement, offset_for_delete_backwards, Direction, EditHistory,
+    EditKind, EditableText, InputMask, Movement, Selection, VerticalMovement,
 };
 // use crate::views::scrollview::SCROLL_SENSITIVITY;
 use accesskit::{ActionData, ActionRequest};
 use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle};
 use skia_safe::{Paint, PaintStyle, Rect};
-use unicode_segmentation::UnicodeSegmentation;
+use std::cell::Cell;
+use std::ops::Range;
 
 /// Events for modifying a textbox.
 pub enum TextEvent {
@@ -51,6 +53,17 @@ pub enum TextEvent {
     Blur,
     /// Toggle the visibility of the text Caret.
     ToggleCaret,
+    /// Revert the most recent edit.
+    Undo,
+    /// Reapply the most recently reverted edit.
+    Redo,
+    /// Set the text being composed by an input method, with the byte range of the cursor within the text.
+    ///
+    /// The preedit text is shown at the caret but is not part of the text of the textbox. An empty string clears the
+    /// preedit text.
+    SetPreedit(String, Option<(usize, usize)>),
+    /// Set whether the textbox is in secure mode, which hides the text for entering passwords.
+    SetSecure(bool),
 }
 
 /// The `Textbox` view provides an input control for editing a value as a string.
@@ -76,6 +89,21 @@ pub struct Textbox<L: Lens> {
     show_caret: bool,
     caret_timer: Timer,
     selection: Selection,
+    #[lens(ignore)]
+    history: EditHistory,
+    // The text after the most recent edit, used to detect changes to the bound data from outside of the textbox.
+    #[lens(ignore)]
+    edited_text: String,
+    // The byte range of the preedit text of an input method composition within the displayed text.
+    #[lens(ignore)]
+    preedit: Option<Range<usize>>,
+    // The caret area most recently sent to the input method, in physical window coordinates.
+    #[lens(ignore)]
+    ime_cursor_area: Cell<Option<BoundingBox>>,
+    #[lens(ignore)]
+    secure: bool,
+    #[lens(ignore)]
+    mask: Option<InputMask>,
 }
 
 // Determines whether the enter key submits the text or inserts a new line.
@@ -162,6 +190,12 @@ where
             show_caret: true,
             caret_timer,
             selection: Selection::new(0, 0),
+            history: EditHistory::new(),
+            edited_text: String::new(),
+            preedit: None,
+            ime_cursor_area: Cell::new(None),
+            secure: false,
+            mask: None,
         }
         .build(cx, move |cx| {
             cx.add_listener(move |textbox: &mut Self, cx, event| {
@@ -186,33 +220,72 @@ where
         .text(lens)
         .placeholder_shown(Self::show_placeholder)
         .bind(lens, |handle, lens| {
-            let flag = lens.get(&handle).to_string_local(handle.cx).is_empty();
-            handle.modify(|textbox| textbox.show_placeholder = flag).bind(
-                Self::placeholder,
-                move |handle, placeholder| {
+            let value = lens.get(&handle).to_string_local(handle.cx);
+            let flag = value.is_empty();
+            handle
+                .modify(|textbox: &mut Self| {
+                    // Edits are submitted to the bound data by the callbacks of the textbox, so the history is only
+                    // reset when the data changes to something other than the edited text.
+                    if textbox.edited_text != value {
+                        textbox.history.clear();
+                    }
+                    // The displayed text has been replaced, so any composition in progress is discarded.
+                    textbox.preedit = None;
+                    textbox.show_placeholder = flag;
+                })
+                .bind(Self::placeholder, move |handle, placeholder| {
                     let value = placeholder.get(&handle).to_string_local(handle.cx);
                     if flag {
                         handle.text(value);
                     }
-                },
-            );
+                });
         })
     }
 
     fn insert_text(&mut self, cx: &mut EventContext, txt: &str) {
+        self.remove_preedit(cx);
         if let Some(text) = cx.style.text.get_mut(cx.current) {
             if self.show_placeholder && !txt.is_empty() {
                 text.clear();
                 self.show_placeholder = false;
             }
-            text.edit(self.selection.range(), txt);
-            self.selection = Selection::caret(self.selection.min() + txt.len());
+            // Consecutive characters are grouped into one history entry, while new lines and pasted text are not.
+            let kind = if txt.chars().count() == 1 && txt != "\n" {
+                EditKind::Typing
+            } else {
+                EditKind::Other
+            };
+            self.replace_range(text, self.selection.range(), txt, kind);
             self.show_placeholder = text.is_empty();
+            // All of the text may have been rejected by the input mask.
+            if self.show_placeholder {
+                *text = self.placeholder.clone();
+                self.selection = Selection::caret(0);
+            }
             cx.style.needs_text_update(cx.current);
         }
     }
 
+    /// Replaces a range of the text and records the edit in the history, first passing the edit through the input
+    /// mask if there is one.
+    fn replace_range(&mut self, text: &mut String, range: Range<usize>, new: &str, kind: EditKind) {
+        if let Some(mask) = &self.mask {
+            if let Some((range, new, caret)) = mask.edit(text, range, new) {
+                let caret = Selection::caret(caret);
+                self.history.edit(text, range, &new, self.selection, caret, kind);
+                self.selection = caret;
+            }
+        } else {
+            let caret = Selection::caret(range.start + new.len());
+            self.history.edit(text, range, new, self.selection, caret, kind);
+            self.selection = caret;
+        }
+    }
+
     fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        self.remove_preedit(cx);
+        let movement = self.hide_word_boundaries(movement);
+        let mask = self.text_mask(cx);
         if self.selection.is_caret() {
             if movement == Movement::Grapheme(Direction::Upstream) {
                 if self.selection.active == 0 {
@@ -222,27 +295,30 @@ where
                     let del_offset = offset_for_delete_backwards(&self.selection, text);
                     let del_range = del_offset..self.selection.active;
 
-                    self.selection = Selection::caret(del_range.start);
-
-                    text.edit(del_range, "");
+                    self.replace_range(text, del_range, "", EditKind::Deleting);
 
                     cx.style.needs_text_update(cx.current);
                 }
             } else if let Some(text) = cx.style.text.get_mut(cx.current) {
                 if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                    let to_delete = apply_movement(movement, self.selection, text, paragraph, true);
-                    self.selection = to_delete;
-                    let new_cursor_pos = self.selection.min();
-                    text.edit(to_delete.range(), "");
-                    self.selection = Selection::caret(new_cursor_pos);
+                    let to_delete = match mask {
+                        Some(mask) => apply_masked_movement(
+                            movement,
+                            self.selection,
+                            text,
+                            mask,
+                            paragraph,
+                            true,
+                        ),
+                        None => apply_movement(movement, self.selection, text, paragraph, true),
+                    };
+                    self.replace_range(text, to_delete.range(), "", EditKind::Deleting);
                     cx.style.needs_text_update(cx.current);
                 }
             }
         } else if let Some(text) = cx.style.text.get_mut(cx.current) {
             let del_range = self.selection.range();
-            self.selection = Selection::caret(del_range.start);
-
-            text.edit(del_range, "");
+            self.replace_range(text, del_range, "", EditKind::Other);
 
             cx.style.needs_text_update(cx.current);
         }
@@ -266,11 +342,93 @@ where
         }
     }
 
+    /// Reverts the most recent edit, or reapplies the most recently undone edit if `redo` is true.
+    fn restore_history(&mut self, cx: &mut EventContext, redo: bool) {
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            if self.show_placeholder {
+                text.clear();
+            }
+
+            let selection = if redo { self.history.redo(text) } else { self.history.undo(text) };
+            if let Some(selection) = selection {
+                self.selection = selection;
+            }
+
+            self.show_placeholder = text.is_empty();
+            if self.show_placeholder {
+                *text = self.placeholder.clone();
+                self.selection = Selection::caret(0);
+            }
+
+            cx.style.needs_text_update(cx.current);
+        }
+    }
+
+    /// Validates the edited text and passes it to the `on_edit` callback.
+    fn after_edit(&mut self, cx: &mut EventContext) {
+        let text = self.clone_text(cx);
+        self.edited_text.clone_from(&text);
+
+        if let Ok(value) = &text.parse::<L::Target>() {
+            if let Some(validate) = &self.validate {
+                cx.set_valid(validate(value));
+            } else {
+                cx.set_valid(true);
+            }
+        } else {
+            cx.set_valid(false);
+        }
+
+        if self.edit {
+            if let Some(callback) = &self.on_edit {
+                (callback)(cx, text);
+            }
+        }
+    }
+
+    // Word boundaries would reveal the hidden text of a secure textbox, so word movements instead move to the start
+    // or end of the text.
+    fn hide_word_boundaries(&self, movement: Movement) -> Movement {
+        match movement {
+            Movement::Word(Direction::Left | Direction::Upstream) if self.secure => {
+                Movement::Vertical(VerticalMovement::DocumentStart)
+            }
+            Movement::Word(Direction::Right | Direction::Downstream) if self.secure => {
+                Movement::Vertical(VerticalMovement::DocumentEnd)
+            }
+            movement => movement,
+        }
+    }
+
+    // The mask character which replaces each grapheme of the laid out text, if any. The placeholder is never masked.
+    fn text_mask(&self, cx: &EventContext) -> Option<char> {
+        cx.style.text_mask.get(cx.current).copied().filter(|_| !self.show_placeholder)
+    }
+
     fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        self.history.break_group();
+        let movement = self.hide_word_boundaries(movement);
+        let mask = self.text_mask(cx);
         if let Some(text) = cx.style.text.get_mut(cx.current) {
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                let new_selection =
-                    apply_movement(movement, self.selection, text, paragraph, selection);
+                let mut new_selection = match mask {
+                    Some(mask) => apply_masked_movement(
+                        movement,
+                        self.selection,
+                        text,
+                        mask,
+                        paragraph,
+                        selection,
+                    ),
+                    None => apply_movement(movement, self.selection, text, paragraph, selection),
+                };
+                if let (Some(mask), Movement::Grapheme(_)) = (&self.mask, movement) {
+                    let upstream = new_selection.active < self.selection.active;
+                    new_selection.active = mask.skip_literals(text, new_selection.active, upstream);
+                    if !selection {
+                        new_selection.anchor = new_selection.active;
+                    }
+                }
                 self.selection = new_selection;
                 cx.needs_redraw();
             }
@@ -278,6 +436,7 @@ where
     }
 
     fn select_all(&mut self, cx: &mut EventContext) {
+        self.history.break_group();
         if let Some(text) = cx.style.text.get(cx.current) {
             self.selection.anchor = 0;
             self.selection.active = text.len();
@@ -354,19 +513,12 @@ where
 
     /// This function takes window-global physical coordinates.
     fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
+        self.history.break_group();
         if let Some(text) = cx.style.text.get(cx.current) {
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
                 let gp = paragraph
                     .get_glyph_position_at_coordinate(self.coordinates_global_to_text(cx, x, y));
-                let num_graphemes = text.graphemes(true).count();
-                let pos = (gp.position as usize).min(num_graphemes);
-                let mut cursor = text.len();
-                for (i, (j, _)) in text.grapheme_indices(true).enumerate() {
-                    if pos == i {
-                        cursor = j;
-                        break;
-                    }
-                }
+                let cursor = text_offset(paragraph, text, gp.position as usize);
 
                 if selection {
                     self.selection.active = cursor;
@@ -385,16 +537,7 @@ where
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
                 let gp = paragraph
                     .get_glyph_position_at_coordinate(self.coordinates_global_to_text(cx, x, y));
-                let num_graphemes = text.graphemes(true).count();
-                let pos = (gp.position as usize).min(num_graphemes);
-
-                let mut cursor = text.len();
-                for (i, (j, _)) in text.grapheme_indices(true).enumerate() {
-                    if pos == i {
-                        cursor = j;
-                        break;
-                    }
-                }
+                let cursor = text_offset(paragraph, text, gp.position as usize);
 
                 self.selection.active = cursor;
 
@@ -422,12 +565,65 @@ where
         }
 
         if let Some(text) = cx.style.text.get(cx.current) {
-            text.clone()
+            let mut text = text.clone();
+            if let Some(preedit) = &self.preedit {
+                text.replace_range(preedit.clone(), "");
+            }
+            text
         } else {
             String::new()
         }
     }
 
+    fn set_preedit(
+        &mut self,
+        cx: &mut EventContext,
+        preedit: &str,
+        cursor: Option<(usize, usize)>,
+    ) {
+        if self.preedit.is_none() && !preedit.is_empty() {
+            // Composition replaces the selected text.
+            if !self.selection.is_caret() {
+                self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
+            }
+        }
+
+        self.remove_preedit(cx);
+
+        if preedit.is_empty() {
+            return;
+        }
+
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            if self.show_placeholder {
+                text.clear();
+                self.show_placeholder = false;
+            }
+
+            let start = self.selection.min();
+            text.edit(start..start, preedit);
+            self.preedit = Some(start..start + preedit.len());
+            self.selection = Selection::caret(
+                start + cursor.map_or(preedit.len(), |(_, end)| end.min(preedit.len())),
+            );
+            cx.style.needs_text_update(cx.current);
+        }
+    }
+
+    fn remove_preedit(&mut self, cx: &mut EventContext) {
+        if let Some(preedit) = self.preedit.take() {
+            if let Some(text) = cx.style.text.get_mut(cx.current) {
+                self.selection = Selection::caret(preedit.start);
+                text.edit(preedit, "");
+                self.show_placeholder = text.is_empty();
+                if self.show_placeholder {
+                    *text = self.placeholder.clone();
+                }
+                cx.style.needs_text_update(cx.current);
+            }
+        }
+    }
+
     fn reset_caret_timer(&mut self, cx: &mut EventContext) {
         cx.stop_timer(self.caret_timer);
         if !cx.is_read_only() {
@@ -438,10 +634,11 @@ where
 
     fn draw_selection(&self, cx: &mut DrawContext, canvas: &Canvas) {
         if !self.selection.is_caret() {
+            let mut selection_rects = Vec::new();
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
                 if let Some(text) = cx.style.text.get(cx.current) {
-                    let min = text.current_grapheme_offset(self.selection.min());
-                    let max = text.current_grapheme_offset(self.selection.max());
+                    let min = paragraph_position(paragraph, text, self.selection.min());
+                    let max = paragraph_position(paragraph, text, self.selection.max());
 
                     let cursor_rects = paragraph.get_rects_for_range(
                         min..max,
@@ -449,65 +646,32 @@ where
                         RectWidthStyle::Tight,
                     );
 
+                    let (origin_x, origin_y) = cx.text_origin(paragraph.height());
+
+                    let mut paint = Paint::default();
+                    paint.set_anti_alias(true);
+                    paint.set_style(PaintStyle::Fill);
+                    paint.set_color(cx.selection_color());
+
                     for cursor_rect in cursor_rects {
-                        let bounds = cx.bounds();
-
-                        let alignment = cx.alignment();
-
-                        let (mut top, left) = match alignment {
-                            Alignment::TopLeft => (0.0, 0.0),
-                            Alignment::TopCenter => (0.0, 0.5),
-                            Alignment::TopRight => (0.0, 1.0),
-                            Alignment::Left => (0.5, 0.0),
-                            Alignment::Center => (0.5, 0.5),
-                            Alignment::Right => (0.5, 1.0),
-                            Alignment::BottomLeft => (1.0, 0.0),
-                            Alignment::BottomCenter => (1.0, 0.5),
-                            Alignment::BottomRight => (1.0, 1.0),
-                        };
-
-                        let padding_top = match cx.padding_top() {
-                            Units::Pixels(val) => val,
-                            _ => 0.0,
-                        };
-
-                        let padding_bottom = match cx.padding_bottom() {
-                            Units::Pixels(val) => val,
-                            _ => 0.0,
-                        };
-
-                        top *= bounds.height() - padding_top - padding_bottom - paragraph.height();
-
-                        let padding_left = match cx.padding_left() {
-                            Units::Pixels(val) => val,
-                            _ => 0.0,
-                        };
-
-                        let x = bounds.x + padding_left + cursor_rect.rect.left + left;
-                        let y = bounds.y + padding_top + cursor_rect.rect.top + top;
-
-                        let x2 = x + (cursor_rect.rect.right - cursor_rect.rect.left);
-                        let y2 = y + (cursor_rect.rect.bottom - cursor_rect.rect.top);
-
-                        let mut paint = Paint::default();
-                        paint.set_anti_alias(true);
-                        paint.set_style(PaintStyle::Fill);
-                        paint.set_color(cx.selection_color());
-
-                        canvas.draw_rect(Rect::new(x, y, x2, y2), &paint);
+                        let rect = cursor_rect.rect.with_offset((origin_x, origin_y));
+                        canvas.draw_rect(rect, &paint);
+                        selection_rects.push(rect);
                     }
                 }
             }
+
+            cx.draw_selected_text(canvas, &selection_rects);
         }
     }
 
     /// Draw text caret for the current view.
     pub fn draw_text_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let mut caret_area = None;
+
         if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
             if let Some(text) = cx.style.text.get(cx.current) {
-                let bounds = cx.bounds();
-
-                let current = text.current_grapheme_offset(self.selection.active);
+                let current = paragraph_position(paragraph, text, self.selection.active);
 
                 let rects = paragraph.get_rects_for_range(
                     current..current + 1,
@@ -517,39 +681,10 @@ where
 
                 let cursor_rect = rects.first().unwrap();
 
-                let alignment = cx.alignment();
-
-                let (mut top, _) = match alignment {
-                    Alignment::TopLeft => (0.0, 0.0),
-                    Alignment::TopCenter => (0.0, 0.5),
-                    Alignment::TopRight => (0.0, 1.0),
-                    Alignment::Left => (0.5, 0.0),
-                    Alignment::Center => (0.5, 0.5),
-                    Alignment::Right => (0.5, 1.0),
-                    Alignment::BottomLeft => (1.0, 0.0),
-                    Alignment::BottomCenter => (1.0, 0.5),
-                    Alignment::BottomRight => (1.0, 1.0),
-                };
+                let (origin_x, origin_y) = cx.text_origin(paragraph.height());
 
-                let padding_top = match cx.padding_top() {
-                    Units::Pixels(val) => val,
-                    _ => 0.0,
-                };
-
-                let padding_bottom = match cx.padding_bottom() {
-                    Units::Pixels(val) => val,
-                    _ => 0.0,
-                };
-
-                top *= bounds.height() - padding_top - padding_bottom - paragraph.height();
-
-                let padding_left = match cx.padding_left() {
-                    Units::Pixels(val) => val,
-                    _ => 0.0,
-                };
-
-                let x = (bounds.x + padding_left + cursor_rect.rect.left).round();
-                let y = (bounds.y + padding_top + cursor_rect.rect.top + top).round();
+                let x = (origin_x + cursor_rect.rect.left).round();
+                let y = (origin_y + cursor_rect.rect.top).round();
 
                 let x2 = x + 1.0;
                 let y2 = y + (cursor_rect.rect.bottom - cursor_rect.rect.top);
@@ -560,6 +695,53 @@ where
                 paint.set_color(cx.caret_color());
 
                 canvas.draw_rect(Rect::new(x, y, x2, y2), &paint);
+
+                caret_area = Some(BoundingBox { x, y, w: x2 - x, h: y2 - y });
+            }
+        }
+
+        // The caret is only known after the text has been laid out, so the input method is told where it is from
+        // here whenever it moves.
+        if let Some(area) = caret_area {
+            if self.ime_cursor_area.get() != Some(area) {
+                self.ime_cursor_area.set(Some(area));
+                cx.emit(WindowEvent::SetImeCursorArea(area));
+            }
+        }
+    }
+
+    /// Draw an underline beneath the preedit text of an input method composition.
+    fn draw_preedit(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some(preedit) = &self.preedit else {
+            return;
+        };
+
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            if let Some(text) = cx.style.text.get(cx.current) {
+                let min = paragraph_position(paragraph, text, preedit.start);
+                let max = paragraph_position(paragraph, text, preedit.end);
+
+                let rects = paragraph.get_rects_for_range(
+                    min..max,
+                    RectHeightStyle::Tight,
+                    RectWidthStyle::Tight,
+                );
+
+                let (origin_x, origin_y) = cx.text_origin(paragraph.height());
+
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(cx.font_color());
+
+                let thickness = cx.scale_factor().round().max(1.0);
+
+                for rect in rects {
+                    let x = origin_x + rect.rect.left;
+                    let y = (origin_y + rect.rect.bottom).round();
+
+                    canvas.draw_rect(Rect::new(x, y - thickness, x + rect.rect.width(), y), &paint);
+                }
             }
         }
     }
@@ -625,6 +807,29 @@ impl<L: Lens> Handle<'_, Textbox<L>> {
 
         self
     }
+
+    /// Sets whether the textbox is in secure mode, for entering passwords.
+    ///
+    /// A secure textbox displays its text as bullets, does not allow its text to be copied or cut to the clipboard,
+    /// and is exposed to assistive technologies as a password input.
+    pub fn secure(self, secure: impl Res<bool>) -> Self {
+        secure.set_or_bind(self.cx, self.entity, |cx, val| {
+            cx.emit(TextEvent::SetSecure(val.get(cx)));
+        });
+
+        self
+    }
+
+    /// Restricts the text of the textbox to a pattern, such as `####-####` for a licence key or `##/##/####` for a
+    /// date.
+    ///
+    /// In the pattern, `#` accepts a digit, `A` accepts a letter, and `*` accepts a letter or digit. Any other
+    /// character is fixed, and is inserted automatically and skipped over by the caret. A fixed `#`, `A` or `*` can
+    /// be written by escaping it with `\`. Typed or pasted characters which do not fit the pattern are rejected.
+    pub fn input_mask(self, pattern: &str) -> Self {
+        let mask = InputMask::new(pattern);
+        self.modify(|textbox| textbox.mask = Some(mask))
+    }
 }
 
 impl<L> View for Textbox<L>
@@ -650,7 +855,7 @@ where
         let mut _current_cursor = 0;
         let mut _prev_line_index = usize::MAX;
 
-        if let Some(_text) = cx.style.text.get(cx.current) {
+        if let Some(text) = cx.style.text.get(cx.current) {
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
                 let line_metrics = paragraph.get_line_metrics();
                 for line in line_metrics.iter() {
@@ -680,7 +885,9 @@ where
 
                     // let mut line_length = 0;
 
-                    let mut glyph_pos = line.start_index;
+                    // Line metrics count UTF-16 code units, while glyph clusters are looked up by byte offset.
+                    let mut glyph_pos = text_offset_from_utf16(paragraph, text, line.start_index)
+                        + text_indent_offset(paragraph);
 
                     for _ in line.start_index..line.end_index {
                         if let Some(cluster_info) = paragraph.get_glyph_cluster_at(glyph_pos) {
@@ -879,6 +1086,33 @@ where
                 }
             }
 
+            WindowEvent::ImeEnabled => {
+                if self.edit {
+                    // The caret area is sent to the new input method on the next draw.
+                    self.ime_cursor_area.set(None);
+                    cx.needs_redraw();
+                }
+            }
+
+            WindowEvent::ImePreedit(text, cursor) => {
+                if self.edit && !cx.is_read_only() {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::SetPreedit(text.clone(), *cursor));
+                }
+            }
+
+            WindowEvent::ImeCommit(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::SetPreedit(String::new(), None));
+                    cx.emit(TextEvent::InsertText(text.clone()));
+                }
+            }
+
+            WindowEvent::ImeDisabled => {
+                cx.emit(TextEvent::SetPreedit(String::new(), None));
+            }
+
             WindowEvent::KeyDown(code, _) => match code {
                 Code::Enter => {
                     if matches!(self.kind, TextboxKind::SingleLine) {
@@ -1039,6 +1273,26 @@ where
                     }
                 }
 
+                Code::KeyZ => {
+                    #[cfg(target_os = "macos")]
+                    let modifier = Modifiers::SUPER;
+                    #[cfg(not(target_os = "macos"))]
+                    let modifier = Modifiers::CTRL;
+
+                    if cx.modifiers == &modifier {
+                        cx.emit(TextEvent::Undo);
+                    } else if cx.modifiers == &(modifier | Modifiers::SHIFT) {
+                        cx.emit(TextEvent::Redo);
+                    }
+                }
+
+                #[cfg(not(target_os = "macos"))]
+                Code::KeyY => {
+                    if cx.modifiers == &Modifiers::CTRL {
+                        cx.emit(TextEvent::Redo);
+                    }
+                }
+
                 _ => {}
             },
 
@@ -1107,24 +1361,7 @@ where
                 }
 
                 self.insert_text(cx, text);
-
-                let text = self.clone_text(cx);
-
-                if let Ok(value) = &text.parse::<L::Target>() {
-                    if let Some(validate) = &self.validate {
-                        cx.set_valid(validate(value));
-                    } else {
-                        cx.set_valid(true);
-                    }
-                } else {
-                    cx.set_valid(false);
-                }
-
-                if self.edit {
-                    if let Some(callback) = &self.on_edit {
-                        (callback)(cx, text);
-                    }
-                }
+                self.after_edit(cx);
             }
 
             TextEvent::Clear => {
@@ -1137,22 +1374,7 @@ where
             TextEvent::DeleteText(movement) => {
                 if self.edit {
                     self.delete_text(cx, *movement);
-
-                    let text = self.clone_text(cx);
-
-                    if let Ok(value) = &text.parse::<L::Target>() {
-                        if let Some(validate) = &self.validate {
-                            cx.set_valid(validate(value));
-                        } else {
-                            cx.set_valid(true);
-                        }
-                    } else {
-                        cx.set_valid(false);
-                    }
-
-                    if let Some(callback) = &self.on_edit {
-                        (callback)(cx, text);
-                    }
+                    self.after_edit(cx);
                 }
             }
 
@@ -1164,9 +1386,24 @@ where
 
             TextEvent::SetPlaceholder(text) => self.placeholder.clone_from(text),
 
+            TextEvent::SetSecure(secure) => {
+                self.secure = *secure;
+                if self.secure {
+                    cx.style.text_mask.insert(cx.current, '•');
+                    cx.set_role(Role::PasswordInput);
+                } else {
+                    cx.style.text_mask.remove(cx.current);
+                    cx.set_role(Role::TextInput);
+                }
+
+                cx.style.needs_text_update(cx.current);
+                cx.style.needs_access_update(cx.current);
+            }
+
             TextEvent::StartEdit => {
                 if !cx.is_disabled() && !self.edit {
                     self.edit = true;
+                    self.ime_cursor_area.set(None);
                     cx.focus_with_visibility(false);
                     cx.capture();
                     cx.set_checked(true);
@@ -1195,6 +1432,7 @@ where
             }
 
             TextEvent::EndEdit => {
+                self.remove_preedit(cx);
                 self.deselect();
                 self.edit = false;
                 cx.set_checked(false);
@@ -1204,6 +1442,11 @@ where
                 let text = self.lens.get(cx);
                 let text = text.to_string_local(cx);
 
+                // The text is reset to the bound data, which invalidates the history if the edits were not submitted.
+                if text != self.edited_text {
+                    self.history.clear();
+                }
+
                 self.select_all(cx);
 
                 if let Ok(value) = &text.parse::<L::Target>() {
@@ -1277,7 +1520,7 @@ where
             TextEvent::Copy =>
             {
                 #[cfg(feature = "clipboard")]
-                if self.edit {
+                if self.edit && !self.secure {
                     if let Some(selected_text) = self.clone_selected(cx) {
                         if !selected_text.is_empty() {
                             cx.set_clipboard(selected_text)
@@ -1300,28 +1543,13 @@ where
             TextEvent::Cut =>
             {
                 #[cfg(feature = "clipboard")]
-                if self.edit {
+                if self.edit && !self.secure {
                     if let Some(selected_text) = self.clone_selected(cx) {
                         if !selected_text.is_empty() {
                             cx.set_clipboard(selected_text)
                                 .expect("Failed to add text to clipboard");
                             self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
-
-                            let text = self.clone_text(cx);
-
-                            if let Ok(value) = &text.parse::<L::Target>() {
-                                if let Some(validate) = &self.validate {
-                                    cx.set_valid(validate(value));
-                                } else {
-                                    cx.set_valid(true);
-                                }
-                            } else {
-                                cx.set_valid(false);
-                            }
-
-                            if let Some(callback) = &self.on_edit {
-                                (callback)(cx, text);
-                            }
+                            self.after_edit(cx);
                         }
                     }
                 }
@@ -1330,6 +1558,19 @@ where
             TextEvent::ToggleCaret => {
                 self.show_caret ^= true;
             }
+
+            TextEvent::SetPreedit(text, cursor) => {
+                if self.edit {
+                    self.set_preedit(cx, text, *cursor);
+                }
+            }
+
+            TextEvent::Undo | TextEvent::Redo => {
+                if self.edit && !cx.is_read_only() {
+                    self.restore_history(cx, matches!(text_event, TextEvent::Redo));
+                    self.after_edit(cx);
+                }
+            }
         });
     }
 
@@ -1345,6 +1586,7 @@ where
         cx.draw_text(canvas);
         if self.edit {
             self.draw_selection(cx, canvas);
+            self.draw_preedit(cx, canvas);
             self.draw_text_caret(cx, canvas);
         }
         // canvas.restore();
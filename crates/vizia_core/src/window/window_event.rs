@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use crate::{entity::Entity, environment::ThemeMode, layout::cache::GeoChanged};
+use crate::{
+    entity::Entity,
+    environment::ThemeMode,
+    layout::{cache::GeoChanged, BoundingBox},
+};
 use vizia_input::{Code, Key, MouseButton};
 use vizia_style::CursorIcon;
 use vizia_window::{WindowPosition, WindowSize};
@@ -74,6 +78,15 @@ pub enum WindowEvent {
     WindowFocused(bool),
     /// Emitted when a character is typed.
     CharInput(char),
+    /// Emitted when an input method is enabled for the window.
+    ImeEnabled,
+    /// Emitted when an input method updates the text being composed, along with the byte range of the cursor within
+    /// the text, or `None` if the cursor should be hidden. An empty string clears the composed text.
+    ImePreedit(String, Option<(usize, usize)>),
+    /// Emitted when an input method commits the composed text, which replaces any preedit text.
+    ImeCommit(String),
+    /// Emitted when an input method is disabled for the window.
+    ImeDisabled,
     /// Emitted when a keyboard key is pressed.
     KeyDown(Code, Option<Key>),
     /// Emitted when a keyboard key is released.
@@ -86,6 +99,9 @@ pub enum WindowEvent {
     GrabCursor(bool),
     /// Sets the (x,y) position of the mouse cursor in window coordinates.
     SetCursorPosition(u32, u32),
+    /// Sets the area of the text caret in physical window coordinates, which the input method uses to position its
+    /// candidate window.
+    SetImeCursorArea(BoundingBox),
     /// Sets the title of the window.
     SetTitle(String),
     /// Sets the size of the window.
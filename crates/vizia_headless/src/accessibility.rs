@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use accesskit::{Action, Node, NodeId, Toggled, TreeUpdate};
+
+use crate::snapshot::sibling_path;
+use crate::UPDATE_SNAPSHOTS_VAR;
+
+/// The actions which are listed for each node of an accessibility snapshot, in the order they are listed.
+const SNAPSHOT_ACTIONS: [Action; 17] = [
+    Action::Click,
+    Action::Focus,
+    Action::Blur,
+    Action::Collapse,
+    Action::Expand,
+    Action::Increment,
+    Action::Decrement,
+    Action::SetValue,
+    Action::ReplaceSelectedText,
+    Action::SetTextSelection,
+    Action::ShowContextMenu,
+    Action::ScrollIntoView,
+    Action::ScrollUp,
+    Action::ScrollDown,
+    Action::ScrollLeft,
+    Action::ScrollRight,
+    Action::ShowTooltip,
+];
+
+/// A copy of the accessibility tree of an application, built by applying the tree updates produced by vizia.
+///
+/// The tree can be written out as a human-readable snapshot with [`snapshot()`](Self::snapshot), which lists
+/// the role, name, value, states and actions of each node reachable from the root, indented by depth:
+///
+/// ```text
+/// Window
+///   Button label="Submit" actions=[Click, Focus]
+///     Label value="Submit"
+///   Slider numeric_value=0.5 range=0..1 states=[focused] actions=[Focus, Increment, Decrement, SetValue]
+/// ```
+///
+/// Bounds are not included, so snapshots only change when the semantics of the tree change.
+#[derive(Debug, Default, Clone)]
+pub struct AccessibilityTree {
+    nodes: HashMap<NodeId, Node>,
+    root: Option<NodeId>,
+    focus: Option<NodeId>,
+}
+
+impl AccessibilityTree {
+    /// Creates an empty accessibility tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a tree update, replacing any nodes it contains.
+    pub fn update(&mut self, update: TreeUpdate) {
+        if let Some(tree) = update.tree {
+            self.root = Some(tree.root);
+        }
+
+        self.focus = Some(update.focus);
+        self.nodes.extend(update.nodes);
+    }
+
+    /// Returns the node with the given id, if it is part of the tree.
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(&id)
+    }
+
+    /// Writes the tree out in the snapshot format.
+    pub fn snapshot(&self) -> String {
+        let mut output = String::new();
+
+        if let Some(root) = self.root {
+            self.write_node(&mut output, root, 0);
+        }
+
+        output
+    }
+
+    fn write_node(&self, output: &mut String, id: NodeId, depth: usize) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
+
+        let _ = write!(output, "{:indent$}{:?}", "", node.role(), indent = depth * 2);
+
+        if let Some(label) = node.label() {
+            let _ = write!(output, " label={label:?}");
+        }
+
+        if let Some(value) = node.value() {
+            let _ = write!(output, " value={value:?}");
+        }
+
+        if let Some(numeric_value) = node.numeric_value() {
+            let _ = write!(output, " numeric_value={numeric_value}");
+        }
+
+        if let (Some(min), Some(max)) = (node.min_numeric_value(), node.max_numeric_value()) {
+            let _ = write!(output, " range={min}..{max}");
+        }
+
+        if !node.labelled_by().is_empty() {
+            let names = node
+                .labelled_by()
+                .iter()
+                .map(|id| format!("{:?}", self.display_name(*id)))
+                .collect::<Vec<_>>();
+            let _ = write!(output, " labelled_by=[{}]", names.join(", "));
+        }
+
+        let states = self.states(id, node);
+        if !states.is_empty() {
+            let _ = write!(output, " states=[{}]", states.join(", "));
+        }
+
+        let actions = SNAPSHOT_ACTIONS
+            .iter()
+            .filter(|action| node.supports_action(**action))
+            .map(|action| format!("{action:?}"))
+            .collect::<Vec<_>>();
+        if !actions.is_empty() {
+            let _ = write!(output, " actions=[{}]", actions.join(", "));
+        }
+
+        output.push('\n');
+
+        for child in node.children() {
+            self.write_node(output, *child, depth + 1);
+        }
+    }
+
+    fn states(&self, id: NodeId, node: &Node) -> Vec<&'static str> {
+        let mut states = Vec::new();
+
+        if self.focus == Some(id) {
+            states.push("focused");
+        }
+
+        if node.is_disabled() {
+            states.push("disabled");
+        }
+
+        if node.is_hidden() {
+            states.push("hidden");
+        }
+
+        if node.is_read_only() {
+            states.push("read_only");
+        }
+
+        match node.toggled() {
+            Some(Toggled::True) => states.push("checked"),
+            Some(Toggled::False) => states.push("unchecked"),
+            Some(Toggled::Mixed) => states.push("mixed"),
+            None => {}
+        }
+
+        match node.is_selected() {
+            Some(true) => states.push("selected"),
+            Some(false) => states.push("unselected"),
+            None => {}
+        }
+
+        match node.is_expanded() {
+            Some(true) => states.push("expanded"),
+            Some(false) => states.push("collapsed"),
+            None => {}
+        }
+
+        states
+    }
+
+    // Returns a name for a node which is stable between runs, used when one node refers to another.
+    fn display_name(&self, id: NodeId) -> String {
+        match self.nodes.get(&id) {
+            Some(node) => node
+                .label()
+                .or_else(|| node.value())
+                .map(ToString::to_string)
+                .unwrap_or_else(|| format!("{:?}", node.role())),
+            None => String::from("missing"),
+        }
+    }
+}
+
+/// Asserts that an accessibility snapshot matches the reference file at the given path.
+///
+/// If the `VIZIA_UPDATE_SNAPSHOTS` environment variable is set, the snapshot is written to the path instead. If the
+/// reference does not exist, or on a mismatch, the snapshot is written next to the reference with an `.actual`
+/// extension before panicking, along with the differing lines for a mismatch.
+#[track_caller]
+pub fn assert_accessibility_snapshot(snapshot: &str, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        std::fs::write(path, snapshot)
+            .unwrap_or_else(|err| panic!("Failed to write snapshot {}: {}", path.display(), err));
+        return;
+    }
+
+    if !path.exists() {
+        let actual_path = sibling_path(path, "actual");
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(&actual_path, snapshot);
+
+        panic!(
+            "Reference snapshot {} does not exist, actual snapshot written to {}. Set {} to create it",
+            path.display(),
+            actual_path.display(),
+            UPDATE_SNAPSHOTS_VAR,
+        );
+    }
+
+    let reference = std::fs::read_to_string(path).unwrap_or_else(|err| {
+        panic!("Failed to load reference snapshot {}: {}", path.display(), err)
+    });
+
+    // Ignore line ending differences introduced by version control.
+    let reference = reference.replace("\r\n", "\n");
+    if reference == snapshot {
+        return;
+    }
+
+    let actual_path = sibling_path(path, "actual");
+    let _ = std::fs::write(&actual_path, snapshot);
+
+    panic!(
+        "Accessibility snapshot does not match reference {}, actual snapshot written to {}\n{}",
+        path.display(),
+        actual_path.display(),
+        line_diff(&reference, snapshot),
+    );
+}
+
+// Lists the lines which differ between two snapshots, prefixed with `-` for expected and `+` for actual lines.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    let mut diff = String::new();
+    for line in 0..expected.len().max(actual.len()) {
+        let (expected, actual) = (expected.get(line), actual.get(line));
+        if expected == actual {
+            continue;
+        }
+
+        let _ = writeln!(diff, "line {}:", line + 1);
+        if let Some(expected) = expected {
+            let _ = writeln!(diff, "-{expected}");
+        }
+        if let Some(actual) = actual {
+            let _ = writeln!(diff, "+{actual}");
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesskit::{Role, Tree};
+
+    #[test]
+    fn snapshot_lists_nodes_by_depth() {
+        let mut root = Node::new(Role::Window);
+        root.set_children(vec![NodeId(1), NodeId(2)]);
+
+        let mut label = Node::new(Role::Label);
+        label.set_value("Volume");
+
+        let mut slider = Node::new(Role::Slider);
+        slider.set_numeric_value(0.5);
+        slider.set_min_numeric_value(0.0);
+        slider.set_max_numeric_value(1.0);
+        slider.set_labelled_by(vec![NodeId(1)]);
+        slider.add_action(Action::Focus);
+        slider.add_action(Action::Increment);
+
+        let mut tree = AccessibilityTree::new();
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(0), root), (NodeId(1), label), (NodeId(2), slider)],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(2),
+        });
+
+        assert_eq!(
+            tree.snapshot(),
+            "Window\n  Label value=\"Volume\"\n  Slider numeric_value=0.5 range=0..1 labelled_by=[\"Volume\"] states=[focused] actions=[Focus, Increment]\n"
+        );
+
+        let mut checkbox = Node::new(Role::CheckBox);
+        checkbox.set_toggled(Toggled::True);
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), checkbox)],
+            tree: None,
+            focus: NodeId(0),
+        });
+
+        assert_eq!(
+            tree.snapshot(),
+            "Window states=[focused]\n  Label value=\"Volume\"\n  CheckBox states=[checked]\n"
+        );
+    }
+
+    #[test]
+    fn line_diff_shows_changed_lines() {
+        assert_eq!(line_diff("a\nb\nc", "a\nx\nc\nd"), "line 2:\n-b\n+x\nline 4:\n+d\n");
+    }
+}
@@ -0,0 +1,913 @@
+use std::path::Path;
+
+use skia_safe::{surfaces, Image, Surface};
+use vizia_core::backend::*;
+use vizia_core::events::EventManager;
+use vizia_core::prelude::*;
+
+use crate::proxy::{HeadlessProxy, ProxyQueue};
+use crate::{assert_accessibility_snapshot, AccessibilityTree, Snapshot};
+
+/// The amount the virtual clock is moved forward per frame when advancing time, roughly 60 frames per second.
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// The maximum number of frames stepped by [`HeadlessApplication::run_until_idle`] before giving up.
+const MAX_IDLE_FRAMES: usize = 1000;
+
+/// An application which runs without a window or GPU, for use in automated tests.
+///
+/// Instead of entering an event loop, the application is stepped on demand with [`step()`](Self::step),
+/// [`run_until_idle()`](Self::run_until_idle) and [`advance()`](Self::advance). Timers, scheduled events and
+/// animations are driven by a virtual clock which only moves when the application is advanced, and the main
+/// window is rendered into an in-memory raster surface.
+///
+/// The virtual clock is shared by all headless applications on the same thread.
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::prelude::*;
+/// # use vizia_headless::HeadlessApplication;
+/// let mut app = HeadlessApplication::new(|cx| {
+///     Label::new(cx, "Hello");
+/// })
+/// .inner_size((400, 300));
+///
+/// app.run_until_idle();
+/// app.advance(Duration::from_millis(500));
+///
+/// let image = app.image_snapshot();
+/// ```
+/// The family name of the font bundled with the headless backend.
+const DEFAULT_FONT: &str = "DejaVu Sans Mono";
+
+pub struct HeadlessApplication {
+    cx: BackendContext,
+    event_manager: EventManager,
+    proxy_queue: ProxyQueue,
+    window_description: WindowDescription,
+    on_idle: Option<Box<dyn Fn(&mut Context)>>,
+    surfaces: Option<(Surface, Surface)>,
+    accessibility_tree: Option<AccessibilityTree>,
+    should_redraw: bool,
+}
+
+/// A view for the main window of a headless application.
+pub struct HeadlessWindow {}
+
+impl View for HeadlessWindow {}
+
+impl HeadlessApplication {
+    pub fn new<F>(content: F) -> Self
+    where
+        F: FnOnce(&mut Context),
+    {
+        let context = Context::new();
+
+        let mut cx = BackendContext::new(context);
+        cx.set_virtual_clock(Instant::now());
+
+        let proxy_queue = ProxyQueue::default();
+        cx.set_event_proxy(Box::new(HeadlessProxy(proxy_queue.clone())));
+
+        // Text is rendered with the bundled font by default, so that snapshots do not depend on installed fonts.
+        cx.disable_system_fonts();
+        cx.context().add_font_mem(include_bytes!("../resources/fonts/DejaVuSansMono.ttf"));
+        cx.context().set_default_font(&[DEFAULT_FONT]);
+
+        cx.renegotiate_language();
+        cx.0.remove_user_themes();
+        (content)(cx.context());
+
+        Self {
+            cx,
+            event_manager: EventManager::new(),
+            proxy_queue,
+            window_description: WindowDescription::new(),
+            on_idle: None,
+            surfaces: None,
+            accessibility_tree: None,
+            should_redraw: true,
+        }
+    }
+
+    /// Sets the default built-in theming to be ignored.
+    pub fn ignore_default_theme(mut self) -> Self {
+        self.cx.context().ignore_default_theme = true;
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.window_description.title = title.to_owned();
+
+        self
+    }
+
+    pub fn inner_size(mut self, size: impl Into<WindowSize>) -> Self {
+        self.window_description.inner_size = size.into();
+
+        self
+    }
+
+    /// Sets the scale factor used to render the window, defaults to 1.0.
+    pub fn user_scale_factor(mut self, factor: f64) -> Self {
+        self.window_description.user_scale_factor = factor;
+
+        self
+    }
+
+    /// Registers a font and makes it the default font used to render text, instead of the bundled DejaVu Sans Mono.
+    ///
+    /// Fonts installed on the system are never used, so that rendering is deterministic across machines, which is
+    /// required for comparing snapshots.
+    pub fn font(mut self, family_name: &str, data: impl AsRef<[u8]>) -> Self {
+        self.cx.context().add_font_mem(data);
+        self.cx.context().set_default_font(&[family_name]);
+
+        self
+    }
+
+    /// Takes a closure which will be called at the end of every step of the application.
+    pub fn on_idle<I: 'static + Fn(&mut Context)>(mut self, callback: I) -> Self {
+        self.on_idle = Some(Box::new(callback));
+
+        self
+    }
+
+    /// Returns a mutable reference to the context of the application.
+    pub fn context(&mut self) -> &mut Context {
+        self.cx.context()
+    }
+
+    /// Returns a mutable reference to the backend context of the application.
+    pub fn backend_context(&mut self) -> &mut BackendContext {
+        &mut self.cx
+    }
+
+    /// Returns the current time of the virtual clock.
+    pub fn now(&self) -> Instant {
+        self.cx.now()
+    }
+
+    /// Sends an event to the application, which will be handled on the next step.
+    pub fn send_event(&mut self, event: Event) {
+        self.cx.send_event(event);
+    }
+
+    /// Sends a window event to the main window, which will be handled on the next step.
+    pub fn emit_window_event(&mut self, window_event: WindowEvent) {
+        self.cx.emit_window_event(Entity::root(), window_event);
+    }
+
+    /// Resizes the main window to the given logical size.
+    pub fn resize(&mut self, size: impl Into<WindowSize>) {
+        self.window_description.inner_size = size.into();
+
+        if self.surfaces.is_none() {
+            return;
+        }
+
+        if let Some(window_state) = self.cx.0.windows.get_mut(&Entity::root()) {
+            window_state.window_description.inner_size = self.window_description.inner_size;
+        }
+
+        let (physical_width, physical_height) = self.physical_size();
+        self.cx.set_window_size(Entity::root(), physical_width as f32, physical_height as f32);
+        self.surfaces = Some(create_surfaces(physical_width, physical_height));
+        self.cx.needs_refresh(Entity::root());
+        self.should_redraw = true;
+    }
+
+    /// Returns true if the main window has been asked to close.
+    pub fn should_close(&self) -> bool {
+        self.cx
+            .0
+            .windows
+            .get(&Entity::root())
+            .map(|window_state| window_state.should_close)
+            .unwrap_or(true)
+    }
+
+    /// Runs a single iteration of the event loop at the current time of the virtual clock.
+    ///
+    /// Any due timers and scheduled events are fired, queued events are dispatched, and then the style,
+    /// animation, layout and accessibility systems are run before the window is redrawn.
+    pub fn step(&mut self) {
+        self.init_window();
+
+        let proxied_events = std::mem::take(&mut *self.proxy_queue.lock().unwrap());
+        for event in proxied_events {
+            self.cx.send_event(event);
+        }
+
+        self.cx.process_timers();
+        self.cx.emit_scheduled_events();
+
+        self.event_manager.flush_events(self.cx.context(), |_| {});
+
+        self.cx.process_style_updates();
+
+        if self.cx.process_animations() {
+            self.should_redraw = true;
+        }
+
+        self.cx.process_visual_updates();
+
+        self.cx.process_tree_updates();
+        match &mut self.accessibility_tree {
+            Some(accessibility_tree) => {
+                for update in self.cx.0.tree_updates.iter_mut() {
+                    if let Some(update) = update.take() {
+                        accessibility_tree.update(update);
+                    }
+                }
+            }
+
+            // The full tree is built after the first layout so that it reflects the initial state of the views.
+            None => {
+                let mut accessibility_tree = AccessibilityTree::new();
+                accessibility_tree.update(self.cx.init_accessibility_tree());
+                self.accessibility_tree = Some(accessibility_tree);
+            }
+        }
+        self.cx.0.tree_updates.clear();
+
+        if let Some(idle_callback) = &self.on_idle {
+            self.cx.set_current(Entity::root());
+            (idle_callback)(self.cx.context());
+        }
+
+        if self.cx.0.windows.iter().any(|(_, window_state)| !window_state.redraw_list.is_empty()) {
+            self.should_redraw = true;
+        }
+
+        if self.should_redraw {
+            if let Some((surface, dirty_surface)) = &mut self.surfaces {
+                self.cx.draw(Entity::root(), surface, dirty_surface);
+            }
+            self.should_redraw = false;
+        }
+    }
+
+    /// Steps the application until there are no more events waiting to be handled.
+    ///
+    /// The virtual clock is not advanced, so running timers and animations will not progress.
+    pub fn run_until_idle(&mut self) {
+        for _ in 0..MAX_IDLE_FRAMES {
+            self.step();
+
+            if !self.has_pending_events() {
+                return;
+            }
+        }
+    }
+
+    /// Moves the virtual clock forward by the given duration, stepping the application once per frame.
+    pub fn advance(&mut self, duration: Duration) {
+        self.run_until_idle();
+
+        let mut remaining = duration;
+        while !remaining.is_zero() {
+            let delta = remaining.min(FRAME_INTERVAL);
+            self.cx.advance_clock(delta);
+            remaining -= delta;
+
+            self.run_until_idle();
+        }
+    }
+
+    /// Returns the surface the main window is rendered into.
+    pub fn surface(&mut self) -> &mut Surface {
+        self.init_window();
+        &mut self.surfaces.as_mut().unwrap().0
+    }
+
+    /// Returns an image of the last rendered frame of the main window.
+    pub fn image_snapshot(&mut self) -> Image {
+        self.surface().image_snapshot()
+    }
+
+    /// Renders any pending changes and returns a snapshot of the main window.
+    pub fn snapshot(&mut self) -> Snapshot {
+        self.run_until_idle();
+        Snapshot::from_image(&self.image_snapshot()).expect("Failed to read window pixels")
+    }
+
+    /// Renders any pending changes and returns a snapshot of the region of the main window covered by a view.
+    ///
+    /// Only the view and its descendants are drawn, onto a transparent background, so the snapshot does not
+    /// include the views behind or in front of it.
+    pub fn snapshot_view(&mut self, entity: Entity) -> Snapshot {
+        self.run_until_idle();
+
+        let (physical_width, physical_height) = self.physical_size();
+        let mut surface =
+            surfaces::raster_n32_premul((physical_width.max(1), physical_height.max(1)))
+                .expect("Could not create skia surface");
+
+        self.cx.draw_subtree(entity, surface.canvas());
+
+        let snapshot =
+            Snapshot::from_image(&surface.image_snapshot()).expect("Failed to read view pixels");
+        let bounds = self.cx.cache().get_bounds(entity);
+
+        snapshot.crop(
+            bounds.x.max(0.0).floor() as u32,
+            bounds.y.max(0.0).floor() as u32,
+            bounds.w.max(0.0).ceil() as u32,
+            bounds.h.max(0.0).ceil() as u32,
+        )
+    }
+
+    /// Handles any pending events and returns the accessibility tree of the application.
+    pub fn accessibility_tree(&mut self) -> &AccessibilityTree {
+        self.run_until_idle();
+        self.accessibility_tree.as_ref().expect("Accessibility tree is built on the first step")
+    }
+
+    /// Handles any pending events and returns a human-readable snapshot of the accessibility tree.
+    ///
+    /// See [`AccessibilityTree`] for a description of the format.
+    pub fn accessibility_snapshot(&mut self) -> String {
+        self.accessibility_tree().snapshot()
+    }
+
+    /// Asserts that the accessibility tree matches the reference snapshot file at the given path.
+    ///
+    /// See [`assert_accessibility_snapshot`] for how references are created and updated.
+    #[track_caller]
+    pub fn assert_accessibility_snapshot(&mut self, path: impl AsRef<Path>) {
+        let snapshot = self.accessibility_snapshot();
+        assert_accessibility_snapshot(&snapshot, path);
+    }
+
+    fn has_pending_events(&self) -> bool {
+        self.cx.has_queued_events() || !self.proxy_queue.lock().unwrap().is_empty()
+    }
+
+    fn physical_size(&self) -> (i32, i32) {
+        let scale_factor = self.window_description.user_scale_factor;
+        let physical_width = self.window_description.inner_size.width as f64 * scale_factor;
+        let physical_height = self.window_description.inner_size.height as f64 * scale_factor;
+
+        (physical_width.round() as i32, physical_height.round() as i32)
+    }
+
+    // Sets up the main window and its surfaces on the first step of the application.
+    fn init_window(&mut self) {
+        if self.surfaces.is_some() {
+            return;
+        }
+
+        let dpi_factor = self.window_description.user_scale_factor as f32;
+
+        self.cx.add_main_window(Entity::root(), &self.window_description, dpi_factor);
+        self.cx.add_window(HeadlessWindow {});
+
+        self.cx.0.windows.insert(
+            Entity::root(),
+            WindowState {
+                window_description: self.window_description.clone(),
+                scale_factor: dpi_factor,
+                ..Default::default()
+            },
+        );
+
+        self.cx.0.remove_user_themes();
+
+        let (physical_width, physical_height) = self.physical_size();
+        self.surfaces = Some(create_surfaces(physical_width, physical_height));
+    }
+}
+
+impl Drop for HeadlessApplication {
+    fn drop(&mut self) {
+        self.cx.clear_virtual_clock();
+    }
+}
+
+fn create_surfaces(width: i32, height: i32) -> (Surface, Surface) {
+    let mut surface = surfaces::raster_n32_premul((width.max(1), height.max(1)))
+        .expect("Could not create skia surface");
+
+    let dirty_surface = surface.new_surface_with_dimensions((width.max(1), height.max(1))).unwrap();
+
+    (surface, dirty_surface)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use skia_safe::{AlphaType, ColorType, ImageInfo};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn timers_follow_virtual_clock() {
+        let ticks = Rc::new(Cell::new(0));
+        let counter = ticks.clone();
+
+        let mut app = HeadlessApplication::new(move |cx| {
+            let timer = cx.add_timer(Duration::from_millis(100), None, move |_, action| {
+                if let TimerAction::Tick(_) = action {
+                    counter.set(counter.get() + 1);
+                }
+            });
+            cx.start_timer(timer);
+        });
+
+        app.run_until_idle();
+        assert_eq!(ticks.get(), 0);
+
+        app.advance(Duration::from_millis(350));
+        assert_eq!(ticks.get(), 3);
+    }
+
+    #[test]
+    fn renders_to_raster_surface() {
+        let mut app = HeadlessApplication::new(|cx| {
+            Element::new(cx).size(Pixels(10.0)).background_color(Color::rgb(255, 0, 0));
+        })
+        .inner_size((100, 100));
+
+        app.run_until_idle();
+
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixel = [0u8; 4];
+        assert!(app.surface().read_pixels(&info, &mut pixel, 4, (5, 5)));
+        assert_eq!(pixel, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn text_is_rendered_with_the_bundled_font() {
+        let mut app = HeadlessApplication::new(|cx| {
+            Label::new(cx, "Hello, vizia!")
+                .font_size(16.0)
+                .color(Color::black())
+                .background_color(Color::white());
+        })
+        .inner_size((120, 30))
+        .ignore_default_theme();
+
+        app.run_until_idle();
+
+        app.snapshot()
+            .assert_matches(concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots/bundled_font.png"), 2);
+    }
+
+    #[test]
+    fn snapshot_view_draws_only_the_subtree() {
+        let mut app = HeadlessApplication::new(|cx| {
+            Element::new(cx).id("red").size(Pixels(10.0)).background_color(Color::rgb(255, 0, 0));
+            Element::new(cx)
+                .position_type(PositionType::Absolute)
+                .left(Pixels(5.0))
+                .size(Pixels(10.0))
+                .background_color(Color::rgb(0, 0, 255));
+        })
+        .inner_size((100, 100));
+
+        let entity = app.context().query_first("#red").unwrap();
+        let snapshot = app.snapshot_view(entity);
+
+        assert_eq!((snapshot.width(), snapshot.height()), (10, 10));
+        assert!(snapshot.pixels().chunks_exact(4).all(|pixel| pixel == [255, 0, 0, 255]));
+        assert_eq!(app.snapshot().pixel(7, 5), Some([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn simulated_clicks_press_and_double_click() {
+        let presses = Arc::new(AtomicUsize::new(0));
+        let double_clicks = Arc::new(AtomicUsize::new(0));
+        let (press_counter, double_click_counter) = (presses.clone(), double_clicks.clone());
+
+        let mut app = HeadlessApplication::new(move |cx| {
+            Element::new(cx)
+                .size(Pixels(50.0))
+                .on_press(move |_| {
+                    press_counter.fetch_add(1, Ordering::SeqCst);
+                })
+                .on_double_click(move |_, _| {
+                    double_click_counter.fetch_add(1, Ordering::SeqCst);
+                });
+        })
+        .inner_size((100, 100));
+
+        app.run_until_idle();
+
+        app.context().simulate_mouse_move(25.0, 25.0);
+        app.context().simulate_click(MouseButton::Left);
+        app.run_until_idle();
+        assert_eq!(presses.load(Ordering::SeqCst), 1);
+        assert_eq!(double_clicks.load(Ordering::SeqCst), 0);
+
+        app.advance(Duration::from_secs(1));
+        app.context().simulate_double_click(MouseButton::Left);
+        app.run_until_idle();
+        assert_eq!(presses.load(Ordering::SeqCst), 3);
+        assert_eq!(double_clicks.load(Ordering::SeqCst), 1);
+
+        app.context().simulate_mouse_move(75.0, 75.0);
+        app.context().simulate_click(MouseButton::Left);
+        app.run_until_idle();
+        assert_eq!(presses.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn simulated_typing_holds_shift_for_shifted_characters() {
+        struct KeyRecorder {
+            keys: Arc<Mutex<Vec<(Code, bool)>>>,
+        }
+
+        impl Model for KeyRecorder {
+            fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+                event.map(|window_event, _| {
+                    if let WindowEvent::KeyDown(code, _) = window_event {
+                        self.keys.lock().unwrap().push((*code, cx.modifiers().shift()));
+                    }
+                });
+            }
+        }
+
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let recorded = keys.clone();
+        let mut app = HeadlessApplication::new(move |cx| {
+            KeyRecorder { keys: recorded.clone() }.build(cx);
+        });
+
+        app.run_until_idle();
+        app.context().simulate_type("aB!1");
+
+        assert_eq!(
+            *keys.lock().unwrap(),
+            [(Code::KeyA, false), (Code::KeyB, true), (Code::Digit1, true), (Code::Digit1, false)]
+        );
+    }
+
+    #[test]
+    fn query_finds_views_by_selector() {
+        let mut app = HeadlessApplication::new(|cx| {
+            VStack::new(cx, |cx| {
+                Label::new(cx, "One").class("item");
+                Label::new(cx, "Two").class("item").id("second");
+            })
+            .class("list");
+            Label::new(cx, "Three").class("item");
+        });
+
+        app.run_until_idle();
+
+        let cx = app.context();
+        assert_eq!(cx.query(".list > .item").len(), 2);
+        assert_eq!(cx.query("label.item").len(), 3);
+        assert_eq!(cx.query_first("#second"), cx.resolve_entity_identifier("second"));
+        assert!(cx.query("label[").is_empty());
+    }
+
+    #[test]
+    fn accessibility_snapshot_includes_names_and_roles() {
+        let mut app = HeadlessApplication::new(|cx| {
+            Element::new(cx).role(Role::Button).name("Submit").navigable(true);
+        });
+
+        let snapshot = app.accessibility_snapshot();
+        assert!(snapshot.starts_with("Window"));
+        assert!(snapshot.contains("\n  Button label=\"Submit\" actions=[Focus]\n"));
+    }
+
+    #[derive(Lens)]
+    struct ControlsData {
+        value: f32,
+        checked: bool,
+        tabs: Vec<&'static str>,
+    }
+
+    impl Model for ControlsData {}
+
+    fn controls_data() -> ControlsData {
+        ControlsData { value: 0.5, checked: true, tabs: vec!["General", "Advanced"] }
+    }
+
+    fn accessibility_snapshot_path(name: &str) -> String {
+        format!("{}/snapshots/accessibility/{}.txt", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn slider_accessibility_snapshot() {
+        let mut app = HeadlessApplication::new(|cx| {
+            controls_data().build(cx);
+
+            Slider::new(cx, ControlsData::value);
+        })
+        .inner_size((200, 100));
+
+        app.assert_accessibility_snapshot(accessibility_snapshot_path("slider"));
+    }
+
+    #[test]
+    fn checkbox_accessibility_snapshot() {
+        let mut app = HeadlessApplication::new(|cx| {
+            controls_data().build(cx);
+
+            Checkbox::new(cx, ControlsData::checked);
+            Checkbox::new(cx, ControlsData::checked.map(|checked| !checked));
+        })
+        .inner_size((200, 100));
+
+        app.assert_accessibility_snapshot(accessibility_snapshot_path("checkbox"));
+    }
+
+    #[test]
+    fn tabview_accessibility_snapshot() {
+        let mut app = HeadlessApplication::new(|cx| {
+            controls_data().build(cx);
+
+            TabView::new(cx, ControlsData::tabs, |cx, item| {
+                let content = match item.get(cx) {
+                    "General" => "General settings",
+                    _ => "Advanced settings",
+                };
+
+                TabPair::new(
+                    move |cx| {
+                        Label::new(cx, item);
+                    },
+                    move |cx| {
+                        Label::new(cx, content);
+                    },
+                )
+            });
+        })
+        .inner_size((200, 100));
+
+        app.assert_accessibility_snapshot(accessibility_snapshot_path("tabview"));
+    }
+
+    #[test]
+    fn menu_accessibility_snapshot() {
+        let mut app = HeadlessApplication::new(|cx| {
+            MenuBar::new(cx, |cx| {
+                Submenu::new(
+                    cx,
+                    |cx| Label::new(cx, "File"),
+                    |cx| {
+                        MenuButton::new(cx, |_| {}, |cx| Label::new(cx, "Open"));
+                    },
+                );
+            });
+
+            // The items of a menu, as they are presented in its popup.
+            VStack::new(cx, |cx| {
+                MenuButton::new(cx, |_| {}, |cx| Label::new(cx, "Open"));
+                MenuDivider::new(cx);
+                MenuButton::new(cx, |_| {}, |cx| Label::new(cx, "Quit"));
+            });
+        })
+        .inner_size((200, 100));
+
+        app.assert_accessibility_snapshot(accessibility_snapshot_path("menu"));
+    }
+
+    #[test]
+    fn watched_stylesheets_reload_when_modified() {
+        let path =
+            std::env::temp_dir().join(format!("vizia_headless_watch_{}.css", std::process::id()));
+
+        // Writes the stylesheet with a later modification time than the previous write.
+        let write = |contents: &str, seconds: u64| {
+            std::fs::write(&path, contents).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+                .unwrap();
+        };
+
+        write(".swatch { width: 10px; height: 10px; background-color: #ff0000; }", 1);
+
+        let stylesheet = path.clone();
+        let mut app = HeadlessApplication::new(move |cx| {
+            cx.add_stylesheet(stylesheet.clone()).unwrap();
+            cx.watch_stylesheets(Duration::from_millis(100));
+
+            Element::new(cx).class("swatch").id("swatch");
+        });
+
+        let swatch = app.context().query_first("#swatch").unwrap();
+        assert_eq!(app.snapshot_view(swatch).pixel(5, 5), Some([255, 0, 0, 255]));
+
+        write(".swatch { width: 10px; height: 10px; background-color: #0000ff; }", 2);
+        app.advance(Duration::from_millis(200));
+        assert_eq!(app.snapshot_view(swatch).pixel(5, 5), Some([0, 0, 255, 255]));
+
+        // A stylesheet which fails to parse keeps its previous rules.
+        write(".swatch { background-color: #00ff00; } !!! { }", 3);
+        app.advance(Duration::from_millis(200));
+        assert_eq!(app.snapshot_view(swatch).pixel(5, 5), Some([0, 0, 255, 255]));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn typography_properties_affect_text_layout() {
+        let mut app = HeadlessApplication::new(|cx| {
+            cx.add_stylesheet(
+                r#"
+                .spaced {
+                    letter-spacing: 4px;
+                    line-height: 3;
+                }
+                "#,
+            )
+            .unwrap();
+
+            Label::new(cx, "Hello").id("plain");
+            Label::new(cx, "Hello").id("spaced").class("spaced");
+        })
+        .inner_size((200, 200));
+
+        app.run_until_idle();
+
+        let plain = app.context().query_first("#plain").unwrap();
+        let spaced = app.context().query_first("#spaced").unwrap();
+        let plain_bounds = app.backend_context().cache().get_bounds(plain);
+        let spaced_bounds = app.backend_context().cache().get_bounds(spaced);
+
+        assert!(spaced_bounds.w >= plain_bounds.w + 16.0);
+        assert!(spaced_bounds.h > plain_bounds.h);
+    }
+
+    #[test]
+    fn filters_are_applied_to_views() {
+        let mut app = HeadlessApplication::new(|cx| {
+            cx.add_stylesheet(
+                r#"
+                element {
+                    width: 10px;
+                    height: 10px;
+                    background-color: #ff0000;
+                }
+
+                .inverted {
+                    filter: invert(100%);
+                }
+
+                .disabled {
+                    filter: grayscale(100%) brightness(0.5);
+                }
+                "#,
+            )
+            .unwrap();
+
+            Element::new(cx).class("inverted").id("inverted");
+            Element::new(cx).class("disabled").id("disabled");
+        })
+        .inner_size((100, 100));
+
+        let inverted = app.context().query_first("#inverted").unwrap();
+        let disabled = app.context().query_first("#disabled").unwrap();
+
+        assert_eq!(app.snapshot_view(inverted).pixel(5, 5), Some([0, 255, 255, 255]));
+
+        let [r, g, b, _] = app.snapshot_view(disabled).pixel(5, 5).unwrap();
+        assert!(r == g && g == b && r < 64);
+    }
+
+    #[test]
+    fn clip_path_shapes_clip_drawing_and_hovering() {
+        let presses = Arc::new(AtomicUsize::new(0));
+        let press_counter = presses.clone();
+
+        let mut app = HeadlessApplication::new(move |cx| {
+            cx.add_stylesheet(
+                r#"
+                .avatar {
+                    width: 40px;
+                    height: 40px;
+                    background-color: #ff0000;
+                    clip-path: circle();
+                }
+                "#,
+            )
+            .unwrap();
+
+            Element::new(cx).class("avatar").id("avatar").on_press(move |_| {
+                press_counter.fetch_add(1, Ordering::SeqCst);
+            });
+        })
+        .inner_size((100, 100));
+
+        let avatar = app.context().query_first("#avatar").unwrap();
+        let snapshot = app.snapshot_view(avatar);
+        assert_eq!(snapshot.pixel(20, 20), Some([255, 0, 0, 255]));
+        assert_ne!(snapshot.pixel(1, 1), Some([255, 0, 0, 255]));
+
+        app.context().simulate_mouse_move(2.0, 2.0);
+        app.context().simulate_click(MouseButton::Left);
+        app.run_until_idle();
+        assert_eq!(presses.load(Ordering::SeqCst), 0);
+
+        app.context().simulate_mouse_move(20.0, 20.0);
+        app.context().simulate_click(MouseButton::Left);
+        app.run_until_idle();
+        assert_eq!(presses.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn conic_and_repeating_gradients_are_drawn() {
+        let mut app = HeadlessApplication::new(|cx| {
+            cx.add_stylesheet(
+                r#"
+                element {
+                    width: 40px;
+                    height: 40px;
+                }
+
+                #stripes {
+                    background-image: repeating-linear-gradient(to right, red 0px, red 5px, blue 5px, blue 10px);
+                }
+                "#,
+            )
+            .unwrap();
+
+            Element::new(cx).id("stripes");
+            Element::new(cx).id("conic").background_image(
+                ConicGradientBuilder::new()
+                    .add_stop((Color::rgb(255, 0, 0), AnglePercentage::Percentage(0.0)))
+                    .add_stop((Color::rgb(255, 0, 0), AnglePercentage::Angle(Angle::Deg(90.0))))
+                    .add_stop((Color::rgb(0, 0, 255), AnglePercentage::Angle(Angle::Deg(90.0))))
+                    .add_stop(Color::rgb(0, 0, 255)),
+            );
+        })
+        .inner_size((100, 100));
+
+        let stripes = app.context().query_first("#stripes").unwrap();
+        let conic = app.context().query_first("#conic").unwrap();
+
+        let snapshot = app.snapshot_view(stripes);
+        assert_eq!(snapshot.pixel(2, 20), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(7, 20), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(22, 20), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(27, 20), Some([0, 0, 255, 255]));
+
+        let snapshot = app.snapshot_view(conic);
+        assert_eq!(snapshot.pixel(30, 10), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(10, 30), Some([0, 0, 255, 255]));
+    }
+
+    #[derive(Lens)]
+    struct FormData {
+        text: String,
+    }
+
+    enum FormEvent {
+        SetText(String),
+    }
+
+    impl Model for FormData {
+        fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+            event.map(|form_event, _| match form_event {
+                FormEvent::SetText(text) => self.text.clone_from(text),
+            });
+        }
+    }
+
+    #[test]
+    fn textbox_commits_ime_composition() {
+        let mut app = HeadlessApplication::new(|cx| {
+            FormData { text: String::new() }.build(cx);
+
+            Textbox::new(cx, FormData::text)
+                .on_edit(|cx, text| cx.emit(FormEvent::SetText(text)))
+                .width(Pixels(100.0))
+                .height(Pixels(30.0));
+        })
+        .inner_size((100, 100));
+
+        #[cfg(target_os = "macos")]
+        let modifier = Modifiers::SUPER;
+        #[cfg(not(target_os = "macos"))]
+        let modifier = Modifiers::CTRL;
+
+        let text = |app: &mut HeadlessApplication| {
+            app.run_until_idle();
+            app.context().data::<FormData>().unwrap().text.clone()
+        };
+
+        app.run_until_idle();
+        app.context().simulate_mouse_move(50.0, 15.0);
+        app.context().simulate_click(MouseButton::Left);
+
+        // Text being composed is not part of the edited text until it is committed.
+        app.context().simulate_ime_preedit("にほ", Some((6, 6)));
+        assert_eq!(text(&mut app), "");
+
+        app.context().simulate_ime_commit("日本");
+        assert_eq!(text(&mut app), "日本");
+
+        app.context().simulate_key_chord(KeyChord::new(modifier, Code::KeyZ));
+        assert_eq!(text(&mut app), "");
+    }
+}
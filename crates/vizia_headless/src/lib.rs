@@ -0,0 +1,10 @@
+//! A headless backend for vizia, used to run applications in automated tests without a display or GPU.
+
+mod accessibility;
+mod application;
+pub(crate) mod proxy;
+mod snapshot;
+
+pub use accessibility::{assert_accessibility_snapshot, AccessibilityTree};
+pub use application::{HeadlessApplication, HeadlessWindow};
+pub use snapshot::{Snapshot, SnapshotComparison, UPDATE_SNAPSHOTS_VAR};
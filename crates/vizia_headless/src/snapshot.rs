@@ -0,0 +1,320 @@
+use std::path::{Path, PathBuf};
+
+use skia_safe::{png_encoder, AlphaType, CachingHint, ColorType, Data, Image, ImageInfo, Pixmap};
+
+/// Environment variable which, when set, causes reference images to be overwritten instead of compared.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "VIZIA_UPDATE_SNAPSHOTS";
+
+/// An RGBA image of rendered views, used for golden-image testing.
+///
+/// Pixels are stored as unpremultiplied 8-bit RGBA in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+/// The result of comparing a [`Snapshot`] with a reference.
+#[derive(Debug, Clone)]
+pub struct SnapshotComparison {
+    /// The number of pixels with a channel which differs from the reference by more than the tolerance.
+    pub mismatched_pixels: usize,
+    /// The largest difference of any channel of any pixel.
+    pub max_difference: u8,
+    /// An image highlighting mismatched pixels in red over a faded copy of the reference.
+    /// This is `None` if the snapshots have different sizes.
+    pub diff: Option<Snapshot>,
+}
+
+impl SnapshotComparison {
+    /// Returns true if the snapshot matched its reference within the tolerance.
+    pub fn is_match(&self) -> bool {
+        self.diff.is_some() && self.mismatched_pixels == 0
+    }
+}
+
+impl Snapshot {
+    /// Creates a snapshot from raw unpremultiplied RGBA pixel data.
+    ///
+    /// Returns `None` if the length of the pixel data does not match the size.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        (pixels.len() == width as usize * height as usize * 4).then_some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Creates a snapshot by reading the pixels of a Skia image.
+    pub fn from_image(image: &Image) -> Option<Self> {
+        let (width, height) = (image.width().max(0) as u32, image.height().max(0) as u32);
+        let info = Self::image_info(width, height);
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+
+        image
+            .read_pixels(&info, &mut pixels, width as usize * 4, (0, 0), CachingHint::Allow)
+            .then_some(Self { width, height, pixels })
+    }
+
+    /// Decodes a snapshot from PNG data.
+    pub fn from_png(data: &[u8]) -> Option<Self> {
+        Image::from_encoded(Data::new_copy(data)).and_then(|image| Self::from_image(&image))
+    }
+
+    /// Loads a snapshot from a PNG file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
+        let data = std::fs::read(path)?;
+
+        Self::from_png(&data).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "Failed to decode PNG image")
+        })
+    }
+
+    /// Encodes the snapshot as PNG data.
+    pub fn to_png(&self) -> Option<Vec<u8>> {
+        let info = Self::image_info(self.width, self.height);
+        let pixmap = Pixmap::new(&info, &self.pixels, self.width as usize * 4)?;
+
+        let mut data = Vec::new();
+        png_encoder::encode(&pixmap, &mut data, &png_encoder::Options::default()).then_some(data)
+    }
+
+    /// Saves the snapshot to a PNG file, creating any missing parent directories.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let data = self.to_png().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "Failed to encode PNG image")
+        })?;
+
+        std::fs::write(path, data)
+    }
+
+    /// Returns the width of the snapshot in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the snapshot in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the unpremultiplied RGBA pixel data of the snapshot.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the RGBA value of the pixel at the given position, or `None` if it is out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels[index..index + 4].try_into().ok()
+    }
+
+    /// Returns a copy of the given region of the snapshot, clamped to the bounds of the snapshot.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        for row in y..y + height {
+            let start = (row as usize * self.width as usize + x as usize) * 4;
+            pixels.extend_from_slice(&self.pixels[start..start + width as usize * 4]);
+        }
+
+        Self { width, height, pixels }
+    }
+
+    /// Compares the snapshot with a reference, allowing each channel of each pixel to differ by up to `tolerance`.
+    pub fn compare(&self, reference: &Snapshot, tolerance: u8) -> SnapshotComparison {
+        if self.width != reference.width || self.height != reference.height {
+            return SnapshotComparison {
+                mismatched_pixels: self.width.max(reference.width) as usize
+                    * self.height.max(reference.height) as usize,
+                max_difference: u8::MAX,
+                diff: None,
+            };
+        }
+
+        let mut mismatched_pixels = 0;
+        let mut max_difference = 0;
+        let mut diff_pixels = Vec::with_capacity(self.pixels.len());
+
+        for (actual, expected) in self.pixels.chunks_exact(4).zip(reference.pixels.chunks_exact(4))
+        {
+            let difference =
+                actual.iter().zip(expected).map(|(a, e)| a.abs_diff(*e)).max().unwrap_or_default();
+
+            max_difference = max_difference.max(difference);
+
+            if difference > tolerance {
+                mismatched_pixels += 1;
+                diff_pixels.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                let luma = (expected[0] as u32 * 299
+                    + expected[1] as u32 * 587
+                    + expected[2] as u32 * 114)
+                    / 1000;
+                diff_pixels.extend_from_slice(&[
+                    luma as u8,
+                    luma as u8,
+                    luma as u8,
+                    expected[3] / 4,
+                ]);
+            }
+        }
+
+        SnapshotComparison {
+            mismatched_pixels,
+            max_difference,
+            diff: Some(Snapshot { width: self.width, height: self.height, pixels: diff_pixels }),
+        }
+    }
+
+    /// Asserts that the snapshot matches the reference PNG file at the given path within the tolerance.
+    ///
+    /// If the `VIZIA_UPDATE_SNAPSHOTS` environment variable is set, the snapshot is written to the path instead.
+    /// If the reference does not exist, or on a mismatch, the snapshot is written next to the reference with an
+    /// `.actual.png` extension, along with a `.diff.png` image for a mismatch, before panicking.
+    #[track_caller]
+    pub fn assert_matches(&self, path: impl AsRef<Path>, tolerance: u8) {
+        let path = path.as_ref();
+
+        if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+            self.save(path).unwrap_or_else(|err| {
+                panic!("Failed to write snapshot {}: {}", path.display(), err)
+            });
+            return;
+        }
+
+        if !path.exists() {
+            let actual_path = sibling_path(path, "actual");
+            let _ = self.save(&actual_path);
+
+            panic!(
+                "Reference snapshot {} does not exist, actual image written to {}. Set {} to create it",
+                path.display(),
+                actual_path.display(),
+                UPDATE_SNAPSHOTS_VAR,
+            );
+        }
+
+        let reference = Snapshot::load(path).unwrap_or_else(|err| {
+            panic!("Failed to load reference snapshot {}: {}", path.display(), err)
+        });
+
+        let comparison = self.compare(&reference, tolerance);
+        if comparison.is_match() {
+            return;
+        }
+
+        let actual_path = sibling_path(path, "actual");
+        let _ = self.save(&actual_path);
+
+        let diff_path = sibling_path(path, "diff");
+        if let Some(diff) = &comparison.diff {
+            let _ = diff.save(&diff_path);
+        }
+
+        if comparison.diff.is_none() {
+            panic!(
+                "Snapshot size {}x{} does not match reference {} with size {}x{}, actual image written to {}",
+                self.width,
+                self.height,
+                path.display(),
+                reference.width,
+                reference.height,
+                actual_path.display(),
+            );
+        }
+
+        panic!(
+            "Snapshot does not match reference {}: {} pixels differ by more than {} (max difference {}), actual image written to {} and diff image written to {}",
+            path.display(),
+            comparison.mismatched_pixels,
+            tolerance,
+            comparison.max_difference,
+            actual_path.display(),
+            diff_path.display(),
+        );
+    }
+
+    fn image_info(width: u32, height: u32) -> ImageInfo {
+        ImageInfo::new(
+            (width as i32, height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        )
+    }
+}
+
+// Returns the path of a file next to the given path, e.g. `button.png` -> `button.diff.png`.
+pub(crate) fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    match path.extension() {
+        Some(extension) => {
+            path.with_file_name(format!("{stem}.{suffix}.{}", extension.to_string_lossy()))
+        }
+        None => path.with_file_name(format!("{stem}.{suffix}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Snapshot {
+        Snapshot::from_rgba(width, height, rgba.repeat(width as usize * height as usize)).unwrap()
+    }
+
+    #[test]
+    fn compare_within_tolerance() {
+        let a = solid(4, 4, [100, 100, 100, 255]);
+        let b = solid(4, 4, [102, 99, 100, 255]);
+
+        let comparison = a.compare(&b, 2);
+        assert!(comparison.is_match());
+        assert_eq!(comparison.max_difference, 2);
+
+        let comparison = a.compare(&b, 1);
+        assert!(!comparison.is_match());
+        assert_eq!(comparison.mismatched_pixels, 16);
+        assert_eq!(comparison.diff.unwrap().pixel(0, 0), Some([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn compare_different_sizes() {
+        let comparison = solid(4, 4, [0; 4]).compare(&solid(4, 2, [0; 4]), 255);
+        assert!(!comparison.is_match());
+        assert!(comparison.diff.is_none());
+    }
+
+    #[test]
+    fn crop_region() {
+        let mut snapshot = solid(4, 4, [0, 0, 0, 255]);
+        snapshot.pixels[(4 + 2) * 4] = 255;
+
+        let cropped = snapshot.crop(2, 1, 10, 10);
+        assert_eq!((cropped.width(), cropped.height()), (2, 3));
+        assert_eq!(cropped.pixel(0, 0), Some([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let snapshot = solid(3, 2, [10, 20, 30, 255]);
+        let png = snapshot.to_png().unwrap();
+        assert_eq!(Snapshot::from_png(&png), Some(snapshot));
+    }
+}
@@ -356,6 +356,7 @@ where
         }
 
         self.next_sibling[index] = previous_first_child;
+        self.prev_sibling[index] = None;
 
         self.first_child[parent.index()] = Some(entity);
 
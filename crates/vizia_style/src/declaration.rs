@@ -59,11 +59,30 @@ pub(crate) fn parse_declaration<'i>(
     input: &mut cssparser::Parser<'i, '_>,
     declarations: &mut DeclarationList<'i>,
     important_declarations: &mut DeclarationList<'i>,
-    _options: &ParserOptions,
+    options: &ParserOptions<'i>,
 ) -> Result<(), ParseError<'i, CustomParseError<'i>>> {
+    let location = input.current_source_location();
     let property =
         input.parse_until_before(Delimiter::Bang, |input| Property::parse_value(name, input))?;
 
+    // Declarations which could not be parsed are kept, but are reported as warnings unless they
+    // contain variables which are substituted later.
+    let warning = match &property {
+        Property::Unparsed(unparsed) if !unparsed.value.has_var() => {
+            Some(CustomParseError::InvalidPropertyValue(unparsed.name.clone()))
+        }
+
+        Property::Custom(custom) if !custom.name.starts_with("--") => {
+            Some(CustomParseError::UnknownProperty(custom.name.clone()))
+        }
+
+        _ => None,
+    };
+
+    if let Some(warning) = warning {
+        options.warn(location.new_custom_error(warning));
+    }
+
     let important = input
         .try_parse(|input| {
             input.expect_delim('!')?;
@@ -64,6 +64,40 @@ pub enum CustomParseError<'i> {
     AtRuleInvalid(CowRcStr<'i>),
     AtRuleBodyInvalid,
     QualifiedRuleInvalid,
+    /// A declaration of a property which is not supported.
+    UnknownProperty(CowRcStr<'i>),
+    /// A declaration of a supported property with a value which could not be parsed.
+    InvalidPropertyValue(CowRcStr<'i>),
+}
+
+impl CustomParseError<'_> {
+    /// Returns whether the error only caused a declaration to be kept unparsed, rather than a rule or
+    /// declaration being dropped.
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            CustomParseError::UnknownProperty(_) | CustomParseError::InvalidPropertyValue(_)
+        )
+    }
+}
+
+impl fmt::Display for CustomParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CustomParseError::*;
+        match self {
+            InvalidValue => write!(f, "Invalid value"),
+            InvalidDeclaration => write!(f, "Invalid declaration"),
+            InvalidNesting => write!(f, "Invalid nesting"),
+            SelectorError(err) => write!(f, "{}", err.reason()),
+            EndOfInput => write!(f, "Unexpected end of input"),
+            UnexpectedToken(token) => write!(f, "Unexpected token: {:?}", token),
+            AtRuleInvalid(name) => write!(f, "Unknown at-rule: @{}", name),
+            AtRuleBodyInvalid => write!(f, "Invalid at-rule body"),
+            QualifiedRuleInvalid => write!(f, "Invalid rule"),
+            UnknownProperty(name) => write!(f, "Unknown property: {}", name),
+            InvalidPropertyValue(name) => write!(f, "Invalid value for property: {}", name),
+        }
+    }
 }
 
 impl<'i> From<SelectorParseErrorKind<'i>> for CustomParseError<'i> {
@@ -157,7 +191,7 @@ impl<'i> From<SelectorParseErrorKind<'i>> for SelectorError<'i> {
 }
 
 impl SelectorError<'_> {
-    fn _reason(&self) -> String {
+    fn reason(&self) -> String {
         use SelectorError::*;
         match self {
         NoQualifiedNameInAttributeSelector(token) => format!("No qualified name in attribute selector: {:?}.", token),
@@ -60,6 +60,7 @@ mod test {
         element: HashMap<Entity, String>,
         classes: HashMap<Entity, HashSet<String>>,
         pseudo_class: HashMap<Entity, PseudoClass>,
+        attributes: HashMap<Entity, HashMap<String, String>>,
     }
 
     #[derive(Debug, Clone)]
@@ -179,6 +180,12 @@ mod test {
                 &<Self::Impl as selectors::SelectorImpl>::AttrValue,
             >,
         ) -> bool {
+            if let Some(attributes) = self.store.attributes.get(&self.entity) {
+                if let Some(value) = attributes.get(&local_name.0) {
+                    return operation.eval_str(value);
+                }
+            }
+
             false
         }
 
@@ -219,8 +226,11 @@ mod test {
                     crate::PseudoClass::Optional => todo!(),
                     crate::PseudoClass::UserValid => todo!(),
                     crate::PseudoClass::UserInvalid => todo!(),
-                    crate::PseudoClass::Lang(_) => todo!(),
-                    crate::PseudoClass::Dir(_) => todo!(),
+                    // The test store has a fixed locale of `en-US`.
+                    crate::PseudoClass::Lang(langs) => {
+                        langs.iter().any(|lang| crate::lang_matches("en-US", lang))
+                    }
+                    crate::PseudoClass::Dir(direction) => *direction == crate::Direction::Ltr,
                     crate::PseudoClass::Custom(_) => todo!(),
                     _ => todo!(),
                 }
@@ -255,6 +265,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            attributes: HashMap::new(),
         };
 
         let root = Entity(0);
@@ -288,6 +299,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            attributes: HashMap::new(),
         };
 
         let root = Entity(0);
@@ -327,6 +339,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            attributes: HashMap::new(),
         };
 
         let root = Entity(0);
@@ -428,6 +441,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            attributes: HashMap::new(),
         };
 
         let root = Entity(0);
@@ -464,4 +478,54 @@ mod test {
             assert!(!result);
         }
     }
+
+    #[test]
+    fn attribute_match() {
+        let mut store = Store {
+            element: HashMap::new(),
+            classes: HashMap::new(),
+            pseudo_class: HashMap::new(),
+            attributes: HashMap::new(),
+        };
+
+        let root = Entity(0);
+        let child = Entity(1);
+
+        let mut attributes = HashMap::new();
+        attributes.insert(String::from("orientation"), String::from("vertical"));
+        attributes.insert(String::from("data-tags"), String::from("primary large"));
+        attributes.insert(String::from("lang"), String::from("en-US"));
+        store.attributes.insert(root, attributes);
+
+        let root_node = Node { entity: root, store: &store };
+
+        let child_node = Node { entity: child, store: &store };
+
+        let matches = |selector: &str, node: &Node| {
+            let selector_list = parse(selector).unwrap();
+            let mut cache = SelectorCaches::default();
+            let mut context = MatchingContext::new(
+                MatchingMode::Normal,
+                None,
+                &mut cache,
+                QuirksMode::NoQuirks,
+                NeedsSelectorFlags::No,
+                MatchingForInvalidation::No,
+            );
+
+            matches_selector_list(&selector_list, node, &mut context)
+        };
+
+        assert!(matches("[orientation]", &root_node));
+        assert!(!matches("[orientation]", &child_node));
+        assert!(matches("[orientation=vertical]", &root_node));
+        assert!(!matches("[orientation=horizontal]", &root_node));
+        assert!(matches("[data-tags~=large]", &root_node));
+        assert!(!matches("[data-tags~=lar]", &root_node));
+        assert!(matches("[orientation^=vert]", &root_node));
+        assert!(matches("[orientation$=ical]", &root_node));
+        assert!(matches("[orientation*=rti]", &root_node));
+        assert!(matches("[lang|=en]", &root_node));
+        assert!(!matches("[lang|=US]", &root_node));
+    }
 }
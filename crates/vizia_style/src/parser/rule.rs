@@ -1,7 +1,7 @@
 use crate::{
-    parse_declaration, CssRule, CssRuleList, CustomParseError, DeclarationBlock, DeclarationList,
-    KeyframeListParser, KeyframesName, KeyframesRule, Location, Parse, ParserOptions,
-    SelectorParser, Selectors, StyleRule,
+    parse_declaration, parse_selectors, CssRule, CssRuleList, CustomParseError, DeclarationBlock,
+    DeclarationList, KeyframeListParser, KeyframesName, KeyframesRule, Location, MediaQueryList,
+    MediaRule, Parse, ParserOptions, SelectorParser, Selectors, StyleRule,
 };
 use cssparser::*;
 use selectors::{parser::ParseRelative, SelectorList};
@@ -42,6 +42,7 @@ impl<'a, 'i> TopLevelRuleParser<'a, 'i> {
 pub enum AtRulePrelude<'i> {
     // Property(DashedIdent<'i>),
     Keyframes(KeyframesName<'i>),
+    Media(MediaQueryList),
 }
 
 impl<'i> AtRuleParser<'i> for TopLevelRuleParser<'_, 'i> {
@@ -178,6 +179,10 @@ impl<'i> AtRuleParser<'i> for NestedRuleParser<'_, 'i> {
                 let name = input.try_parse(KeyframesName::parse)?;
                 Ok(AtRulePrelude::Keyframes(name))
             },
+            "media" => {
+                let query = MediaQueryList::parse(input)?;
+                Ok(AtRulePrelude::Media(query))
+            },
             _ => Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)))
         }
     }
@@ -200,6 +205,32 @@ impl<'i> AtRuleParser<'i> for NestedRuleParser<'_, 'i> {
                 }));
                 Ok(())
             }
+
+            AtRulePrelude::Media(query) => {
+                let (declarations, mut rules) = self.parse_nested(input, false)?;
+
+                // Declarations directly within a media rule nested in a style rule apply to the
+                // parent selector, as if they were wrapped in `& { ... }`.
+                if self.is_in_style_rule
+                    && (!declarations.declarations.is_empty()
+                        || !declarations.important_declarations.is_empty())
+                {
+                    let selectors = parse_selectors("&")
+                        .map_err(|_| input.new_custom_error(CustomParseError::InvalidNesting))?;
+                    rules.0.insert(
+                        0,
+                        CssRule::Style(StyleRule {
+                            selectors,
+                            declarations,
+                            rules: CssRuleList(vec![]),
+                            loc,
+                        }),
+                    );
+                }
+
+                self.rules.0.push(CssRule::Media(MediaRule { query, rules, loc }));
+                Ok(())
+            }
         }
     }
 }
@@ -215,7 +246,12 @@ impl<'i> QualifiedRuleParser<'i> for NestedRuleParser<'_, 'i> {
     ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
         let selector_parser = SelectorParser { options: self.options };
 
-        SelectorList::parse(&selector_parser, input, ParseRelative::No)
+        // Selectors of nested rules are relative to the parent rule, so `label` is parsed as
+        // `& label` and `> label` as `& > label`.
+        let parse_relative =
+            if self.is_in_style_rule { ParseRelative::ForNesting } else { ParseRelative::No };
+
+        SelectorList::parse(&selector_parser, input, parse_relative)
     }
 
     fn parse_block<'t>(
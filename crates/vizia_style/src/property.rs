@@ -1,13 +1,14 @@
 use crate::{
     define_property, Alignment, Angle, BackgroundImage, BackgroundSize, BlendMode, Border,
-    BorderStyle, BorderWidth, ClipPath, Color, CornerRadius, CornerShape, CursorIcon,
+    BorderStyle, BorderWidth, ClipPath, Color, Content, CornerRadius, CornerShape, CursorIcon,
     CustomParseError, CustomProperty, Display, Filter, FontFamily, FontSize, FontSlant,
     FontVariation, FontWeight, FontWidth, LayoutType, Length, LengthOrPercentage, LineClamp,
-    Opacity, Outline, Overflow, Parse, PointerEvents, Position, PositionType, Rect, Scale, Shadow,
-    TextAlign, TextDecoration, TextDecorationLine, TextDecorationStyle, TextOverflow, TextStroke,
-    TextStrokeStyle, Transform, Transition, Translate, Units, UnparsedProperty, Visibility,
+    LineHeight, Opacity, Outline, Overflow, Parse, PointerEvents, Position, PositionType, Rect,
+    Scale, Shadow, TextAlign, TextDecoration, TextDecorationLine, TextDecorationStyle,
+    TextOverflow, TextSpacing, TextStroke, TextStrokeStyle, Transform, Transition, Translate,
+    Units, UnparsedProperty, Visibility,
 };
-use cssparser::Parser;
+use cssparser::{CowRcStr, Parser, ParserInput};
 
 define_property! {
     pub enum Property<'i> {
@@ -151,6 +152,9 @@ define_property! {
 
         "fill": Fill(Color),
 
+        // Generated Content
+        "content": Content(Content),
+
         // Text
         "font-size": FontSize(FontSize),
         "color": FontColor(Color),
@@ -170,6 +174,10 @@ define_property! {
         "text-stroke": TextStroke(TextStroke),
         "text-stroke-width": TextStrokeWidth(Length),
         "text-stroke-style": TextStrokeStyle(TextStrokeStyle),
+        "line-height": LineHeight(LineHeight),
+        "letter-spacing": LetterSpacing(TextSpacing),
+        "word-spacing": WordSpacing(TextSpacing),
+        "text-indent": TextIndent(LengthOrPercentage),
         "underline-style": UnderlineStyle(TextDecorationStyle),
         "underline-thickness": UnderlineThickness(LengthOrPercentage),
         "underline-color": UnderlineColor(Color),
@@ -182,8 +190,10 @@ define_property! {
 
         // Shadow
         "shadow": Shadow(Vec<Shadow>),
+        "text-shadow": TextShadow(Vec<Shadow>),
 
-        // Backdrop Filter
+        // Filters
+        "filter": Filter(Vec<Filter>),
         "backdrop-filter": BackdropFilter(Filter),
 
         // Animations
@@ -202,10 +212,23 @@ define_property! {
     }
 }
 
+impl<'i> Property<'i> {
+    /// Parses a property from its name and a string containing its value, such as a value produced by
+    /// substituting variables.
+    pub fn parse_string(
+        name: &'i str,
+        value: &'i str,
+    ) -> Result<Self, cssparser::ParseError<'i, CustomParseError<'i>>> {
+        let mut parser_input = ParserInput::new(value);
+        let mut parser = Parser::new(&mut parser_input);
+        let property = Property::parse_value(CowRcStr::from(name), &mut parser)?;
+        parser.expect_exhausted()?;
+        Ok(property)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use cssparser::{CowRcStr, ParserInput};
-
     use super::*;
 
     #[test]
@@ -215,4 +238,37 @@ mod tests {
         let _parsed_property =
             Property::parse_value(CowRcStr::from("background-color"), &mut parser);
     }
+
+    #[test]
+    fn parse_property_string() {
+        assert!(matches!(
+            Property::parse_string("background-color", "red"),
+            Ok(Property::BackgroundColor(_))
+        ));
+        assert!(matches!(
+            Property::parse_string("background-color", "var(--accent)"),
+            Ok(Property::Unparsed(_))
+        ));
+    }
+
+    #[test]
+    fn parse_custom_property_declarations() {
+        let Ok(Property::Custom(custom)) = Property::parse_string("--accent", "#00ff00") else {
+            panic!("expected a custom property");
+        };
+        assert_eq!(&*custom.name, "--accent");
+        assert!(!custom.value.has_var());
+
+        let Ok(Property::Unparsed(unparsed)) =
+            Property::parse_string("background-color", "var(--accent, #ff0000)")
+        else {
+            panic!("expected an unparsed property");
+        };
+        assert_eq!(&*unparsed.name, "background-color");
+        assert!(unparsed.value.has_var());
+        assert_eq!(
+            crate::UnresolvedValue::from(&unparsed.value).to_string(),
+            "var(--accent, #ff0000)"
+        );
+    }
 }
@@ -63,8 +63,18 @@ impl ToCss for PseudoClass {
             PseudoClass::Optional => dest.write_str(":optional"),
             PseudoClass::UserValid => dest.write_str(":user-valid"),
             PseudoClass::UserInvalid => dest.write_str(":user-invalid"),
-            PseudoClass::Lang(ref _lang) => dest.write_str(":lang()"),
-            PseudoClass::Dir(_) => dest.write_str(":dir()"),
+            PseudoClass::Lang(ref langs) => {
+                dest.write_str(":lang(")?;
+                for (index, lang) in langs.iter().enumerate() {
+                    if index > 0 {
+                        dest.write_str(", ")?;
+                    }
+                    serialize_identifier(lang, dest)?;
+                }
+                dest.write_str(")")
+            }
+            PseudoClass::Dir(Direction::Ltr) => dest.write_str(":dir(ltr)"),
+            PseudoClass::Dir(Direction::Rtl) => dest.write_str(":dir(rtl)"),
             PseudoClass::Custom(_) => dest.write_str(":custom"),
         }
     }
@@ -81,3 +91,56 @@ impl selectors::parser::NonTSPseudoClass for PseudoClass {
         matches!(*self, PseudoClass::Active | PseudoClass::Hover | PseudoClass::Focus)
     }
 }
+
+/// Returns whether a language tag matches a language range of a `:lang()` pseudo-class.
+///
+/// The range matches if it is equal to the tag or to a prefix of the tag followed by a `-`, ignoring case,
+/// so `en` matches `en` and `en-US` but not `eng`. A range of `*` matches any tag.
+pub fn lang_matches(lang: &str, range: &str) -> bool {
+    if range == "*" {
+        return !lang.is_empty();
+    }
+
+    lang.len() >= range.len()
+        && lang.is_char_boundary(range.len())
+        && lang[..range.len()].eq_ignore_ascii_case(range)
+        && (lang.len() == range.len() || lang.as_bytes()[range.len()] == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_language_ranges() {
+        assert!(lang_matches("en-US", "en"));
+        assert!(lang_matches("en-US", "EN-us"));
+        assert!(lang_matches("ar", "ar"));
+        assert!(lang_matches("fr", "*"));
+        assert!(!lang_matches("eng", "en"));
+        assert!(!lang_matches("en", "en-US"));
+    }
+
+    #[test]
+    fn serialize_lang_and_dir() {
+        assert_eq!(
+            PseudoClass::Lang(vec![String::from("en"), String::from("fr")]).to_css_string(),
+            ":lang(en, fr)"
+        );
+        assert_eq!(PseudoClass::Dir(Direction::Rtl).to_css_string(), ":dir(rtl)");
+    }
+
+    #[test]
+    fn parse_lang_and_dir() {
+        let round_trip = |input| crate::parse_selectors(input).unwrap().to_css_string();
+
+        assert_eq!(round_trip(":lang(en, fr)"), ":lang(en, fr)");
+        assert_eq!(round_trip("label:lang(\"de\")"), "label:lang(de)");
+        assert_eq!(round_trip(":dir(rtl)"), ":dir(rtl)");
+        assert_eq!(round_trip("label:DIR(LTR)"), "label:dir(ltr)");
+
+        assert!(crate::parse_selectors(":lang()").is_err());
+        assert!(crate::parse_selectors(":dir(up)").is_err());
+        assert!(crate::parse_selectors(":dir(rtl, ltr)").is_err());
+    }
+}
@@ -11,15 +11,18 @@ pub enum PseudoElement {
 }
 
 impl ToCss for PseudoElement {
-    fn to_css<W>(&self, _dest: &mut W) -> std::fmt::Result
+    fn to_css<W>(&self, dest: &mut W) -> std::fmt::Result
     where
         W: std::fmt::Write,
     {
         match *self {
-            PseudoElement::After => todo!(),
-            PseudoElement::Before => todo!(),
-            PseudoElement::Selection => todo!(),
-            PseudoElement::Custom(_) => todo!(),
+            PseudoElement::After => dest.write_str("::after"),
+            PseudoElement::Before => dest.write_str("::before"),
+            PseudoElement::Selection => dest.write_str("::selection"),
+            PseudoElement::Custom(ref name) => {
+                dest.write_str("::")?;
+                serialize_identifier(name, dest)
+            }
         }
     }
 }
@@ -0,0 +1,283 @@
+use cssparser::*;
+
+use crate::{ColorScheme, CssRuleList, CustomParseError, Location, MediaType, Parse};
+
+/// A `@media` rule, containing rules which only apply when its media query list matches.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MediaRule<'i> {
+    pub query: MediaQueryList,
+    pub rules: CssRuleList<'i>,
+    pub loc: Location,
+}
+
+/// The state of the application which media queries are evaluated against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaEnvironment {
+    /// The current theme mode of the application.
+    pub color_scheme: ColorScheme,
+    /// The logical width of the window.
+    pub width: f32,
+    /// The logical height of the window.
+    pub height: f32,
+    /// The scale factor of the window.
+    pub scale_factor: f32,
+}
+
+/// A comma-separated list of media queries, which matches if any of its queries match.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MediaQueryList(pub Vec<MediaQuery>);
+
+impl MediaQueryList {
+    /// Returns whether the media query list matches the given environment.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        self.0.iter().any(|query| query.matches(environment))
+    }
+}
+
+impl<'i> Parse<'i> for MediaQueryList {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        Ok(MediaQueryList(input.parse_comma_separated(MediaQuery::parse)?))
+    }
+}
+
+/// A media query, such as `screen and (min-width: 600px)`, which matches if all of its features match.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MediaQuery {
+    /// Whether the result of the query is negated with `not`.
+    pub negated: bool,
+    pub media_type: MediaType,
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery {
+    /// Returns whether the media query matches the given environment.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        let matches = self.media_type != MediaType::Print
+            && self.features.iter().all(|feature| feature.matches(environment));
+
+        matches != self.negated
+    }
+}
+
+impl<'i> Parse<'i> for MediaQuery {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut negated = false;
+        let mut media_type = MediaType::All;
+        let mut features = Vec::new();
+
+        if input.try_parse(|input| input.expect_ident_matching("not")).is_ok() {
+            negated = true;
+        } else {
+            let _ = input.try_parse(|input| input.expect_ident_matching("only"));
+        }
+
+        if let Ok(parsed_type) = input.try_parse(MediaType::parse) {
+            media_type = parsed_type;
+
+            if input.try_parse(|input| input.expect_ident_matching("and")).is_err() {
+                return Ok(MediaQuery { negated, media_type, features });
+            }
+        }
+
+        loop {
+            input.expect_parenthesis_block()?;
+            features.push(input.parse_nested_block(MediaFeature::parse)?);
+
+            if input.try_parse(|input| input.expect_ident_matching("and")).is_err() {
+                break;
+            }
+        }
+
+        Ok(MediaQuery { negated, media_type, features })
+    }
+}
+
+/// Whether a media feature is a minimum, maximum or exact value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaRange {
+    Min,
+    Max,
+    Exact,
+}
+
+impl MediaRange {
+    fn matches(&self, actual: f32, value: f32) -> bool {
+        match self {
+            MediaRange::Min => actual >= value,
+            MediaRange::Max => actual <= value,
+            MediaRange::Exact => actual == value,
+        }
+    }
+}
+
+/// A condition of a media query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaFeature {
+    /// `prefers-color-scheme`, matched against the theme mode of the application.
+    PrefersColorScheme(ColorScheme),
+    /// `width`, `min-width` and `max-width` in logical pixels.
+    Width(MediaRange, f32),
+    /// `height`, `min-height` and `max-height` in logical pixels.
+    Height(MediaRange, f32),
+    /// `resolution`, `min-resolution` and `max-resolution` in dots per pixel, matched against the scale factor.
+    Resolution(MediaRange, f32),
+}
+
+impl MediaFeature {
+    /// Returns whether the media feature matches the given environment.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        match self {
+            MediaFeature::PrefersColorScheme(color_scheme) => {
+                environment.color_scheme == *color_scheme
+            }
+            MediaFeature::Width(range, value) => range.matches(environment.width, *value),
+            MediaFeature::Height(range, value) => range.matches(environment.height, *value),
+            MediaFeature::Resolution(range, value) => {
+                range.matches(environment.scale_factor, *value)
+            }
+        }
+    }
+}
+
+impl<'i> Parse<'i> for MediaFeature {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let location = input.current_source_location();
+        let name = input.expect_ident()?.clone();
+        input.expect_colon()?;
+
+        let (range, feature) = if let Some(feature) = name.strip_prefix("min-") {
+            (MediaRange::Min, feature)
+        } else if let Some(feature) = name.strip_prefix("max-") {
+            (MediaRange::Max, feature)
+        } else {
+            (MediaRange::Exact, name.as_ref())
+        };
+
+        match_ignore_ascii_case! { feature,
+            "prefers-color-scheme" => {
+                if range != MediaRange::Exact {
+                    return Err(location.new_unexpected_token_error(Token::Ident(name.clone())));
+                }
+
+                Ok(MediaFeature::PrefersColorScheme(ColorScheme::parse(input)?))
+            },
+            "width" => Ok(MediaFeature::Width(range, parse_length(input)?)),
+            "height" => Ok(MediaFeature::Height(range, parse_length(input)?)),
+            "resolution" => Ok(MediaFeature::Resolution(range, parse_resolution(input)?)),
+            _ => Err(location.new_unexpected_token_error(Token::Ident(name.clone()))),
+        }
+    }
+}
+
+// Parses a length in pixels, the only absolute unit which makes sense for window sizes.
+fn parse_length<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<f32, ParseError<'i, CustomParseError<'i>>> {
+    let location = input.current_source_location();
+    match input.next()? {
+        Token::Dimension { value, unit, .. } if unit.eq_ignore_ascii_case("px") => Ok(*value),
+        Token::Number { value, .. } if *value == 0.0 => Ok(0.0),
+        token => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+// Parses a resolution in dots per pixel.
+fn parse_resolution<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<f32, ParseError<'i, CustomParseError<'i>>> {
+    let location = input.current_source_location();
+    match input.next()? {
+        Token::Dimension { value, unit, .. } => {
+            match_ignore_ascii_case! { unit,
+                "dppx" | "x" => Ok(*value),
+                "dpi" => Ok(*value / 96.0),
+                "dpcm" => Ok(*value * 2.54 / 96.0),
+                _ => Err(location.new_unexpected_token_error(Token::Ident(unit.clone()))),
+            }
+        }
+        token => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        MediaQueryList, parse_media_query_list,
+
+        custom {
+            success {
+                "(min-width: 200px)" => MediaQueryList(vec![MediaQuery {
+                    negated: false,
+                    media_type: MediaType::All,
+                    features: vec![MediaFeature::Width(MediaRange::Min, 200.0)],
+                }]),
+                "only screen and (max-height: 0), not print" => MediaQueryList(vec![
+                    MediaQuery {
+                        negated: false,
+                        media_type: MediaType::Screen,
+                        features: vec![MediaFeature::Height(MediaRange::Max, 0.0)],
+                    },
+                    MediaQuery {
+                        negated: true,
+                        media_type: MediaType::Print,
+                        features: vec![],
+                    },
+                ]),
+                "(resolution: 96dpi)" => MediaQueryList(vec![MediaQuery {
+                    negated: false,
+                    media_type: MediaType::All,
+                    features: vec![MediaFeature::Resolution(MediaRange::Exact, 1.0)],
+                }]),
+            }
+
+            failure {
+                "(min-width: 10em)",
+                "(min-prefers-color-scheme: dark)",
+                "(depth: 10px)",
+                "screen and",
+            }
+        }
+    }
+
+    fn parse(query: &str) -> MediaQueryList {
+        let mut parser_input = ParserInput::new(query);
+        let mut parser = Parser::new(&mut parser_input);
+        MediaQueryList::parse(&mut parser).unwrap()
+    }
+
+    const ENVIRONMENT: MediaEnvironment = MediaEnvironment {
+        color_scheme: ColorScheme::Dark,
+        width: 800.0,
+        height: 600.0,
+        scale_factor: 2.0,
+    };
+
+    #[test]
+    fn parse_media_query() {
+        assert_eq!(
+            parse("screen and (min-width: 600px) and (prefers-color-scheme: dark)"),
+            MediaQueryList(vec![MediaQuery {
+                negated: false,
+                media_type: MediaType::Screen,
+                features: vec![
+                    MediaFeature::Width(MediaRange::Min, 600.0),
+                    MediaFeature::PrefersColorScheme(ColorScheme::Dark),
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn match_media_query() {
+        assert!(parse("(prefers-color-scheme: dark)").matches(&ENVIRONMENT));
+        assert!(!parse("(prefers-color-scheme: light)").matches(&ENVIRONMENT));
+        assert!(parse("(min-width: 600px) and (max-height: 600px)").matches(&ENVIRONMENT));
+        assert!(!parse("(max-width: 600px)").matches(&ENVIRONMENT));
+        assert!(parse("(max-width: 600px), (min-resolution: 2dppx)").matches(&ENVIRONMENT));
+        assert!(parse("not print").matches(&ENVIRONMENT));
+        assert!(!parse("not all and (min-resolution: 192dpi)").matches(&ENVIRONMENT));
+    }
+}
@@ -2,9 +2,9 @@ use std::hash::Hash;
 
 use cssparser::*;
 use precomputed_hash::PrecomputedHash;
-use selectors::SelectorImpl;
+use selectors::{parser::ParseRelative, SelectorImpl, SelectorList};
 
-use crate::{CustomParseError, Direction, Parse, ParserOptions, PseudoClass, PseudoElement};
+use crate::{CustomParseError, Direction, Error, Parse, ParserOptions, PseudoClass, PseudoElement};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Selectors;
@@ -24,6 +24,12 @@ impl std::convert::From<&str> for SelectorString {
     }
 }
 
+impl AsRef<str> for SelectorString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
 impl ToCss for SelectorString {
     fn to_css<W>(&self, dest: &mut W) -> std::fmt::Result
     where
@@ -86,6 +92,14 @@ impl<'i> selectors::parser::Parser<'i> for SelectorParser<'_, 'i> {
     type Impl = Selectors;
     type Error = CustomParseError<'i>;
 
+    fn parse_parent_selector(&self) -> bool {
+        true
+    }
+
+    fn parse_is_and_where(&self) -> bool {
+        true
+    }
+
     fn parse_non_ts_pseudo_class(
         &self,
         _: SourceLocation,
@@ -98,6 +112,7 @@ impl<'i> selectors::parser::Parser<'i> for SelectorParser<'_, 'i> {
             "over" => Over,
             "focus" => Focus,
             "focus-visible" => FocusVisible,
+            "focus-within" => FocusWithin,
             "enabled" => Enabled,
             "disabled" => Disabled,
             "read-only" => ReadOnly,
@@ -167,6 +182,60 @@ impl<'i> selectors::parser::Parser<'i> for SelectorParser<'_, 'i> {
     }
 }
 
+/// Writes an element name, class name or ID for use in a selector, escaping any characters which would otherwise
+/// be interpreted as part of the selector syntax.
+pub fn serialize_selector_ident(ident: &str, dest: &mut String) {
+    // Writing to a `String` cannot fail.
+    let _ = serialize_identifier(ident, dest);
+}
+
+/// Returns the CSS text of a selector, such as `list > list-item:checked`.
+pub fn selector_to_css(selector: &selectors::parser::Selector<Selectors>) -> String {
+    selector.to_css_string()
+}
+
+/// Parses a comma separated list of selectors, such as `list > list-item:checked, #submit`.
+pub fn parse_selectors(
+    input: &str,
+) -> Result<SelectorList<Selectors>, Error<CustomParseError<'_>>> {
+    let mut parser_input = ParserInput::new(input);
+    let mut parser = Parser::new(&mut parser_input);
+    let options = ParserOptions::new();
+
+    parser
+        .parse_entirely(|parser| {
+            SelectorList::parse(&SelectorParser { options: &options }, parser, ParseRelative::No)
+        })
+        .map_err(|err| Error::from(err, String::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(input: &str) -> String {
+        parse_selectors(input).unwrap().to_css_string()
+    }
+
+    #[test]
+    fn parse_attribute_selectors() {
+        assert_eq!(round_trip("[data-severity]"), "[data-severity]");
+        assert_eq!(round_trip(".alert[data-severity=error]"), ".alert[data-severity=error]");
+        assert_eq!(round_trip("[data-tags~=large]"), "[data-tags~=large]");
+        assert_eq!(round_trip("[lang|=en]"), "[lang|=en]");
+        assert_eq!(round_trip("[data-severity^=inf]"), "[data-severity^=inf]");
+        assert_eq!(round_trip("[data-severity$=fo]"), "[data-severity$=fo]");
+        assert_eq!(round_trip("[data-severity*=nf]"), "[data-severity*=nf]");
+    }
+
+    #[test]
+    fn parse_invalid_attribute_selectors() {
+        assert!(parse_selectors("[data-severity=]").is_err());
+        assert!(parse_selectors("[=error]").is_err());
+        assert!(parse_selectors("[data-severity error]").is_err());
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use selectors::{
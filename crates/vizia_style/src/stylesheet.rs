@@ -47,6 +47,8 @@ impl<'i> StyleSheet<'i> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CssRule;
+    use std::sync::{Arc, RwLock};
 
     const CSS_EXAMPLE: &str = r#"
 button label {
@@ -129,6 +131,89 @@ test {
         let style_sheet = StyleSheet::parse(CSS_EXAMPLE, ParserOptions::default());
         println!("{:#?}", style_sheet);
     }
+
+    #[test]
+    fn collect_warnings() {
+        let warnings = Arc::new(RwLock::new(Vec::new()));
+        let options = ParserOptions {
+            filename: String::from("test.css"),
+            warnings: Some(warnings.clone()),
+            ..ParserOptions::new()
+        };
+
+        let css = ".a {\n    backgrond-color: red;\n    width: wide;\n    height: var(--h);\n}\n.b:nope(1) {}\n";
+        let style_sheet = StyleSheet::parse(css, options);
+        assert!(style_sheet.is_ok());
+
+        let warnings = warnings.read().unwrap();
+        assert_eq!(warnings.len(), 3);
+
+        let lines = warnings
+            .iter()
+            .map(|warning| warning.location.as_ref().map(|location| location.line))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![Some(1), Some(2), Some(5)]);
+        assert!(warnings
+            .iter()
+            .all(|warning| warning.location.as_ref().unwrap().filename == "test.css"));
+
+        assert_eq!(warnings[0].kind, CustomParseError::UnknownProperty("backgrond-color".into()));
+        assert_eq!(warnings[1].kind, CustomParseError::InvalidPropertyValue("width".into()));
+        assert!(matches!(warnings[2].kind, CustomParseError::SelectorError(_)));
+        assert!(!warnings[2].kind.is_warning());
+    }
+
+    #[test]
+    fn parse_nested_rules() {
+        let css = r#"
+            .button {
+                color: red;
+
+                &:hover {
+                    color: blue;
+                }
+
+                > label {
+                    width: 10px;
+                }
+
+                @media (min-width: 100px) {
+                    height: 20px;
+                }
+
+                @keyframes pulse {
+                    from { opacity: 0; }
+                    to { opacity: 1; }
+                }
+            }
+        "#;
+
+        let style_sheet = StyleSheet::parse(css, ParserOptions::new()).unwrap();
+        assert_eq!(style_sheet.rules.0.len(), 1);
+
+        let CssRule::Style(style_rule) = &style_sheet.rules.0[0] else {
+            panic!("expected a style rule");
+        };
+        assert_eq!(style_rule.declarations.declarations.len(), 1);
+
+        let nested = &style_rule.rules.0;
+        assert_eq!(nested.len(), 4);
+
+        let selector = |rule: &CssRule| match rule {
+            CssRule::Style(rule) => rule.selectors.to_css_string(),
+            _ => panic!("expected a style rule"),
+        };
+        assert_eq!(selector(&nested[0]), "&:hover");
+        assert!(selector(&nested[1]).ends_with("> label"));
+
+        let CssRule::Media(media_rule) = &nested[2] else {
+            panic!("expected a media rule");
+        };
+        assert_eq!(media_rule.rules.0.len(), 1);
+        assert_eq!(selector(&media_rule.rules.0[0]), "&");
+
+        assert!(matches!(&nested[3], CssRule::Keyframes(_)));
+    }
 }
 
 // use cssparser::*;
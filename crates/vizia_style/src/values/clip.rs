@@ -1,11 +1,30 @@
-use crate::{AutoKeyword, Length, LengthOrPercentage, Parse, Rect};
+use crate::{AutoKeyword, CustomParseError, Length, LengthOrPercentage, Parse, Position, Rect};
 use cssparser::*;
 
+/// A shape used to clip the drawing of a view and its children.
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum ClipPath {
+    /// No clipping shape.
     #[default]
     Auto,
+    /// A rectangle inset from each side of the view, specified by `inset()` or `rect()`.
     Shape(Rect<LengthOrPercentage>),
+    /// A rectangle inset from each side of the view with rounded corners, specified by `inset(... round ...)`.
+    ///
+    /// The second rect holds the top-left, top-right, bottom-right and bottom-left corner radii.
+    Inset(Rect<LengthOrPercentage>, Rect<LengthOrPercentage>),
+    /// A circle with an optional radius and a center position, specified by `circle()`.
+    ///
+    /// If no radius is given the circle extends to the closest side of the view.
+    Circle(Option<LengthOrPercentage>, Position),
+    /// An ellipse with optional horizontal and vertical radii and a center position, specified by `ellipse()`.
+    ///
+    /// If no radii are given the ellipse extends to the closest sides of the view.
+    Ellipse(Option<(LengthOrPercentage, LengthOrPercentage)>, Position),
+    /// A polygon with a list of vertices, specified by `polygon()`.
+    Polygon(Vec<(LengthOrPercentage, LengthOrPercentage)>),
+    /// An SVG path, specified by `path()`, with coordinates relative to the top-left corner of the view.
+    Path(String),
 }
 
 impl From<Rect<LengthOrPercentage>> for ClipPath {
@@ -31,6 +50,52 @@ impl From<Length> for ClipPath {
     }
 }
 
+impl From<&str> for ClipPath {
+    fn from(s: &str) -> Self {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        ClipPath::parse(&mut parser).unwrap_or_default()
+    }
+}
+
+/// Parses one to four length-percentage values, expanded to four sides in the same way as [`Rect`].
+fn parse_sides<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Rect<LengthOrPercentage>, ParseError<'i, CustomParseError<'i>>> {
+    let first = LengthOrPercentage::parse(input)?;
+    let Ok(second) = input.try_parse(LengthOrPercentage::parse) else {
+        return Ok(Rect(first.clone(), first.clone(), first.clone(), first));
+    };
+    let Ok(third) = input.try_parse(LengthOrPercentage::parse) else {
+        return Ok(Rect(first.clone(), second.clone(), first, second));
+    };
+    let Ok(fourth) = input.try_parse(LengthOrPercentage::parse) else {
+        return Ok(Rect(first, second.clone(), third, second));
+    };
+
+    Ok(Rect(first, second, third, fourth))
+}
+
+/// Parses an optional `at <position>`, defaulting to the center of the view.
+fn parse_center<'i>(input: &mut Parser<'i, '_>) -> Position {
+    if input.try_parse(|input| input.expect_ident_matching("at")).is_ok() {
+        input.try_parse(Position::parse).unwrap_or_else(|_| Position::center())
+    } else {
+        Position::center()
+    }
+}
+
+/// Parses a shape radius, where the `closest-side` keyword is represented by `None`.
+fn parse_radius<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Option<LengthOrPercentage>, ParseError<'i, CustomParseError<'i>>> {
+    if input.try_parse(|input| input.expect_ident_matching("closest-side")).is_ok() {
+        return Ok(None);
+    }
+
+    LengthOrPercentage::parse(input).map(Some)
+}
+
 impl<'i> Parse<'i> for ClipPath {
     fn parse<'t>(
         input: &mut cssparser::Parser<'i, 't>,
@@ -44,8 +109,45 @@ impl<'i> Parse<'i> for ClipPath {
                 let location = input.current_source_location();
                 match_ignore_ascii_case! { &function,
                     "inset" | "rect" => {
-                        let rect = Rect::parse(input)?;
-                        Ok(ClipPath::Shape(rect))
+                        let rect = parse_sides(input)?;
+                        if input.try_parse(|input| input.expect_ident_matching("round")).is_ok() {
+                            let radii = parse_sides(input)?;
+                            Ok(ClipPath::Inset(rect, radii))
+                        } else {
+                            Ok(ClipPath::Shape(rect))
+                        }
+                    },
+
+                    "circle" => {
+                        let radius = input.try_parse(parse_radius).unwrap_or(None);
+                        let center = parse_center(input);
+                        Ok(ClipPath::Circle(radius, center))
+                    },
+
+                    "ellipse" => {
+                        let radii = input
+                            .try_parse(|input| {
+                                let rx = LengthOrPercentage::parse(input)?;
+                                let ry = LengthOrPercentage::parse(input)?;
+                                Ok::<_, ParseError<'i, CustomParseError<'i>>>((rx, ry))
+                            })
+                            .ok();
+                        let center = parse_center(input);
+                        Ok(ClipPath::Ellipse(radii, center))
+                    },
+
+                    "polygon" => {
+                        let points = input.parse_comma_separated(|input| {
+                            let x = LengthOrPercentage::parse(input)?;
+                            let y = LengthOrPercentage::parse(input)?;
+                            Ok((x, y))
+                        })?;
+                        Ok(ClipPath::Polygon(points))
+                    },
+
+                    "path" => {
+                        let data = input.expect_string()?.as_ref().to_owned();
+                        Ok(ClipPath::Path(data))
                     },
 
                     _ => {
@@ -56,3 +158,63 @@ impl<'i> Parse<'i> for ClipPath {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        ClipPath, parse_clip_path,
+
+        custom {
+            success {
+                "auto" => ClipPath::Auto,
+                "inset(10px)" => ClipPath::Shape(Rect(
+                    LengthOrPercentage::Length(Length::px(10.0)),
+                    LengthOrPercentage::Length(Length::px(10.0)),
+                    LengthOrPercentage::Length(Length::px(10.0)),
+                    LengthOrPercentage::Length(Length::px(10.0)),
+                )),
+                "inset(10px 20px round 5px)" => ClipPath::Inset(
+                    Rect(
+                        LengthOrPercentage::Length(Length::px(10.0)),
+                        LengthOrPercentage::Length(Length::px(20.0)),
+                        LengthOrPercentage::Length(Length::px(10.0)),
+                        LengthOrPercentage::Length(Length::px(20.0)),
+                    ),
+                    Rect(
+                        LengthOrPercentage::Length(Length::px(5.0)),
+                        LengthOrPercentage::Length(Length::px(5.0)),
+                        LengthOrPercentage::Length(Length::px(5.0)),
+                        LengthOrPercentage::Length(Length::px(5.0)),
+                    ),
+                ),
+                "circle()" => ClipPath::Circle(None, Position::center()),
+                "circle(50% at 10px 20px)" => ClipPath::Circle(
+                    Some(LengthOrPercentage::Percentage(50.0)),
+                    Position::from((Length::px(10.0), Length::px(20.0))),
+                ),
+                "ellipse(10px 20px)" => ClipPath::Ellipse(
+                    Some((
+                        LengthOrPercentage::Length(Length::px(10.0)),
+                        LengthOrPercentage::Length(Length::px(20.0)),
+                    )),
+                    Position::center(),
+                ),
+                "polygon(50% 0%, 100% 100%, 0% 100%)" => ClipPath::Polygon(vec![
+                    (LengthOrPercentage::Percentage(50.0), LengthOrPercentage::Percentage(0.0)),
+                    (LengthOrPercentage::Percentage(100.0), LengthOrPercentage::Percentage(100.0)),
+                    (LengthOrPercentage::Percentage(0.0), LengthOrPercentage::Percentage(100.0)),
+                ]),
+                "path(\"M 0 0 L 10 0 L 10 10 Z\")" => ClipPath::Path(String::from("M 0 0 L 10 0 L 10 10 Z")),
+            }
+
+            failure {
+                "test",
+                "polygon(50%)",
+                "path(10px)",
+            }
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{CustomParseError, InterpolationColorSpace, Parse, RGBA};
+use cssparser::*;
+
+/// Determines which way around the hue wheel a polar color space interpolates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HueInterpolation {
+    /// Takes the shorter arc between the two hues.
+    #[default]
+    Shorter,
+    /// Takes the longer arc between the two hues.
+    Longer,
+    /// Always interpolates with increasing hue.
+    Increasing,
+    /// Always interpolates with decreasing hue.
+    Decreasing,
+}
+
+impl<'i> Parse<'i> for HueInterpolation {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let location = input.current_source_location();
+        let ident = input.expect_ident()?.clone();
+        let method = match_ignore_ascii_case! { &ident,
+            "shorter" => HueInterpolation::Shorter,
+            "longer" => HueInterpolation::Longer,
+            "increasing" => HueInterpolation::Increasing,
+            "decreasing" => HueInterpolation::Decreasing,
+            _ => return Err(location.new_unexpected_token_error(Token::Ident(ident))),
+        };
+
+        input.expect_ident_matching("hue")?;
+
+        Ok(method)
+    }
+}
+
+/// Describes how colors are interpolated, specified by `in <color-space> [<hue-method> hue]`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ColorInterpolation {
+    /// The color space used for interpolation.
+    pub color_space: InterpolationColorSpace,
+    /// The direction of hue interpolation for polar color spaces.
+    pub hue: HueInterpolation,
+}
+
+impl ColorInterpolation {
+    /// Creates a new color interpolation method for the given color space.
+    pub fn new(color_space: InterpolationColorSpace) -> Self {
+        Self { color_space, hue: HueInterpolation::default() }
+    }
+
+    /// Sets the direction of hue interpolation.
+    pub fn with_hue(mut self, hue: HueInterpolation) -> Self {
+        self.hue = hue;
+        self
+    }
+
+    /// Returns the color a fraction `t` of the way between `start` and `end`.
+    pub fn mix(&self, start: RGBA, end: RGBA, t: f32) -> RGBA {
+        let alpha = lerp(start.alpha as f32, end.alpha as f32, t);
+
+        let [r, g, b] = match self.color_space {
+            InterpolationColorSpace::Srgb => {
+                let [r0, g0, b0] = to_srgb(start);
+                let [r1, g1, b1] = to_srgb(end);
+                [lerp(r0, r1, t), lerp(g0, g1, t), lerp(b0, b1, t)]
+            }
+
+            InterpolationColorSpace::SrgbLinear => {
+                let [r0, g0, b0] = to_srgb(start).map(to_linear);
+                let [r1, g1, b1] = to_srgb(end).map(to_linear);
+                [lerp(r0, r1, t), lerp(g0, g1, t), lerp(b0, b1, t)].map(from_linear)
+            }
+
+            InterpolationColorSpace::Oklab => {
+                let [l0, a0, b0] = to_oklab(start);
+                let [l1, a1, b1] = to_oklab(end);
+                from_oklab([lerp(l0, l1, t), lerp(a0, a1, t), lerp(b0, b1, t)])
+            }
+
+            InterpolationColorSpace::Oklch => {
+                let [l0, c0, h0] = to_polar(to_oklab(start));
+                let [l1, c1, h1] = to_polar(to_oklab(end));
+                let (h0, h1) = fix_hues(h0, h1, c0 < 1e-4, c1 < 1e-4, self.hue);
+                from_oklab(from_polar([lerp(l0, l1, t), lerp(c0, c1, t), lerp(h0, h1, t)]))
+            }
+
+            InterpolationColorSpace::Hsl => {
+                let [h0, s0, l0] = to_hsl(to_srgb(start));
+                let [h1, s1, l1] = to_hsl(to_srgb(end));
+                let (h0, h1) = fix_hues(h0, h1, s0 < 1e-4, s1 < 1e-4, self.hue);
+                from_hsl([lerp(h0, h1, t), lerp(s0, s1, t), lerp(l0, l1, t)])
+            }
+        };
+
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        RGBA::rgba(channel(r), channel(g), channel(b), alpha.round() as u8)
+    }
+}
+
+impl<'i> Parse<'i> for ColorInterpolation {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.expect_ident_matching("in")?;
+        let color_space = InterpolationColorSpace::parse(input)?;
+        let hue = if color_space.is_polar() {
+            input.try_parse(HueInterpolation::parse).unwrap_or_default()
+        } else {
+            HueInterpolation::default()
+        };
+
+        Ok(ColorInterpolation { color_space, hue })
+    }
+}
+
+impl From<InterpolationColorSpace> for ColorInterpolation {
+    fn from(color_space: InterpolationColorSpace) -> Self {
+        ColorInterpolation::new(color_space)
+    }
+}
+
+fn lerp(start: f32, end: f32, t: f32) -> f32 {
+    start + (end - start) * t
+}
+
+fn to_srgb(color: RGBA) -> [f32; 3] {
+    [color.red as f32 / 255.0, color.green as f32 / 255.0, color.blue as f32 / 255.0]
+}
+
+fn to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn to_oklab(color: RGBA) -> [f32; 3] {
+    let [r, g, b] = to_srgb(color).map(to_linear);
+
+    let l = (0.4122215 * r + 0.5363325 * g + 0.05144599 * b).cbrt();
+    let m = (0.2119035 * r + 0.6806996 * g + 0.107397 * b).cbrt();
+    let s = (0.08830246 * r + 0.2817188 * g + 0.6299787 * b).cbrt();
+
+    [
+        0.2104543 * l + 0.7936178 * m - 0.00407205 * s,
+        1.977998 * l - 2.428592 * m + 0.4505937 * s,
+        0.02590404 * l + 0.7827718 * m - 0.8086758 * s,
+    ]
+}
+
+fn from_oklab([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = (l + 0.3963378 * a + 0.2158038 * b).powi(3);
+    let m_ = (l - 0.1055613 * a - 0.06385417 * b).powi(3);
+    let s_ = (l - 0.08948418 * a - 1.291486 * b).powi(3);
+
+    [
+        4.076742 * l_ - 3.307712 * m_ + 0.2309699 * s_,
+        -1.268438 * l_ + 2.609757 * m_ - 0.3413194 * s_,
+        -0.00419609 * l_ - 0.7034186 * m_ + 1.707615 * s_,
+    ]
+    .map(from_linear)
+}
+
+fn to_polar([l, a, b]: [f32; 3]) -> [f32; 3] {
+    [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+}
+
+fn from_polar([l, c, h]: [f32; 3]) -> [f32; 3] {
+    let (sin, cos) = h.to_radians().sin_cos();
+    [l, c * cos, c * sin]
+}
+
+fn to_hsl([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+
+    if d == 0.0 {
+        return [0.0, 0.0, l];
+    }
+
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+
+    [h * 60.0, s, l]
+}
+
+fn from_hsl([h, s, l]: [f32; 3]) -> [f32; 3] {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    [r + m, g + m, b + m]
+}
+
+/// Adjusts a pair of hues so that interpolating between them follows the given hue method.
+///
+/// Achromatic colors have no meaningful hue and take on the hue of the other color.
+fn fix_hues(
+    h0: f32,
+    h1: f32,
+    achromatic0: bool,
+    achromatic1: bool,
+    method: HueInterpolation,
+) -> (f32, f32) {
+    let (h0, h1) = match (achromatic0, achromatic1) {
+        (true, false) => (h1, h1),
+        (false, true) => (h0, h0),
+        _ => (h0, h1),
+    };
+
+    let delta = h1 - h0;
+    let h1 = match method {
+        HueInterpolation::Shorter if delta > 180.0 => h1 - 360.0,
+        HueInterpolation::Shorter if delta < -180.0 => h1 + 360.0,
+        HueInterpolation::Longer if (0.0..180.0).contains(&delta) && delta != 0.0 => h1 - 360.0,
+        HueInterpolation::Longer if (-180.0..0.0).contains(&delta) => h1 + 360.0,
+        HueInterpolation::Increasing if delta < 0.0 => h1 + 360.0,
+        HueInterpolation::Decreasing if delta > 0.0 => h1 - 360.0,
+        _ => h1,
+    };
+
+    (h0, h1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        ColorInterpolation, parse_color_interpolation,
+
+        custom {
+            success {
+                "in srgb" => ColorInterpolation::new(InterpolationColorSpace::Srgb),
+                "in oklab" => ColorInterpolation::new(InterpolationColorSpace::Oklab),
+                "in oklch longer hue" => ColorInterpolation::new(InterpolationColorSpace::Oklch)
+                    .with_hue(HueInterpolation::Longer),
+            }
+
+            failure {
+                "oklab",
+                "in lab",
+            }
+        }
+    }
+
+    #[test]
+    fn mix_endpoints_are_exact() {
+        let start = RGBA::rgba(255, 0, 0, 255);
+        let end = RGBA::rgba(0, 0, 255, 255);
+
+        for color_space in [
+            InterpolationColorSpace::Srgb,
+            InterpolationColorSpace::SrgbLinear,
+            InterpolationColorSpace::Oklab,
+            InterpolationColorSpace::Oklch,
+            InterpolationColorSpace::Hsl,
+        ] {
+            let interpolation = ColorInterpolation::new(color_space);
+            assert_eq!(interpolation.mix(start, end, 0.0), start);
+            assert_eq!(interpolation.mix(start, end, 1.0), end);
+        }
+    }
+
+    #[test]
+    fn hue_methods_choose_direction() {
+        let red = RGBA::rgba(255, 0, 0, 255);
+        let blue = RGBA::rgba(0, 0, 255, 255);
+
+        // Red (0deg) to blue (240deg) takes the shorter path through magenta in HSL.
+        let shorter = ColorInterpolation::new(InterpolationColorSpace::Hsl).mix(red, blue, 0.5);
+        assert_eq!(shorter, RGBA::rgba(255, 0, 255, 255));
+
+        // The longer path passes through green.
+        let longer = ColorInterpolation::new(InterpolationColorSpace::Hsl)
+            .with_hue(HueInterpolation::Longer)
+            .mix(red, blue, 0.5);
+        assert_eq!(longer, RGBA::rgba(0, 255, 0, 255));
+    }
+}
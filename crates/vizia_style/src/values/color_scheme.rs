@@ -0,0 +1,11 @@
+use crate::{macros::define_enum, Parse};
+
+define_enum! {
+    /// The theme mode matched by the `prefers-color-scheme` media feature.
+    pub enum ColorScheme {
+        /// A light theme.
+        "light": Light,
+        /// A dark theme.
+        "dark": Dark,
+    }
+}
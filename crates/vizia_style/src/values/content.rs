@@ -0,0 +1,62 @@
+use crate::{CustomParseError, Parse};
+use cssparser::*;
+
+/// The content generated by a `::before` or `::after` pseudo-element.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Content {
+    /// No content is generated.
+    #[default]
+    None,
+    /// Text content, which can also be an icon when used with an icon font.
+    Text(String),
+}
+
+impl<'i> Parse<'i> for Content {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if input
+            .try_parse(|input| {
+                let location = input.current_source_location();
+                let ident = input.expect_ident()?;
+                match_ignore_ascii_case! { ident,
+                    "none" | "normal" => Ok(()),
+                    _ => Err(location.new_unexpected_token_error(Token::Ident(ident.clone()))),
+                }
+            })
+            .is_ok()
+        {
+            return Ok(Content::None);
+        }
+
+        // Consecutive strings are concatenated.
+        let mut text = input.expect_string()?.to_string();
+        while let Ok(string) = input.try_parse(|input| input.expect_string().cloned()) {
+            text.push_str(&string);
+        }
+
+        Ok(Content::Text(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        Content, parse_content,
+
+        custom {
+            success {
+                "none" => Content::None,
+                "normal" => Content::None,
+                "\"*\"" => Content::Text(String::from("*")),
+                "\"a\" \"b\"" => Content::Text(String::from("ab")),
+                "\"\\e900\"" => Content::Text(String::from("\u{e900}")),
+            }
+            failure {
+                "auto",
+                "12px",
+            }
+        }
+    }
+}
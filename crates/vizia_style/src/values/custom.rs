@@ -1,5 +1,6 @@
 use cssparser::*;
 use cssparser_color::Color;
+use std::fmt::Write;
 
 use crate::{CustomParseError, DashedIdent, Parse};
 
@@ -70,6 +71,11 @@ impl TokenOrValue<'_> {
 }
 
 impl<'i> TokenList<'i> {
+    /// Returns whether the token list contains a variable reference.
+    pub fn has_var(&self) -> bool {
+        self.0.iter().any(|token| matches!(token, TokenOrValue::Var(_)))
+    }
+
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
         input.parse_until_before(Delimiter::Bang | Delimiter::Semicolon, |input| {
             let mut tokens = vec![];
@@ -127,7 +133,8 @@ impl<'i> TokenList<'i> {
                             Ok(TokenOrValue::Var(var))
                         })?;
                         tokens.push(var);
-                        last_is_delim = true;
+                        // Whitespace after a variable is kept because it may separate the substituted value from the next token.
+                        last_is_delim = false;
                         last_is_whitespace = false;
                     } else {
                         tokens.push(Token::Function(f).into());
@@ -170,8 +177,10 @@ impl<'i> TokenList<'i> {
                     last_is_whitespace = false;
                 }
                 Ok(token) => {
+                    // Whitespace around `+` and `-` is kept, as `calc()` requires it on both sides of them.
                     last_is_delim =
-                        matches!(token, cssparser::Token::Delim(_) | cssparser::Token::Comma);
+                        matches!(token, cssparser::Token::Delim(_) | cssparser::Token::Comma)
+                            && !matches!(token, cssparser::Token::Delim('+' | '-'));
 
                     // If this is a delimeter, and the last token was whitespace,
                     // replace the whitespace with the delimeter since both are not required.
@@ -236,12 +245,170 @@ impl<'i> Variable<'i> {
     }
 }
 
+/// An owned property value which may contain `var()` references, resolved once the values of the referenced
+/// custom properties are known.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnresolvedValue(Vec<UnresolvedPart>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum UnresolvedPart {
+    /// Serialized tokens which don't need to be resolved.
+    Text(String),
+    /// A variable reference.
+    Var { name: String, fallback: Option<UnresolvedValue> },
+}
+
+impl UnresolvedValue {
+    /// Parses a property value from a string.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut parser_input = ParserInput::new(input);
+        let mut parser = Parser::new(&mut parser_input);
+        let tokens = TokenList::parse(&mut parser).ok()?;
+        parser.expect_exhausted().ok()?;
+        Some(Self::from(&tokens))
+    }
+
+    /// Returns whether the value contains a variable reference.
+    pub fn has_var(&self) -> bool {
+        self.0.iter().any(|part| matches!(part, UnresolvedPart::Var { .. }))
+    }
+
+    /// Substitutes the variable references in the value, returning the resulting CSS text.
+    ///
+    /// The `lookup` closure returns the value of a custom property given its name. If a referenced property has
+    /// no value then its fallback is used, and if there is no fallback the whole value is invalid and `None`
+    /// is returned.
+    pub fn resolve(&self, lookup: &mut dyn FnMut(&str) -> Option<String>) -> Option<String> {
+        let mut output = String::new();
+
+        for part in self.0.iter() {
+            match part {
+                UnresolvedPart::Text(text) => output.push_str(text),
+                UnresolvedPart::Var { name, fallback } => {
+                    let value = match lookup(name) {
+                        Some(value) => value,
+                        None => fallback.as_ref()?.resolve(lookup)?,
+                    };
+                    output.push_str(&value);
+                }
+            }
+        }
+
+        Some(output)
+    }
+}
+
+impl From<&TokenList<'_>> for UnresolvedValue {
+    fn from(tokens: &TokenList<'_>) -> Self {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        for token in tokens.0.iter() {
+            match token {
+                TokenOrValue::Token(token) => {
+                    let _ = token.to_css(&mut text);
+                }
+                TokenOrValue::Color(color) => {
+                    let _ = color.to_css(&mut text);
+                }
+                TokenOrValue::Var(var) => {
+                    if !text.is_empty() {
+                        parts.push(UnresolvedPart::Text(std::mem::take(&mut text)));
+                    }
+
+                    parts.push(UnresolvedPart::Var {
+                        name: var.name.0.to_string(),
+                        fallback: var.fallback.as_ref().map(UnresolvedValue::from),
+                    });
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(UnresolvedPart::Text(text));
+        }
+
+        UnresolvedValue(parts)
+    }
+}
+
+impl std::fmt::Display for UnresolvedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in self.0.iter() {
+            match part {
+                UnresolvedPart::Text(text) => f.write_str(text)?,
+                UnresolvedPart::Var { name, fallback } => {
+                    f.write_str("var(")?;
+                    f.write_str(name)?;
+                    if let Some(fallback) = fallback {
+                        write!(f, ", {fallback}")?;
+                    }
+                    f.write_char(')')?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cssparser::CowRcStr;
+    use std::collections::HashMap;
 
+    use super::UnresolvedValue;
     use crate::CustomProperty;
 
+    fn resolve(value: &str, variables: &[(&str, &str)]) -> Option<String> {
+        let variables: HashMap<&str, &str> = variables.iter().copied().collect();
+        UnresolvedValue::parse(value)
+            .unwrap()
+            .resolve(&mut |name| variables.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn resolve_var() {
+        assert_eq!(
+            resolve("var(--width) solid var(--color)", &[("--width", "2px"), ("--color", "red")]),
+            Some(String::from("2px solid red"))
+        );
+    }
+
+    #[test]
+    fn resolve_var_fallback() {
+        assert_eq!(resolve("var(--missing, 4px)", &[]), Some(String::from("4px")));
+        assert_eq!(
+            resolve("var(--missing, var(--size))", &[("--size", "8px")]),
+            Some(String::from("8px"))
+        );
+        assert_eq!(resolve("var(--missing)", &[]), None);
+    }
+
+    #[test]
+    fn resolve_var_in_function() {
+        assert_eq!(
+            resolve("calc(var(--size) * 2)", &[("--size", "8px")]),
+            Some(String::from("calc(8px*2)"))
+        );
+        assert_eq!(
+            resolve("calc(var(--size) + 2px)", &[("--size", "8px")]),
+            Some(String::from("calc(8px + 2px)"))
+        );
+        assert_eq!(
+            resolve("calc(var(--size) - 2px)", &[("--size", "8px")]),
+            Some(String::from("calc(8px - 2px)"))
+        );
+    }
+
+    #[test]
+    fn unresolved_value_to_string() {
+        let value = UnresolvedValue::parse("1px var(--a, var(--b))").unwrap();
+        assert!(value.has_var());
+        assert_eq!(value.to_string(), "1px var(--a, var(--b))");
+        assert!(!UnresolvedValue::parse("red").unwrap().has_var());
+    }
+
     #[test]
     fn parse_custom_ident() {
         let success_string = "left";
@@ -3,10 +3,10 @@ use crate::{macros::define_enum, Parse};
 define_enum! {
     #[derive(Default)]
     pub enum Direction {
-        /// The entity will be rendered and acted on by the layout system.
+        /// Text flows from left to right.
         #[default]
         "ltr": Ltr,
-        /// The entity will not be rendered and acted on by the layout system.
+        /// Text flows from right to left.
         "rtl": Rtl,
     }
 }
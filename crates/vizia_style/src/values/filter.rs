@@ -0,0 +1,296 @@
+use crate::{Angle, Color, CustomParseError, Length, Parse, PercentageOrNumber, Shadow};
+use cssparser::*;
+
+/// A graphical effect applied to a view, such as a blur or a color shift.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// Applies a gaussian blur with the given radius.
+    Blur(Length),
+    /// Scales the brightness by the given factor.
+    Brightness(f32),
+    /// Scales the contrast by the given factor.
+    Contrast(f32),
+    /// Converts to grayscale by the given amount, where `1.0` is completely grayscale.
+    Grayscale(f32),
+    /// Scales the saturation by the given factor.
+    Saturate(f32),
+    /// Rotates the hue of each color by the given angle.
+    HueRotate(Angle),
+    /// Inverts the colors by the given amount, where `1.0` is completely inverted.
+    Invert(f32),
+    /// Converts to sepia by the given amount, where `1.0` is completely sepia.
+    Sepia(f32),
+    /// Draws a blurred and offset shadow following the shape of the content.
+    DropShadow(Shadow),
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter::Blur(Length::default())
+    }
+}
+
+impl Filter {
+    /// Returns the 4x5 row-major color matrix for the filter, or `None` for filters which are not color filters.
+    pub fn color_matrix(&self) -> Option<[f32; 20]> {
+        let matrix = match *self {
+            Filter::Brightness(amount) => [
+                amount, 0.0, 0.0, 0.0, 0.0, //
+                0.0, amount, 0.0, 0.0, 0.0, //
+                0.0, 0.0, amount, 0.0, 0.0, //
+                0.0, 0.0, 0.0, 1.0, 0.0,
+            ],
+
+            Filter::Contrast(amount) => {
+                let offset = 0.5 - 0.5 * amount;
+                [
+                    amount, 0.0, 0.0, 0.0, offset, //
+                    0.0, amount, 0.0, 0.0, offset, //
+                    0.0, 0.0, amount, 0.0, offset, //
+                    0.0, 0.0, 0.0, 1.0, 0.0,
+                ]
+            }
+
+            Filter::Grayscale(amount) => {
+                let a = 1.0 - amount.clamp(0.0, 1.0);
+                [
+                    0.2126 + 0.7874 * a,
+                    0.7152 - 0.7152 * a,
+                    0.0722 - 0.0722 * a,
+                    0.0,
+                    0.0,
+                    0.2126 - 0.2126 * a,
+                    0.7152 + 0.2848 * a,
+                    0.0722 - 0.0722 * a,
+                    0.0,
+                    0.0,
+                    0.2126 - 0.2126 * a,
+                    0.7152 - 0.7152 * a,
+                    0.0722 + 0.9278 * a,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    0.0,
+                ]
+            }
+
+            Filter::Saturate(amount) => [
+                0.213 + 0.787 * amount,
+                0.715 - 0.715 * amount,
+                0.072 - 0.072 * amount,
+                0.0,
+                0.0,
+                0.213 - 0.213 * amount,
+                0.715 + 0.285 * amount,
+                0.072 - 0.072 * amount,
+                0.0,
+                0.0,
+                0.213 - 0.213 * amount,
+                0.715 - 0.715 * amount,
+                0.072 + 0.928 * amount,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+                0.0,
+            ],
+
+            Filter::HueRotate(angle) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                [
+                    0.213 + cos * 0.787 - sin * 0.213,
+                    0.715 - cos * 0.715 - sin * 0.715,
+                    0.072 - cos * 0.072 + sin * 0.928,
+                    0.0,
+                    0.0,
+                    0.213 - cos * 0.213 + sin * 0.143,
+                    0.715 + cos * 0.285 + sin * 0.140,
+                    0.072 - cos * 0.072 - sin * 0.283,
+                    0.0,
+                    0.0,
+                    0.213 - cos * 0.213 - sin * 0.787,
+                    0.715 - cos * 0.715 + sin * 0.715,
+                    0.072 + cos * 0.928 + sin * 0.072,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    0.0,
+                ]
+            }
+
+            Filter::Invert(amount) => {
+                let amount = amount.clamp(0.0, 1.0);
+                let scale = 1.0 - 2.0 * amount;
+                [
+                    scale, 0.0, 0.0, 0.0, amount, //
+                    0.0, scale, 0.0, 0.0, amount, //
+                    0.0, 0.0, scale, 0.0, amount, //
+                    0.0, 0.0, 0.0, 1.0, 0.0,
+                ]
+            }
+
+            Filter::Sepia(amount) => {
+                let a = 1.0 - amount.clamp(0.0, 1.0);
+                [
+                    0.393 + 0.607 * a,
+                    0.769 - 0.769 * a,
+                    0.189 - 0.189 * a,
+                    0.0,
+                    0.0,
+                    0.349 - 0.349 * a,
+                    0.686 + 0.314 * a,
+                    0.168 - 0.168 * a,
+                    0.0,
+                    0.0,
+                    0.272 - 0.272 * a,
+                    0.534 - 0.534 * a,
+                    0.131 + 0.869 * a,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    0.0,
+                ]
+            }
+
+            Filter::Blur(_) | Filter::DropShadow(_) => return None,
+        };
+
+        Some(matrix)
+    }
+}
+
+fn parse_amount<'i>(input: &mut Parser<'i, '_>) -> f32 {
+    input.try_parse(PercentageOrNumber::parse).map(|amount| amount.to_factor()).unwrap_or(1.0)
+}
+
+fn parse_drop_shadow<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Shadow, ParseError<'i, CustomParseError<'i>>> {
+    let mut color = input.try_parse(Color::parse).ok();
+    let x_offset = Length::parse(input)?;
+    let y_offset = Length::parse(input)?;
+    let blur_radius = input.try_parse(Length::parse).ok();
+    if color.is_none() {
+        color = input.try_parse(Color::parse).ok();
+    }
+
+    Ok(Shadow::new(x_offset, y_offset, blur_radius, None, color, false))
+}
+
+impl<'i> Parse<'i> for Filter {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let function = input.expect_function()?.clone();
+
+        input.parse_nested_block(|input| {
+            let location = input.current_source_location();
+            match_ignore_ascii_case! { &function,
+                "blur" => {
+                    Ok(Filter::Blur(input.try_parse(Length::parse).unwrap_or(Length::px(0.0))))
+                },
+
+                "brightness" => Ok(Filter::Brightness(parse_amount(input))),
+                "contrast" => Ok(Filter::Contrast(parse_amount(input))),
+                "grayscale" => Ok(Filter::Grayscale(parse_amount(input))),
+                "saturate" => Ok(Filter::Saturate(parse_amount(input))),
+                "invert" => Ok(Filter::Invert(parse_amount(input))),
+                "sepia" => Ok(Filter::Sepia(parse_amount(input))),
+
+                "hue-rotate" => {
+                    Ok(Filter::HueRotate(input.try_parse(Angle::parse).unwrap_or(Angle::Deg(0.0))))
+                },
+
+                "drop-shadow" => Ok(Filter::DropShadow(parse_drop_shadow(input)?)),
+
+                _ => {
+                    Err(location.new_unexpected_token_error(Token::Ident(function)))
+                }
+            }
+        })
+    }
+}
+
+impl<'i> Parse<'i> for Vec<Filter> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+            return Ok(Vec::new());
+        }
+
+        let mut filters = vec![Filter::parse(input)?];
+        while let Ok(filter) = input.try_parse(Filter::parse) {
+            filters.push(filter);
+        }
+
+        Ok(filters)
+    }
+}
+
+impl From<&str> for Filter {
+    fn from(s: &str) -> Self {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        Filter::parse(&mut parser).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        Filter, parse_filter,
+
+        custom {
+            success {
+                "blur(4px)" => Filter::Blur(Length::px(4.0)),
+                "brightness(50%)" => Filter::Brightness(0.5),
+                "contrast(2)" => Filter::Contrast(2.0),
+                "grayscale()" => Filter::Grayscale(1.0),
+                "hue-rotate(90deg)" => Filter::HueRotate(Angle::Deg(90.0)),
+                "drop-shadow(red 2px 3px 4px)" => Filter::DropShadow(Shadow::new(
+                    Length::px(2.0),
+                    Length::px(3.0),
+                    Some(Length::px(4.0)),
+                    None,
+                    Some(Color::rgb(255, 0, 0)),
+                    false,
+                )),
+            }
+
+            failure {
+                "test",
+                "glow(2px)",
+            }
+        }
+    }
+
+    assert_parse! {
+        Vec<Filter>, parse_vec_filter,
+
+        custom {
+            success {
+                "none" => vec![],
+                "grayscale(100%) brightness(0.5)" => vec![
+                    Filter::Grayscale(1.0),
+                    Filter::Brightness(0.5),
+                ],
+            }
+
+            failure {
+                "test",
+                "123",
+            }
+        }
+    }
+}
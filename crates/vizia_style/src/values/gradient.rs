@@ -1,6 +1,6 @@
 use crate::{
-    Angle, Color, CustomParseError, HorizontalPositionKeyword, LengthOrPercentage, Parse, Position,
-    VerticalPositionKeyword,
+    Angle, Color, ColorInterpolation, CustomParseError, HorizontalPositionKeyword,
+    LengthOrPercentage, Parse, Percentage, Position, VerticalPositionKeyword,
 };
 use cssparser::*;
 
@@ -10,6 +10,7 @@ pub enum Gradient {
     None,
     Linear(LinearGradient),
     Radial(RadialGradient),
+    Conic(ConicGradient),
 }
 
 impl From<LinearGradient> for Gradient {
@@ -18,6 +19,18 @@ impl From<LinearGradient> for Gradient {
     }
 }
 
+impl From<RadialGradient> for Gradient {
+    fn from(radial_gradient: RadialGradient) -> Self {
+        Gradient::Radial(radial_gradient)
+    }
+}
+
+impl From<ConicGradient> for Gradient {
+    fn from(conic_gradient: ConicGradient) -> Self {
+        Gradient::Conic(conic_gradient)
+    }
+}
+
 impl<'i> Parse<'i> for Gradient {
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
         let location = input.current_source_location();
@@ -26,6 +39,16 @@ impl<'i> Parse<'i> for Gradient {
             match_ignore_ascii_case! { &func,
               "linear-gradient" => Ok(Gradient::Linear(LinearGradient::parse(input)?)),
               "radial-gradient" => Ok(Gradient::Radial(RadialGradient::parse(input)?)),
+              "conic-gradient" => Ok(Gradient::Conic(ConicGradient::parse(input)?)),
+              "repeating-linear-gradient" => {
+                  Ok(Gradient::Linear(LinearGradient { repeating: true, ..LinearGradient::parse(input)? }))
+              },
+              "repeating-radial-gradient" => {
+                  Ok(Gradient::Radial(RadialGradient { repeating: true, ..RadialGradient::parse(input)? }))
+              },
+              "repeating-conic-gradient" => {
+                  Ok(Gradient::Conic(ConicGradient { repeating: true, ..ConicGradient::parse(input)? }))
+              },
               _ => Err(location.new_unexpected_token_error(cssparser::Token::Ident(func.clone())))
             }
         })
@@ -113,19 +136,32 @@ impl From<(HorizontalPositionKeyword, VerticalPositionKeyword)> for LineDirectio
 pub struct LinearGradient {
     pub direction: LineDirection,
     pub stops: Vec<ColorStop<LengthOrPercentage>>,
+    /// The color space used to interpolate between stops.
+    pub interpolation: ColorInterpolation,
+    /// Whether the stops repeat beyond the last stop, as with `repeating-linear-gradient()`.
+    pub repeating: bool,
 }
 
 impl<'i> Parse<'i> for LinearGradient {
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
-        let direction = if let Ok(direction) = input.try_parse(|input| LineDirection::parse(input))
-        {
+        let mut direction = input.try_parse(|input| LineDirection::parse(input)).ok();
+        let interpolation = input.try_parse(ColorInterpolation::parse).ok();
+        if direction.is_none() && interpolation.is_some() {
+            direction = input.try_parse(|input| LineDirection::parse(input)).ok();
+        }
+
+        if direction.is_some() || interpolation.is_some() {
             input.expect_comma()?;
-            direction
-        } else {
-            LineDirection::Vertical(VerticalPositionKeyword::Bottom)
-        };
+        }
+
         let stops = parse_items(input)?;
-        Ok(LinearGradient { direction, stops })
+        Ok(LinearGradient {
+            direction: direction
+                .unwrap_or(LineDirection::Vertical(VerticalPositionKeyword::Bottom)),
+            stops,
+            interpolation: interpolation.unwrap_or_default(),
+            repeating: false,
+        })
     }
 }
 
@@ -174,6 +210,10 @@ fn parse_items<'i, 't, D: Parse<'i>>(
 pub struct RadialGradient {
     pub position: Position,
     pub stops: Vec<ColorStop<LengthOrPercentage>>,
+    /// The color space used to interpolate between stops.
+    pub interpolation: ColorInterpolation,
+    /// Whether the stops repeat beyond the last stop, as with `repeating-radial-gradient()`.
+    pub repeating: bool,
 }
 
 impl<'i> RadialGradient {
@@ -181,26 +221,130 @@ impl<'i> RadialGradient {
         input: &mut Parser<'i, 't>,
     ) -> Result<RadialGradient, ParseError<'i, CustomParseError<'i>>> {
         // let shape = input.try_parse(EndingShape::parse).ok();
-        let position = input
-            .try_parse(|input| {
-                input.expect_ident_matching("at")?;
-                Position::parse(input)
-            })
-            .ok();
+        let mut position = input.try_parse(parse_position).ok();
+        let interpolation = input.try_parse(ColorInterpolation::parse).ok();
+        if position.is_none() && interpolation.is_some() {
+            position = input.try_parse(parse_position).ok();
+        }
 
-        // if shape.is_some() || position.is_some() {
-        //     input.expect_comma()?;
-        // }
+        if position.is_some() || interpolation.is_some() {
+            input.expect_comma()?;
+        }
 
         let stops = parse_items(input)?;
         Ok(RadialGradient {
             // shape: shape.unwrap_or_default(),
             position: position.unwrap_or(Position::center()),
             stops,
+            interpolation: interpolation.unwrap_or_default(),
+            repeating: false,
+        })
+    }
+}
+
+fn parse_position<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Position, ParseError<'i, CustomParseError<'i>>> {
+    input.expect_ident_matching("at")?;
+    Position::parse(input)
+}
+
+/// A gradient whose colors sweep around a center point, specified by `conic-gradient()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConicGradient {
+    /// The angle at which the gradient starts, measured clockwise from the top.
+    pub angle: Angle,
+    /// The center of the gradient.
+    pub position: Position,
+    pub stops: Vec<ColorStop<AnglePercentage>>,
+    /// The color space used to interpolate between stops.
+    pub interpolation: ColorInterpolation,
+    /// Whether the stops repeat beyond the last stop, as with `repeating-conic-gradient()`.
+    pub repeating: bool,
+}
+
+impl<'i> Parse<'i> for ConicGradient {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut angle = None;
+        let mut position = None;
+        let mut interpolation = None;
+
+        loop {
+            if angle.is_none() {
+                if let Ok(value) = input.try_parse(|input| {
+                    input.expect_ident_matching("from")?;
+                    Angle::parse(input)
+                }) {
+                    angle = Some(value);
+                    continue;
+                }
+            }
+
+            if position.is_none() {
+                if let Ok(value) = input.try_parse(parse_position) {
+                    position = Some(value);
+                    continue;
+                }
+            }
+
+            if interpolation.is_none() {
+                if let Ok(value) = input.try_parse(ColorInterpolation::parse) {
+                    interpolation = Some(value);
+                    continue;
+                }
+            }
+
+            break;
+        }
+
+        if angle.is_some() || position.is_some() || interpolation.is_some() {
+            input.expect_comma()?;
+        }
+
+        let stops = parse_items(input)?;
+        Ok(ConicGradient {
+            angle: angle.unwrap_or(Angle::Deg(0.0)),
+            position: position.unwrap_or(Position::center()),
+            stops,
+            interpolation: interpolation.unwrap_or_default(),
+            repeating: false,
         })
     }
 }
 
+/// An angle or a percentage of a full turn, used for the stops of a [`ConicGradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnglePercentage {
+    Angle(Angle),
+    Percentage(f32),
+}
+
+impl AnglePercentage {
+    /// Returns the value as a fraction of a full turn.
+    pub fn to_fraction(&self) -> f32 {
+        match self {
+            AnglePercentage::Angle(angle) => angle.to_degrees() / 360.0,
+            AnglePercentage::Percentage(percentage) => percentage / 100.0,
+        }
+    }
+}
+
+impl<'i> Parse<'i> for AnglePercentage {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if let Ok(percentage) = input.try_parse(Percentage::parse) {
+            return Ok(AnglePercentage::Percentage(percentage.0));
+        }
+
+        Angle::parse(input).map(AnglePercentage::Angle)
+    }
+}
+
+impl From<Angle> for AnglePercentage {
+    fn from(angle: Angle) -> Self {
+        AnglePercentage::Angle(angle)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ColorStop<D> {
     pub color: Color,
@@ -226,3 +370,91 @@ impl<D> From<(Color, D)> for ColorStop<D> {
         ColorStop { color: value.0, position: Some(value.1) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+    use crate::{InterpolationColorSpace, Length};
+
+    assert_parse! {
+        Gradient, parse_gradient,
+
+        custom {
+            success {
+                "linear-gradient(in oklch, red, blue)" => Gradient::Linear(LinearGradient {
+                    direction: LineDirection::Vertical(VerticalPositionKeyword::Bottom),
+                    stops: vec![Color::rgb(255, 0, 0).into(), Color::rgb(0, 0, 255).into()],
+                    interpolation: InterpolationColorSpace::Oklch.into(),
+                    repeating: false,
+                }),
+                "repeating-linear-gradient(to right in srgb-linear, red, blue 10px)" => Gradient::Linear(LinearGradient {
+                    direction: LineDirection::Horizontal(HorizontalPositionKeyword::Right),
+                    stops: vec![
+                        Color::rgb(255, 0, 0).into(),
+                        (Color::rgb(0, 0, 255), LengthOrPercentage::Length(Length::px(10.0))).into(),
+                    ],
+                    interpolation: InterpolationColorSpace::SrgbLinear.into(),
+                    repeating: true,
+                }),
+                "repeating-linear-gradient(to right, red 0px, red 5px, blue 5px, blue 10px)" => Gradient::Linear(LinearGradient {
+                    direction: LineDirection::Horizontal(HorizontalPositionKeyword::Right),
+                    stops: vec![
+                        (Color::rgb(255, 0, 0), LengthOrPercentage::Length(Length::px(0.0))).into(),
+                        (Color::rgb(255, 0, 0), LengthOrPercentage::Length(Length::px(5.0))).into(),
+                        (Color::rgb(0, 0, 255), LengthOrPercentage::Length(Length::px(5.0))).into(),
+                        (Color::rgb(0, 0, 255), LengthOrPercentage::Length(Length::px(10.0))).into(),
+                    ],
+                    interpolation: ColorInterpolation::default(),
+                    repeating: true,
+                }),
+                "linear-gradient(to right in hsl, red, blue)" => Gradient::Linear(LinearGradient {
+                    direction: LineDirection::Horizontal(HorizontalPositionKeyword::Right),
+                    stops: vec![Color::rgb(255, 0, 0).into(), Color::rgb(0, 0, 255).into()],
+                    interpolation: InterpolationColorSpace::Hsl.into(),
+                    repeating: false,
+                }),
+                "radial-gradient(at 0% 0%, red 10px, blue 10px)" => Gradient::Radial(RadialGradient {
+                    position: Position::default(),
+                    stops: vec![
+                        (Color::rgb(255, 0, 0), LengthOrPercentage::Length(Length::px(10.0))).into(),
+                        (Color::rgb(0, 0, 255), LengthOrPercentage::Length(Length::px(10.0))).into(),
+                    ],
+                    interpolation: ColorInterpolation::default(),
+                    repeating: false,
+                }),
+                "radial-gradient(at center, red, blue)" => Gradient::Radial(RadialGradient {
+                    position: Position::center(),
+                    stops: vec![Color::rgb(255, 0, 0).into(), Color::rgb(0, 0, 255).into()],
+                    interpolation: ColorInterpolation::default(),
+                    repeating: false,
+                }),
+                "conic-gradient(from 90deg in hsl, red, blue 50%)" => Gradient::Conic(ConicGradient {
+                    angle: Angle::Deg(90.0),
+                    position: Position::center(),
+                    stops: vec![
+                        Color::rgb(255, 0, 0).into(),
+                        (Color::rgb(0, 0, 255), AnglePercentage::Percentage(50.0)).into(),
+                    ],
+                    interpolation: InterpolationColorSpace::Hsl.into(),
+                    repeating: false,
+                }),
+                "repeating-conic-gradient(red, blue 45deg)" => Gradient::Conic(ConicGradient {
+                    angle: Angle::Deg(0.0),
+                    position: Position::center(),
+                    stops: vec![
+                        Color::rgb(255, 0, 0).into(),
+                        (Color::rgb(0, 0, 255), AnglePercentage::Angle(Angle::Deg(45.0))).into(),
+                    ],
+                    interpolation: ColorInterpolation::default(),
+                    repeating: true,
+                }),
+            }
+
+            failure {
+                "conic-gradient(from red, blue)",
+                "linear-gradient(in lab, red, blue)",
+            }
+        }
+    }
+}
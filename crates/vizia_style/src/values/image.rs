@@ -41,6 +41,12 @@ impl From<Gradient> for BackgroundImage<'_> {
     }
 }
 
+impl From<Gradient> for Vec<BackgroundImage<'_>> {
+    fn from(gradient: Gradient) -> Self {
+        vec![BackgroundImage::from(gradient)]
+    }
+}
+
 impl<'i> From<&'i str> for BackgroundImage<'i> {
     fn from(s: &'i str) -> Self {
         let mut input = ParserInput::new(s);
@@ -0,0 +1,26 @@
+use crate::{define_enum, Parse};
+
+define_enum! {
+    /// The color space in which colors are interpolated, such as between the stops of a gradient.
+    #[derive(Default)]
+    pub enum InterpolationColorSpace {
+        /// The sRGB color space.
+        #[default]
+        "srgb": Srgb,
+        /// The sRGB color space with linear light intensity.
+        "srgb-linear": SrgbLinear,
+        /// The perceptually uniform Oklab color space.
+        "oklab": Oklab,
+        /// The polar form of the Oklab color space, with lightness, chroma and hue components.
+        "oklch": Oklch,
+        /// The HSL color space, with hue, saturation and lightness components.
+        "hsl": Hsl,
+    }
+}
+
+impl InterpolationColorSpace {
+    /// Returns true if the color space has a hue component.
+    pub fn is_polar(&self) -> bool {
+        matches!(self, InterpolationColorSpace::Oklch | InterpolationColorSpace::Hsl)
+    }
+}
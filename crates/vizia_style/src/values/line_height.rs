@@ -0,0 +1,101 @@
+use crate::{impl_parse, Length, LengthOrPercentage, Parse};
+use cssparser::*;
+
+/// The height of each line of text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LineHeight {
+    /// The line height is determined by the metrics of the font.
+    #[default]
+    Normal,
+    /// A multiple of the font size.
+    Number(f32),
+    /// A fixed line height, or a percentage of the font size.
+    Length(LengthOrPercentage),
+}
+
+impl LineHeight {
+    /// Returns the line height as a multiple of the given font size, or `None` for the `normal` line height.
+    pub fn to_multiplier(&self, font_size: f32) -> Option<f32> {
+        match self {
+            LineHeight::Normal => None,
+            LineHeight::Number(number) => Some(*number),
+            LineHeight::Length(LengthOrPercentage::Percentage(percentage)) => {
+                Some(*percentage / 100.0)
+            }
+            LineHeight::Length(LengthOrPercentage::Length(length)) => {
+                length.to_px().filter(|_| font_size > 0.0).map(|px| px / font_size)
+            }
+        }
+    }
+}
+
+impl_parse! {
+    LineHeight,
+
+    custom {
+        |input| {
+            let location = input.current_source_location();
+            if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+                return Ok(LineHeight::Normal);
+            }
+
+            if let Ok(number) = input.try_parse(|input| input.expect_number()) {
+                return Ok(LineHeight::Number(number));
+            }
+
+            LengthOrPercentage::parse(input)
+                .map(LineHeight::Length)
+                .map_err(|_| location.new_custom_error(crate::CustomParseError::InvalidValue))
+        }
+    }
+}
+
+impl From<f32> for LineHeight {
+    fn from(number: f32) -> Self {
+        LineHeight::Number(number)
+    }
+}
+
+impl From<Length> for LineHeight {
+    fn from(length: Length) -> Self {
+        LineHeight::Length(LengthOrPercentage::Length(length))
+    }
+}
+
+impl From<LengthOrPercentage> for LineHeight {
+    fn from(length: LengthOrPercentage) -> Self {
+        LineHeight::Length(length)
+    }
+}
+
+impl From<&str> for LineHeight {
+    fn from(s: &str) -> Self {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        LineHeight::parse(&mut parser).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        LineHeight, parse_line_height,
+
+        custom {
+            success {
+                "normal" => LineHeight::Normal,
+                "1.5" => LineHeight::Number(1.5),
+                "20px" => LineHeight::Length(LengthOrPercentage::Length(Length::px(20.0))),
+                "150%" => LineHeight::Length(LengthOrPercentage::Percentage(150.0)),
+            }
+
+            failure {
+                "auto",
+                "red",
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{macros::define_enum, Parse};
+
+define_enum! {
+    /// The type of device a media query applies to.
+    #[derive(Default)]
+    pub enum MediaType {
+        /// All devices.
+        #[default]
+        "all": All,
+        /// Screens, which includes every window of an application.
+        "screen": Screen,
+        /// Printed output, which never matches.
+        "print": Print,
+    }
+}
@@ -1,7 +1,6 @@
 pub mod alignment;
 pub mod alpha;
 pub mod angle;
-pub mod backdrop_filter;
 pub mod background_size;
 pub mod basic;
 pub mod blend_mode;
@@ -9,6 +8,9 @@ pub mod border;
 pub mod calc;
 pub mod clip;
 pub mod color;
+pub mod color_interpolation;
+pub mod color_scheme;
+pub mod content;
 pub mod cursor_icon;
 pub mod custom;
 pub mod dimension_percentage;
@@ -16,6 +18,7 @@ pub mod direction;
 pub mod display;
 pub mod duration;
 pub mod easing;
+pub mod filter;
 pub mod font_family;
 pub mod font_size;
 pub mod font_size_keyword;
@@ -27,12 +30,15 @@ pub mod font_width;
 pub mod gradient;
 pub mod horizontal_position_keyword;
 pub mod image;
+pub mod interpolation_color_space;
 pub mod keywords;
 pub mod layout_type;
 pub mod length;
 pub mod length_or_percentage;
 pub mod length_percentage_auto;
+pub mod line_height;
 pub mod matrix;
+pub mod media_type;
 pub mod number_or_percentage;
 pub mod opacity;
 pub mod outline;
@@ -48,6 +54,7 @@ pub mod stretch;
 pub mod text_align;
 pub mod text_decoration;
 pub mod text_overflow;
+pub mod text_spacing;
 pub mod text_stroke;
 pub mod transform;
 pub mod transition;
@@ -60,7 +67,6 @@ pub mod visibility;
 pub use alignment::*;
 pub use alpha::*;
 pub use angle::*;
-pub use backdrop_filter::*;
 pub use background_size::*;
 pub use basic::*;
 pub use blend_mode::*;
@@ -68,6 +74,9 @@ pub use border::*;
 pub use calc::*;
 pub use clip::*;
 pub use color::*;
+pub use color_interpolation::*;
+pub use color_scheme::*;
+pub use content::*;
 pub use cursor_icon::*;
 pub use custom::*;
 pub use dimension_percentage::*;
@@ -75,6 +84,7 @@ pub use direction::*;
 pub use display::*;
 pub use duration::*;
 pub use easing::*;
+pub use filter::*;
 pub use font_family::*;
 pub use font_size::*;
 pub use font_size_keyword::*;
@@ -86,12 +96,15 @@ pub use font_width::*;
 pub use gradient::*;
 pub use horizontal_position_keyword::*;
 pub use image::*;
+pub use interpolation_color_space::*;
 pub use keywords::*;
 pub use layout_type::*;
 pub use length::*;
 pub use length_or_percentage::*;
 pub use length_percentage_auto::*;
+pub use line_height::*;
 pub use matrix::*;
+pub use media_type::*;
 pub use number_or_percentage::*;
 pub use opacity::*;
 pub use outline::*;
@@ -107,6 +120,7 @@ pub use stretch::*;
 pub use text_align::*;
 pub use text_decoration::*;
 pub use text_overflow::*;
+pub use text_spacing::*;
 pub use text_stroke::*;
 pub use transform::*;
 pub use transition::*;
@@ -0,0 +1,73 @@
+use crate::{impl_parse, Length, Parse};
+use cssparser::*;
+
+/// The extra spacing between letters or words of text, used by the `letter-spacing` and `word-spacing` properties.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum TextSpacing {
+    /// No extra spacing.
+    #[default]
+    Normal,
+    /// A fixed amount of extra spacing, which can be negative.
+    Length(Length),
+}
+
+impl TextSpacing {
+    /// Returns the spacing in pixels.
+    pub fn to_px(&self) -> f32 {
+        match self {
+            TextSpacing::Normal => 0.0,
+            TextSpacing::Length(length) => length.to_px().unwrap_or(0.0),
+        }
+    }
+}
+
+impl_parse! {
+    TextSpacing,
+
+    custom {
+        |input| {
+            if input.try_parse(|input| input.expect_ident_matching("normal")).is_ok() {
+                return Ok(TextSpacing::Normal);
+            }
+
+            Length::parse(input).map(TextSpacing::Length)
+        }
+    }
+}
+
+impl From<Length> for TextSpacing {
+    fn from(length: Length) -> Self {
+        TextSpacing::Length(length)
+    }
+}
+
+impl From<&str> for TextSpacing {
+    fn from(s: &str) -> Self {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        TextSpacing::parse(&mut parser).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        TextSpacing, parse_text_spacing,
+
+        custom {
+            success {
+                "normal" => TextSpacing::Normal,
+                "2px" => TextSpacing::Length(Length::px(2.0)),
+                "-0.5px" => TextSpacing::Length(Length::px(-0.5)),
+            }
+
+            failure {
+                "auto",
+                "50%",
+            }
+        }
+    }
+}
@@ -580,7 +580,19 @@ impl ApplicationHandler<UserEvent> for Application {
 
                 window.window().request_redraw();
             }
-            winit::event::WindowEvent::Ime(_) => {}
+            winit::event::WindowEvent::Ime(ime) => {
+                let event = match ime {
+                    winit::event::Ime::Enabled => WindowEvent::ImeEnabled,
+                    winit::event::Ime::Preedit(text, cursor) => {
+                        WindowEvent::ImePreedit(text, cursor)
+                    }
+                    winit::event::Ime::Commit(text) => WindowEvent::ImeCommit(text),
+                    winit::event::Ime::Disabled => WindowEvent::ImeDisabled,
+                };
+
+                self.cx.emit_window_event(window.entity, event);
+                window.window().request_redraw();
+            }
             winit::event::WindowEvent::CursorMoved { device_id: _, position } => {
                 self.cx.emit_window_event(
                     window.entity,
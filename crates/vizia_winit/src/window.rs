@@ -443,6 +443,13 @@ impl View for Window {
                     .expect("Failed to set cursor position");
             }
 
+            WindowEvent::SetImeCursorArea(area) => {
+                self.window().set_ime_cursor_area(
+                    PhysicalPosition::new(area.x, area.y),
+                    PhysicalSize::new(area.w, area.h),
+                );
+            }
+
             WindowEvent::SetCursor(cursor) => {
                 let Some(icon) = cursor_icon_to_cursor_icon(*cursor) else {
                     self.window().set_cursor_visible(false);
@@ -504,7 +511,8 @@ impl View for Window {
             }
 
             WindowEvent::ReloadStyles => {
-                cx.reload_styles().unwrap();
+                // Stylesheets which fail to read are logged and keep their previous rules.
+                cx.reload_styles().ok();
             }
 
             WindowEvent::WindowClose => {
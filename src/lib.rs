@@ -10,6 +10,9 @@ pub use vizia_baseview::{
     Application, ApplicationError, ParentWindow, WindowHandle, WindowScalePolicy,
 };
 
+#[cfg(feature = "headless")]
+pub use vizia_headless::HeadlessApplication;
+
 pub use vizia_core::*;
 
 #[doc(hidden)]